@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy_ecss::prelude::{Class, EcssPlugin, StyleSheet, StyleSheetAsset};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Same hierarchy styled only by component selectors, which are checked by each rule.
+const COMPONENT_SHEET: &str = r#"
+node .red { width: 100%; }
+node .red node .green { height: 100%; }
+node .green node .blue { width: 50%; }
+node .blue node .purple { height: 50%; }
+node node node .purple { background-color: red; }
+"#;
+
+/// Builds the same hierarchy used by `stress_test` example, styled by the given sheet.
+fn setup(plugin: EcssPlugin, content: &str) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), plugin));
+
+    let sheet = app
+        .world
+        .resource_mut::<Assets<StyleSheetAsset>>()
+        .add(StyleSheetAsset::parse("sheets/stress.css", content));
+
+    let root = app
+        .world
+        .spawn((
+            NodeBundle::default(),
+            Name::new("root"),
+            StyleSheet::new(sheet),
+        ))
+        .with_children(|builder| {
+            for _ in 0..10 {
+                builder
+                    .spawn((NodeBundle::default(), Class::new("red")))
+                    .with_children(|builder| {
+                        for _ in 0..10 {
+                            builder
+                                .spawn((NodeBundle::default(), Class::new("green")))
+                                .with_children(|builder| {
+                                    for _ in 0..10 {
+                                        builder
+                                            .spawn((NodeBundle::default(), Class::new("blue")))
+                                            .with_children(|builder| {
+                                                for _ in 0..10 {
+                                                    builder.spawn((
+                                                        NodeBundle::default(),
+                                                        Class::new("purple"),
+                                                    ));
+                                                }
+                                            });
+                                    }
+                                });
+                        }
+                    });
+            }
+        })
+        .id();
+
+    app.update();
+    app.update();
+
+    (app, root)
+}
+
+fn apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stress_test");
+    group.sample_size(20);
+
+    let stress = include_str!("../assets/sheets/stress.css");
+    for (name, plugin, content) in [
+        ("system_per_property", EcssPlugin::default(), stress),
+        (
+            "single_pass",
+            EcssPlugin::default().single_pass_apply(),
+            stress,
+        ),
+        (
+            "component_selectors",
+            EcssPlugin::default(),
+            COMPONENT_SHEET,
+        ),
+    ] {
+        let (mut app, root) = setup(plugin, content);
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+                app.update();
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, apply);
+criterion_main!(benches);
@@ -1,12 +1,20 @@
 use std::borrow::Cow;
 
-use bevy::prelude::{Component, Deref, Handle, Name, Reflect, ReflectComponent};
+use bevy::{
+    asset::AssetPath,
+    prelude::{
+        AssetServer, Color, Component, Deref, Handle, Name, Reflect, ReflectComponent, Resource,
+    },
+    ui::Val,
+    window::CursorIcon,
+};
 
-use crate::StyleSheetAsset;
+use crate::{property::CalcVal, selector::PseudoElement, StyleSheetAsset};
 
 /// Sets the entities class to be matched by selectors in on`css`.
 ///
 /// The behavior mimics CSS so a single class name can given or a list separated by spaces.
+/// Class names are always kept trimmed, de-duplicated and separated by a single space, on insertion order.
 ///
 /// # Examples
 ///
@@ -28,7 +36,26 @@ impl Class {
     ///
     /// Multiple class names can be used separated by spaces.
     pub fn new(class: impl Into<Cow<'static, str>>) -> Self {
-        Self(class.into())
+        Self(Self::normalize(class.into()))
+    }
+
+    /// Trims and de-duplicates the given class names, keeping the first occurrence of each one.
+    ///
+    /// Class names which are already normalized are kept as is, to avoid allocating.
+    fn normalize(class: Cow<'static, str>) -> Cow<'static, str> {
+        let mut names = Vec::<&str>::new();
+        for name in class.split_ascii_whitespace() {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        let normalized = names.join(" ");
+        if normalized == class {
+            class
+        } else {
+            normalized.into()
+        }
     }
 
     /// Checks if any of this class names matches the given class name
@@ -37,47 +64,49 @@ impl Class {
     }
 
     /// Appends a new class name to this component. If the class name is already
-    /// present, it will be ignored.
+    /// present, it will be ignored. Multiple class names can be used separated by spaces.
     ///
-    /// Note that modifying a class will not automatically trigger the style
-    /// system to reapply the style sheet. If you want to reapply the style
-    /// sheet, you must manually use the [`StyleSheet::refresh`] method.
+    /// Modifying a class automatically reapplies the style sheet of the entity root, unless the root has
+    /// [`NoAutoRestyle`], in which case [`StyleSheet::refresh`] must be called manually.
     ///
     /// This method returns `true` if the class was modified, `false` otherwise.
-    /// You can use this to check if the style sheet needs to be refreshed.
     pub fn add(&mut self, class: &str) -> bool {
-        if self.matches(class) {
-            return false;
-        }
-
-        if self.0.is_empty() {
-            self.0.to_mut().push_str(class);
-        } else {
-            self.0.to_mut().push(' ');
-            self.0.to_mut().push_str(class);
+        let mut modified = false;
+
+        for name in class.split_ascii_whitespace() {
+            if self.matches(name) {
+                continue;
+            }
+
+            if !self.0.is_empty() {
+                self.0.to_mut().push(' ');
+            }
+            self.0.to_mut().push_str(name);
+            modified = true;
         }
 
-        true
+        modified
     }
 
     /// Removes a class name from this component. If the class name is not
-    /// present, it will be ignored.
+    /// present, it will be ignored. Multiple class names can be used separated by spaces.
     ///
-    /// Note that modifying a class will not automatically trigger the style
-    /// system to reapply the style sheet. If you want to reapply the style
-    /// sheet, you must manually use the [`StyleSheet::refresh`] method.
+    /// Modifying a class automatically reapplies the style sheet of the entity root, unless the root has
+    /// [`NoAutoRestyle`], in which case [`StyleSheet::refresh`] must be called manually.
     ///
     /// This method returns `true` if the class was modified, `false` otherwise.
-    /// You can use this to check if the style sheet needs to be refreshed.
     pub fn remove(&mut self, class: &str) -> bool {
-        if !self.matches(class) {
+        if !class
+            .split_ascii_whitespace()
+            .any(|name| self.matches(name))
+        {
             return false;
         }
 
         self.0 = self
             .0
             .split_ascii_whitespace()
-            .filter(move |c| c != &class)
+            .filter(|c| !class.split_ascii_whitespace().any(|name| name == *c))
             .collect::<Vec<_>>()
             .join(" ")
             .into();
@@ -88,14 +117,12 @@ impl Class {
     /// Replaces all class names with the given one as if a new Class component
     /// was created.
     ///
-    /// Note that modifying a class will not automatically trigger the style
-    /// system to reapply the style sheet. If you want to reapply the style
-    /// sheet, you must manually use the [`StyleSheet::refresh`] method.
+    /// Modifying a class automatically reapplies the style sheet of the entity root, unless the root has
+    /// [`NoAutoRestyle`], in which case [`StyleSheet::refresh`] must be called manually.
     ///
     /// This method returns `true` if the class was modified, `false` otherwise.
-    /// You can use this to check if the style sheet needs to be refreshed.
     pub fn set(&mut self, class: impl Into<Cow<'static, str>>) -> bool {
-        let class = class.into();
+        let class = Self::normalize(class.into());
 
         if self.0 == class {
             return false;
@@ -108,9 +135,10 @@ impl Class {
 
 /// Applies a [`StyleSheetAsset`] on the entity which has this component.
 ///
-/// Note that style rules are applied only once when the component is added, or if the asset is changed
-/// and [hot_reloading](https://github.com/bevyengine/bevy/blob/main/examples/asset/hot_asset_reloading.rs) is enabled.
-/// If you want to reapply the stylesheet, like when new children was added, use [`StyleSheet::refresh`].
+/// Note that style rules are applied when the component is added, when new children are added to its hierarchy,
+/// when a matched entity changes or if the asset is changed and
+/// [hot_reloading](https://github.com/bevyengine/bevy/blob/main/examples/asset/hot_asset_reloading.rs) is enabled.
+/// If you want to reapply the stylesheet at any other time, use [`StyleSheet::refresh`].
 ///
 /// # Examples
 ///
@@ -127,19 +155,87 @@ impl Class {
 #[derive(Component, Debug, Default, Reflect)]
 pub struct StyleSheet {
     sheets: Vec<Handle<StyleSheetAsset>>,
+    // Priority of each handle on `sheets`, which is always kept sorted by it.
+    priorities: Vec<i32>,
+    revert_on_removal: bool,
 }
 
 impl StyleSheet {
     /// Creates a new [`StyleSheet`] from the given asset.
     pub fn new(handle: Handle<StyleSheetAsset>) -> Self {
-        Self {
-            sheets: vec![handle],
-        }
+        Self::from_handles(vec![handle])
     }
 
     /// Creates a new [`StyleSheet`] from the given assets.
     pub fn from_handles(handles: Vec<Handle<StyleSheetAsset>>) -> Self {
-        Self { sheets: handles }
+        Self {
+            priorities: vec![0; handles.len()],
+            sheets: handles,
+            revert_on_removal: false,
+        }
+    }
+
+    /// Reverts styled entities when this component is removed or the entity is despawned.
+    ///
+    /// By default, styles applied by a [`StyleSheet`] are kept when it's removed. With this flag, all entities
+    /// matched by it have [`Style`](bevy::prelude::Style), [`BackgroundColor`](bevy::prelude::BackgroundColor) and
+    /// [`BorderColor`](bevy::prelude::BorderColor) reset to the same defaults of a [`NodeBundle`](bevy::prelude::NodeBundle),
+    /// except for [`UiImage`](bevy::prelude::UiImage) entities which keeps a white background, so images aren't tinted.
+    /// Other components, like [`Text`](bevy::prelude::Text), are kept untouched.
+    ///
+    /// Other style sheets which also matched those entities are reapplied afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// use bevy_ecss::prelude::*;
+    ///
+    /// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+    ///     commands.spawn(StyleSheet::new(asset_server.load("sheets/popup.css")).revert_on_removal());
+    /// }
+    /// ```
+    pub fn revert_on_removal(mut self) -> Self {
+        self.revert_on_removal = true;
+        self
+    }
+
+    /// Checks if styled entities are reverted when this component is removed.
+    ///
+    /// Check [`StyleSheet::revert_on_removal`] for more.
+    pub fn reverts_on_removal(&self) -> bool {
+        self.revert_on_removal
+    }
+
+    /// Creates a new [`StyleSheet`] by loading each given path using the [`AssetServer`].
+    ///
+    /// Handles are kept in the same order of the given paths, which is also the priority order
+    /// when applying the style sheets, so rules on later paths overrides rules on earlier ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// use bevy_ecss::prelude::*;
+    ///
+    /// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+    ///     // Rules on "theme.css" will take precedence over rules on "base.css"
+    ///     commands.spawn(StyleSheet::from_paths(
+    ///         &asset_server,
+    ///         ["sheets/base.css", "sheets/theme.css"],
+    ///     ));
+    /// }
+    /// ```
+    pub fn from_paths<'a, P>(asset_server: &AssetServer, paths: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<AssetPath<'a>>,
+    {
+        Self::from_handles(
+            paths
+                .into_iter()
+                .map(|path| asset_server.load(path))
+                .collect(),
+        )
     }
 
     /// Reapplies the style sheet on entity and all children.
@@ -158,11 +254,19 @@ impl StyleSheet {
         self.sheets.first().unwrap()
     }
 
-    /// Internal [`StyleSheetAsset`] handle
+    /// Internal [`StyleSheetAsset`] handles, in the order they are applied.
     pub fn handles(&self) -> &[Handle<StyleSheetAsset>] {
         &self.sheets
     }
 
+    /// Priority of the given [`StyleSheetAsset`] handle, or `None` if it isn't present.
+    pub fn priority(&self, handle: &Handle<StyleSheetAsset>) -> Option<i32> {
+        self.sheets
+            .iter()
+            .position(|h| h == handle)
+            .map(|index| self.priorities[index])
+    }
+
     /// Change the internal [`StyleSheetAsset`] handle.
     /// This will automatically trigger the systems to reapply the style sheet.
     ///
@@ -172,14 +276,75 @@ impl StyleSheet {
     #[deprecated(since = "0.6.0", note = "Use `set_handles` instead")]
     pub fn set(&mut self, handle: Handle<StyleSheetAsset>) {
         assert_eq!(self.sheets.len(), 1, "Use `set_handles` instead");
-        self.sheets = vec![handle];
+        self.set_handles(vec![handle]);
     }
 
-    /// Change the internal [`StyleSheetAsset`] list of handles.
+    /// Change the internal [`StyleSheetAsset`] list of handles, all of them with the default `0` priority.
     /// This will automatically trigger the systems to reapply the style sheet.
     pub fn set_handles(&mut self, handles: Vec<Handle<StyleSheetAsset>>) {
+        self.priorities = vec![0; handles.len()];
         self.sheets = handles;
     }
+
+    /// Appends a [`StyleSheetAsset`] handle with the default `0` priority, which will have the highest priority
+    /// among handles with the same priority when applying the style sheets.
+    /// This will automatically trigger the systems to reapply the style sheet.
+    ///
+    /// If the handle is already present, it's kept on its current position and this method returns `false`.
+    pub fn push_handle(&mut self, handle: Handle<StyleSheetAsset>) -> bool {
+        self.push_handle_with_priority(handle, 0)
+    }
+
+    /// Adds a [`StyleSheetAsset`] handle with the given priority.
+    /// This will automatically trigger the systems to reapply the style sheet.
+    ///
+    /// Handles are applied by priority, so rules on handles with higher priority overrides rules on handles
+    /// with lower priority, regardless of insertion order. Handles with the same priority are applied in
+    /// insertion order. This is useful to mix a shared base theme with a per-screen override.
+    ///
+    /// If the handle is already present, it's kept on its current position and this method returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// use bevy_ecss::prelude::*;
+    ///
+    /// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+    ///     let mut sheet = StyleSheet::new(asset_server.load("sheets/screen.css"));
+    ///     // Rules on "screen.css" will still take precedence over rules on "theme.css"
+    ///     sheet.push_handle_with_priority(asset_server.load("sheets/theme.css"), -1);
+    ///     commands.spawn(sheet);
+    /// }
+    /// ```
+    pub fn push_handle_with_priority(
+        &mut self,
+        handle: Handle<StyleSheetAsset>,
+        priority: i32,
+    ) -> bool {
+        if self.sheets.contains(&handle) {
+            return false;
+        }
+
+        let index = self.priorities.partition_point(|&p| p <= priority);
+        self.sheets.insert(index, handle);
+        self.priorities.insert(index, priority);
+        true
+    }
+
+    /// Removes a [`StyleSheetAsset`] handle, keeping the order of remaining handles.
+    /// This will automatically trigger the systems to reapply the style sheet.
+    ///
+    /// This method returns `true` if the handle was removed, `false` if it wasn't present.
+    pub fn remove_handle(&mut self, handle: &Handle<StyleSheetAsset>) -> bool {
+        let Some(index) = self.sheets.iter().position(|h| h == handle) else {
+            return false;
+        };
+
+        self.sheets.remove(index);
+        self.priorities.remove(index);
+        true
+    }
 }
 
 impl PartialEq for StyleSheet {
@@ -188,6 +353,329 @@ impl PartialEq for StyleSheet {
     }
 }
 
+/// A [`StyleSheetAsset`] applied on every [`StyleSheet`] root, like a base "reset" style sheet.
+///
+/// The default style sheet is applied before the root own style sheets, so it has the lowest priority
+/// and any rule on the root style sheets overrides it. Roots which already lists the same handle
+/// on its [`StyleSheet`] doesn't apply it twice.
+///
+/// All roots are reapplied when this resource is inserted, changed or removed.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::prelude::*;
+///
+/// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.insert_resource(DefaultStyleSheet::new(asset_server.load("sheets/reset.css")));
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct DefaultStyleSheet(Handle<StyleSheetAsset>);
+
+impl DefaultStyleSheet {
+    /// Creates a new [`DefaultStyleSheet`] from the given asset.
+    pub fn new(handle: Handle<StyleSheetAsset>) -> Self {
+        Self(handle)
+    }
+
+    /// Internal [`StyleSheetAsset`] handle
+    pub fn handle(&self) -> &Handle<StyleSheetAsset> {
+        &self.0
+    }
+}
+
+/// A [`StyleSheetAsset`] which rules are selected against all [`Node`](bevy::ui::Node) entities, regardless of hierarchy.
+///
+/// This is useful for apps with a single style sheet and a flat UI, where attaching a [`StyleSheet`] on a root
+/// isn't convenient. Rules of this sheet have lower priority than any [`StyleSheet`] rule.
+///
+/// Since selectors are matched against every node in the world, the whole sheet is applied again whenever any node
+/// is spawned, reparented or has its [`Class`] changed, so prefer [`StyleSheet`] on large or frequently changing UIs.
+/// Pseudo-elements, like `::before`, aren't supported on global style sheets.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::prelude::*;
+///
+/// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.insert_resource(GlobalStyleSheet::new(asset_server.load("sheets/global.css")));
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct GlobalStyleSheet(Handle<StyleSheetAsset>);
+
+impl GlobalStyleSheet {
+    /// Creates a new [`GlobalStyleSheet`] from the given asset.
+    pub fn new(handle: Handle<StyleSheetAsset>) -> Self {
+        Self(handle)
+    }
+
+    /// Internal [`StyleSheetAsset`] handle
+    pub fn handle(&self) -> &Handle<StyleSheetAsset> {
+        &self.0
+    }
+
+    /// Reapplies the global style sheet on all nodes.
+    pub fn refresh(&mut self) {
+        // Just to trigger DerefMut
+    }
+}
+
+/// Opts a [`StyleSheet`] root entity out of automatic reapplication when a tracked entity changes.
+///
+/// By default, whenever any component used by a selector changes on a matched entity, like a [`Class`],
+/// the whole style sheet is applied again. On large and frequently changing trees this can be costly,
+/// so roots with this component are only applied again when [`StyleSheet::refresh`] is called manually.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::prelude::*;
+///
+/// fn setup(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.spawn((
+///         StyleSheet::new(asset_server.load("sheets/big_list.css")),
+///         NoAutoRestyle,
+///     ));
+/// }
+/// ```
+#[derive(Debug, Component, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct NoAutoRestyle;
+
+/// Marker component inserted on [`StyleSheet`] entities once all of its style sheets are loaded and it's
+/// rules were selected, so properties are applied on the same frame.
+///
+/// It's removed when the [`StyleSheet`] is changed to a style sheet not loaded yet or removed,
+/// which is useful to delay revealing UI until it's styled.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::prelude::*;
+///
+/// fn reveal_menu(mut q_menus: Query<&mut Visibility, Added<StyleSheetApplied>>) {
+///     for mut visibility in &mut q_menus {
+///         *visibility = Visibility::Inherited;
+///     }
+/// }
+/// ```
+#[derive(Debug, Component, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct StyleSheetApplied;
+
+/// Holds the [`CursorIcon`] to be shown on primary window while this entity is hovered.
+///
+/// This component is inserted by `cursor` property, but it can also be added manually. Only entities which
+/// also has an [`Interaction`](bevy::ui::Interaction) component will change the cursor icon.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Deref)]
+pub struct CssCursor(pub CursorIcon);
+
+/// Controls how [`Text`](bevy::text::Text) which doesn't fit on its [`Node`](bevy::ui::Node) width is shown.
+///
+/// This component is inserted by `text-overflow` property, but it can also be added manually.
+/// Only single line texts are truncated, so it's meant to be used along with `white-space: nowrap;`.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum TextOverflow {
+    /// Text is kept as it is, overflowing the node.
+    #[default]
+    Clip,
+    /// Text is truncated and an ellipsis (`…`) is added at the end, so it fits the node width.
+    ///
+    /// Truncated characters aren't restored if the node grows later, unless the text is set again.
+    Ellipsis,
+}
+
+/// Controls how an [`UiImage`](bevy::ui::UiImage) is sized inside its [`Node`](bevy::ui::Node).
+///
+/// This component is inserted by `object-fit` property, but it can also be added manually.
+///
+/// Bevy UI always stretches the image to the node size and can't crop nor letterbox it, so `contain` and `cover`
+/// are approximated by setting [`Style::aspect_ratio`](bevy::ui::Style) to the image aspect ratio. This way the image
+/// keeps its proportions, but the node size is adjusted instead of the image.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum ObjectFit {
+    /// Image is stretched to fill the node, which is the Bevy default behavior.
+    #[default]
+    Fill,
+    /// Image keeps its aspect ratio, by setting the node aspect ratio to the image one.
+    Contain,
+    /// Same as [`ObjectFit::Contain`], since Bevy can't crop UI images.
+    Cover,
+}
+
+/// Limits how many lines of [`Text`](bevy::text::Text) are shown.
+///
+/// This component is inserted by `max-lines` property, but it can also be added manually. Texts which wraps
+/// into more lines are truncated and an ellipsis (`…`) is added at the end of the last visible line.
+///
+/// Lines are found using the [`TextLayoutInfo`](bevy::text::TextLayoutInfo) computed by Bevy text layout, so truncation
+/// happens on the frame after the text was laid out. Like [`TextOverflow`], truncated characters aren't restored if
+/// the node grows later, unless the text is set again.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq, Deref, Reflect)]
+#[reflect(Component)]
+pub struct MaxLines(pub usize);
+
+/// Holds `calc(...)` expressions of `width` and `height` properties which can't be represented by a [`Val`](bevy::ui::Val).
+///
+/// This component is inserted by `width` and `height` properties, when needed, and an internal system resolves
+/// each expression to [`Val::Px`](bevy::ui::Val) every frame, like `calc(100% - 20px)`. Percentages are relative
+/// to the parent [`Node`](bevy::ui::Node) size, or to the primary window size on root nodes.
+///
+/// Since the parent size is computed by Bevy UI layout, the resolved value uses the parent size of the last layout.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct CssCalc {
+    /// Expression of `width` property, if any.
+    pub width: Option<CalcVal>,
+    /// Expression of `height` property, if any.
+    pub height: Option<CalcVal>,
+}
+
+/// Holds colors of an entity before the `opacity` property was applied, so applying the style sheet again doesn't
+/// fade those colors once more.
+///
+/// This component is inserted by `opacity` property. When a color is written by anything else after opacity was
+/// applied, like by a `background-color` rule or by user code, the written color is used as the new base color.
+#[derive(Debug, Component, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct CssOpacity {
+    /// Applied opacity, between `0` and `1`.
+    pub opacity: f32,
+    /// [`BackgroundColor`](bevy::ui::BackgroundColor) before opacity was applied, if any.
+    pub background: Option<Color>,
+    /// Color of each [`Text`](bevy::text::Text) section before opacity was applied.
+    pub sections: Vec<Color>,
+}
+
+impl Default for CssOpacity {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            background: None,
+            sections: Vec::new(),
+        }
+    }
+}
+
+impl CssOpacity {
+    /// Returns the given `color` with its alpha multiplied by the applied opacity.
+    pub fn fade(&self, color: Color) -> Color {
+        color.with_a(color.a() * self.opacity)
+    }
+
+    /// Returns the color before opacity was applied, given the current `color` and the stored `base` color.
+    ///
+    /// If `color` isn't the faded `base` color anymore, it was written after opacity was applied, so it's returned.
+    pub(crate) fn base_color(&self, color: Color, base: Option<Color>) -> Color {
+        base.filter(|base| self.fade(*base) == color)
+            .unwrap_or(color)
+    }
+}
+
+/// Scroll offset of a [`Node`](bevy::ui::Node), in logical pixels.
+///
+/// This component is inserted by `scroll-x` and `scroll-y` properties, but it can also be added manually.
+/// Bevy 0.13 has no scroll position on UI nodes, so an internal system moves the children of the node by the
+/// offset after each layout update, like `scroll-y: 20px;` moves all children 20 pixels up. Use it along with
+/// `overflow: hidden;` to clip the scrolled content.
+///
+/// Since the offset is applied on the [`Transform`](bevy::prelude::Transform) of children, it relies on Bevy UI
+/// layout to reset their translation on each update, and it isn't clamped to the content size.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ScrollPosition {
+    /// How many pixels the content is scrolled to the left.
+    pub offset_x: f32,
+    /// How many pixels the content is scrolled up.
+    pub offset_y: f32,
+}
+
+/// A `linear-gradient(...)` parsed from the `background` property.
+///
+/// This component is inserted by `background` property, but nothing is rendered by `bevy_ecss`, since
+/// Bevy doesn't support gradients yet. Until it does, rendering the gradient is the user's responsibility,
+/// like by using a custom material which reads this component.
+///
+/// # Examples
+///
+/// ```css
+/// #panel {
+///     background: linear-gradient(to right, red, blue 80%);
+/// }
+/// ```
+#[derive(Debug, Component, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct CssGradient {
+    /// Gradient line angle in radians, clockwise where `0` points up and `PI` points down, like in CSS.
+    pub angle: f32,
+    /// Color stops with their optional position along the gradient line, in `0.0..=1.0` range.
+    pub stops: Vec<(Color, Option<f32>)>,
+}
+
+impl Default for CssGradient {
+    fn default() -> Self {
+        Self {
+            angle: std::f32::consts::PI,
+            stops: Vec::new(),
+        }
+    }
+}
+
+/// A `box-shadow` parsed from the style sheet, like `box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);`.
+///
+/// This component is inserted by `box-shadow` property, but nothing is rendered by `bevy_ecss`, since
+/// Bevy doesn't support box shadows yet. Until it does, rendering the shadow is the user's responsibility,
+/// like by spawning a blurred node behind the entity. Only a single shadow is supported.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct CssBoxShadow {
+    /// Horizontal offset, where positive values moves the shadow to the right.
+    pub offset_x: Val,
+    /// Vertical offset, where positive values moves the shadow down.
+    pub offset_y: Val,
+    /// Blur radius, which is zero when not given.
+    pub blur: Val,
+    /// Spread radius, which is zero when not given.
+    pub spread: Val,
+    /// Shadow color, which is black when not given.
+    pub color: Color,
+    /// If the shadow is drawn inside the node border, like `inset 0 2px red`.
+    pub inset: bool,
+}
+
+impl Default for CssBoxShadow {
+    fn default() -> Self {
+        Self {
+            offset_x: Val::Px(0.0),
+            offset_y: Val::Px(0.0),
+            blur: Val::Px(0.0),
+            spread: Val::Px(0.0),
+            color: Color::BLACK,
+            inset: false,
+        }
+    }
+}
+
+/// Marks a child text entity spawned to render a [`PseudoElement`] like `::before` or `::after`.
+///
+/// Pseudo-element entities follows this lifecycle:
+/// - They are spawned once, when a rule with a pseudo-element and a `content` property matches an entity.
+///   `::before` is inserted as the first child and `::after` as the last child of the matched entity.
+/// - Every time the style sheet is applied again, the existing entity is reused and all properties of the rule,
+///   including `content`, are applied on it.
+/// - When the style sheet is applied again and the rule no longer matches, the entity is despawned.
+///
+/// Pseudo-element entities are never matched by regular selectors, only by selectors ending with a pseudo-element.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Deref)]
+pub struct PseudoElementNode(pub PseudoElement);
+
 /// Convenience trait which matches matches a component against a named element selector.
 pub(crate) trait MatchSelectorElement {
     fn matches(&self, element: &str) -> bool;
@@ -200,8 +688,12 @@ impl MatchSelectorElement for Class {
 }
 
 impl MatchSelectorElement for Name {
+    /// Matches the exact name, unless the element ends with `*`, like `item-*`, in which case it matches by prefix.
     fn matches(&self, element: &str) -> bool {
-        self.as_str() == element
+        match element.strip_suffix('*') {
+            Some(prefix) => self.as_str().starts_with(prefix),
+            None => self.as_str() == element,
+        }
     }
 }
 
@@ -229,5 +721,89 @@ mod tests {
 
         assert!(!class.set("blue-button enabled"));
         assert_eq!(class.0, "blue-button enabled");
+
+        let mut class = Class::new("  a   b a\tc ");
+        assert_eq!(class.0, "a b c", "Should be trimmed and de-duplicated");
+
+        assert!(class.add(" d  a "));
+        assert_eq!(class.0, "a b c d");
+
+        assert!(!class.add("  "));
+        assert!(!class.add("b c"));
+        assert_eq!(class.0, "a b c d");
+
+        assert!(class.remove(" b  d "));
+        assert_eq!(class.0, "a c");
+
+        assert!(!class.set(" a  c a "), "Should be the same once normalized");
+        assert_eq!(class.0, "a c");
+
+        assert!(class.set("c b c"));
+        assert_eq!(class.0, "c b", "Should keep insertion order");
+    }
+
+    #[test]
+    fn order_handles_by_priority() {
+        let (base, screen, theme, overlay) = (
+            Handle::weak_from_u128(1),
+            Handle::weak_from_u128(2),
+            Handle::weak_from_u128(3),
+            Handle::weak_from_u128(4),
+        );
+
+        let mut sheet = StyleSheet::new(screen.clone());
+        assert!(sheet.push_handle_with_priority(overlay.clone(), 10));
+        assert!(sheet.push_handle_with_priority(base.clone(), -1));
+        assert!(sheet.push_handle(theme.clone()));
+        assert_eq!(
+            sheet.handles(),
+            [base.clone(), screen.clone(), theme.clone(), overlay.clone()]
+        );
+        assert_eq!(sheet.priority(&overlay), Some(10));
+        assert_eq!(sheet.priority(&theme), Some(0));
+
+        assert!(!sheet.push_handle_with_priority(base.clone(), 20));
+        assert_eq!(sheet.priority(&base), Some(-1));
+
+        assert!(sheet.remove_handle(&screen));
+        assert_eq!(sheet.priority(&screen), None);
+        assert_eq!(sheet.handles(), [base, theme, overlay.clone()]);
+        assert_eq!(sheet.priority(&overlay), Some(10));
+    }
+
+    #[test]
+    fn modify_handles() {
+        let (a, b, c) = (
+            Handle::weak_from_u128(1),
+            Handle::weak_from_u128(2),
+            Handle::weak_from_u128(3),
+        );
+
+        let mut sheet = StyleSheet::new(a.clone());
+        assert!(sheet.push_handle(b.clone()));
+        assert!(sheet.push_handle(c.clone()));
+        assert_eq!(sheet.handles(), [a.clone(), b.clone(), c.clone()]);
+
+        assert!(
+            !sheet.push_handle(a.clone()),
+            "Duplicates should be ignored"
+        );
+        assert_eq!(sheet.handles(), [a.clone(), b.clone(), c.clone()]);
+
+        assert!(sheet.remove_handle(&b));
+        assert_eq!(sheet.handles(), [a.clone(), c.clone()]);
+
+        assert!(!sheet.remove_handle(&b));
+        assert_eq!(sheet.handles(), [a, c]);
+    }
+
+    #[test]
+    fn match_name_by_prefix() {
+        let name = Name::new("right-item-12");
+        assert!(name.matches("right-item-12"));
+        assert!(!name.matches("right-item-1"), "Should match exact name");
+        assert!(name.matches("right-item-*"));
+        assert!(name.matches("right-item-1*"));
+        assert!(!name.matches("left-item-*"));
     }
 }
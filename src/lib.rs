@@ -7,36 +7,63 @@ mod selector;
 mod stylesheet;
 mod system;
 
-use std::{error::Error, fmt::Display};
+use std::{any::TypeId, error::Error, fmt::Display};
 
 use bevy::{
     asset::AssetEvents,
-    ecs::system::SystemState,
+    ecs::{
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        system::SystemState,
+    },
+    log::warn,
     prelude::{
-        AssetApp, Button, Component, Entity, IntoSystemConfigs, IntoSystemSetConfigs, Plugin,
-        PostUpdate, PreUpdate, Query, SystemSet, With,
+        apply_deferred, AssetApp, Assets, Button, Component, Entity, IntoSystemConfigs,
+        IntoSystemSetConfigs, Name, Plugin, PostUpdate, PreUpdate, Query, Resource, SystemSet,
+        With, World,
     },
+    reflect::GetTypeRegistration,
     text::Text,
-    ui::{BackgroundColor, Interaction, Node, Style, UiImage},
+    transform::TransformSystem,
+    ui::{BackgroundColor, Interaction, Node, Style, UiImage, UiSystem},
 };
 
-use property::StyleSheetState;
+use property::{single_pass::PropertyAppliers, StyleSheetState};
 use stylesheet::StyleSheetLoader;
 
-use system::{ComponentFilterRegistry, PrepareParams};
+use system::{AttributeSelector, ComponentFilterRegistry, PrepareParams, PseudoClassSelector};
 
-pub use component::{Class, StyleSheet};
-pub use property::{Property, PropertyToken, PropertyValues};
-pub use selector::{Selector, SelectorElement};
-pub use stylesheet::{StyleRule, StyleSheetAsset};
+pub use component::{
+    Class, CssBoxShadow, CssCalc, CssCursor, CssGradient, CssOpacity, DefaultStyleSheet,
+    GlobalStyleSheet, MaxLines, NoAutoRestyle, ObjectFit, PseudoElementNode, ScrollPosition,
+    StyleSheet, StyleSheetApplied, TextOverflow,
+};
+pub use property::{
+    transition::{AnimatableProperty, Lerp, Transitions},
+    CalcVal, FontRegistry, Property, PropertyToken, PropertyValues, RegisteredProperties,
+    StyleQuery,
+};
+pub use selector::{
+    AttributeOperator, MediaFeature, MediaQuery, PseudoElement, Selector, SelectorElement,
+    Specificity,
+};
+pub use stylesheet::{FontFace, PropertyDiff, RuleDiff, StyleRule, StyleSheetAsset};
+pub use system::CssSelect;
 
 /// use `bevy_ecss::prelude::*;` to import common components, and plugins and utility functions.
 pub mod prelude {
-    pub use super::component::{Class, StyleSheet};
+    pub use super::component::{
+        Class, CssBoxShadow, CssCalc, CssCursor, CssGradient, CssOpacity, DefaultStyleSheet,
+        GlobalStyleSheet, MaxLines, NoAutoRestyle, ObjectFit, ScrollPosition, StyleSheet,
+        StyleSheetApplied, TextOverflow,
+    };
     pub use super::stylesheet::StyleSheetAsset;
+    pub use super::CssSelect;
     pub use super::EcssPlugin;
+    pub use super::FontRegistry;
     pub use super::RegisterComponentSelector;
     pub use super::RegisterProperty;
+    pub use super::RegisterPseudoClass;
+    pub use super::StyleQuery;
 }
 
 /// Errors which can happens while parsing `css` into [`Selector`] or [`Property`].
@@ -45,14 +72,40 @@ pub mod prelude {
 pub enum EcssError {
     /// An unsupported selector was found on a style sheet rule.
     UnsupportedSelector,
-    /// An unsupported property was found on a style sheet rule.
+    /// An unsupported property was found on a style sheet rule. Holds the property name.
     UnsupportedProperty(String),
-    /// An invalid property value was found on a style sheet rule.
+    /// An invalid property value was found on a style sheet rule. Holds the property name.
     InvalidPropertyValue(String),
+    /// A valid `css` value which isn't supported by Bevy was found on a style sheet rule, like `position: sticky`.
+    UnsupportedPropertyValue {
+        /// Property name, like `position`.
+        property: String,
+        /// Unsupported value, like `sticky`.
+        value: String,
+    },
     /// An invalid selector was found on a style sheet rule.
     InvalidSelector,
     /// An unexpected token was found on a style sheet rule.
     UnexpectedToken(String),
+    /// A `@font-face` rule without `font-family` or `src` descriptors was found on a style sheet.
+    InvalidFontFace,
+}
+
+impl EcssError {
+    /// The name of the property which failed to be parsed, if this error is related to a property.
+    ///
+    /// This can be used to handle errors of a specific property without matching the [`Display`] output.
+    pub fn property(&self) -> Option<&str> {
+        match self {
+            EcssError::UnsupportedProperty(property)
+            | EcssError::InvalidPropertyValue(property)
+            | EcssError::UnsupportedPropertyValue { property, .. } => Some(property),
+            EcssError::UnsupportedSelector
+            | EcssError::InvalidSelector
+            | EcssError::UnexpectedToken(_)
+            | EcssError::InvalidFontFace => None,
+        }
+    }
 }
 
 impl Error for EcssError {}
@@ -65,8 +118,17 @@ impl Display for EcssError {
             }
             EcssError::UnsupportedProperty(p) => write!(f, "Unsupported property: {}", p),
             EcssError::InvalidPropertyValue(p) => write!(f, "Invalid property value: {}", p),
+            EcssError::UnsupportedPropertyValue { property, value } => {
+                write!(f, "Unsupported property value: {}: {}", property, value)
+            }
             EcssError::InvalidSelector => write!(f, "Invalid selector"),
             EcssError::UnexpectedToken(t) => write!(f, "Unexpected token: {}", t),
+            EcssError::InvalidFontFace => {
+                write!(
+                    f,
+                    "Invalid @font-face: both font-family and src are required"
+                )
+            }
         }
     }
 }
@@ -74,6 +136,9 @@ impl Display for EcssError {
 struct EcssHotReload;
 
 /// System sets  used by `bevy_ecss` systems
+///
+/// All sets, except [`EcssSet::Cleanup`], runs on [`PreUpdate`] schedule by default, which can be changed
+/// with [`EcssPlugin::in_schedule`]. Those sets can also be used to order your own systems.
 #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum EcssSet {
     /// Checks if any entity affected by some style sheet was changed.
@@ -85,6 +150,12 @@ pub enum EcssSet {
     /// All [`Property`] implementation `systems` are run on this system set.
     /// Those stages runs on [`PreUpdate`] schedule after [`EcssSet::Prepare`].
     Apply,
+    /// Properties which depends on values written by others, like `opacity`, are run on this system set.
+    /// This system set runs after [`EcssSet::Apply`], once commands issued by it are applied.
+    LateApply,
+    /// All [`AnimatableProperty`] animation `systems` are run on this system set.
+    /// This system set runs on [`PreUpdate`] schedule after [`EcssSet::LateApply`].
+    Transition,
     /// Clears the internal state used by [`Property`] implementation `systems` set.
     /// This system runs on [`PostUpdate`] schedule.
     Cleanup,
@@ -92,14 +163,77 @@ pub enum EcssSet {
 
 /// Plugin which add all types, assets, systems and internal resources needed by `bevy_ecss`.
 /// You must add this plugin in order to use `bevy_ecss`.
-#[derive(Default)]
 pub struct EcssPlugin {
     hot_reload: bool,
+    extensions: Vec<&'static str>,
+    schedule: InternedScheduleLabel,
+    single_pass: bool,
+}
+
+impl Default for EcssPlugin {
+    fn default() -> Self {
+        Self {
+            hot_reload: false,
+            extensions: vec!["css"],
+            schedule: PreUpdate.intern(),
+            single_pass: false,
+        }
+    }
+}
+
+/// Holds the schedule which [`EcssSet`] sets runs on, so properties registered
+/// after [`EcssPlugin`] are added on the same schedule.
+#[derive(Resource, Clone, Copy)]
+struct EcssSchedule(InternedScheduleLabel);
+
+impl Default for EcssSchedule {
+    fn default() -> Self {
+        Self(PreUpdate.intern())
+    }
 }
 
 impl EcssPlugin {
     pub fn with_hot_reload() -> EcssPlugin {
-        EcssPlugin { hot_reload: true }
+        EcssPlugin {
+            hot_reload: true,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a plugin which loads [`StyleSheetAsset`] from files with any of the given extensions, like `&["css", "ecss"]`.
+    ///
+    /// By default only `css` files are loaded, so it must be included if those files should still be loaded.
+    pub fn with_extensions(extensions: &[&'static str]) -> EcssPlugin {
+        EcssPlugin {
+            extensions: extensions.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Runs style sheets preparation, properties and transitions systems on the given schedule, instead of [`PreUpdate`].
+    ///
+    /// This is useful when UI systems are driven by another schedule, like `FixedUpdate`, to avoid a frame of lag.
+    /// [`EcssSet::Cleanup`] still runs on [`PostUpdate`], so the given schedule should run before it.
+    ///
+    /// Properties must be registered after adding this plugin in order to run on the given schedule.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> EcssPlugin {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Applies all properties with a single system, instead of one system per [`Property`].
+    ///
+    /// By default each property runs on its own system, which walks all selected rules every time style sheets
+    /// are applied. On large scenes with many rules, this mode walks the selected rules only once and applies
+    /// only the properties used by them, in registration order.
+    ///
+    /// Note that [`Property::apply_system`] isn't used on this mode, so properties which overrides it only calls
+    /// [`Property::apply`], like `color`, which ignores `inherit`, `border-color`, which doesn't warn about missing
+    /// border width, and `debug`, which logs matched entities without their rule. Properties must be registered
+    /// after adding this plugin.
+    pub fn single_pass_apply(mut self) -> EcssPlugin {
+        self.single_pass = true;
+        self
     }
 }
 
@@ -107,32 +241,112 @@ impl Plugin for EcssPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.register_type::<Class>()
             .register_type::<StyleSheet>()
+            .register_type::<StyleSheetApplied>()
+            .register_type::<NoAutoRestyle>()
+            .register_type::<TextOverflow>()
+            .register_type::<MaxLines>()
+            .register_type::<CssCalc>()
+            .register_type::<ObjectFit>()
+            .register_type::<CssGradient>()
+            .register_type::<CssBoxShadow>()
+            .register_type::<CssOpacity>()
+            .register_type::<ScrollPosition>()
             .init_asset::<StyleSheetAsset>()
+            .insert_resource(EcssSchedule(self.schedule))
             .configure_sets(
-                PreUpdate,
-                (EcssSet::Prepare, EcssSet::ChangeDetection, EcssSet::Apply).chain(),
+                self.schedule,
+                (
+                    EcssSet::Prepare,
+                    EcssSet::ChangeDetection,
+                    EcssSet::Apply,
+                    EcssSet::LateApply,
+                    EcssSet::Transition,
+                )
+                    .chain(),
             )
             .configure_sets(PostUpdate, EcssSet::Cleanup)
             .init_resource::<StyleSheetState>()
+            .init_resource::<StyleQuery>()
+            .init_resource::<RegisteredProperties>()
             .init_resource::<ComponentFilterRegistry>()
-            .init_asset_loader::<StyleSheetLoader>()
-            .add_systems(PreUpdate, system::prepare.in_set(EcssSet::Prepare))
+            .init_resource::<FontRegistry>()
+            .register_asset_loader(StyleSheetLoader::new(self.extensions.clone()))
             .add_systems(
-                PreUpdate,
+                self.schedule,
+                (
+                    system::watch_default_style_sheet.before(EcssSet::Prepare),
+                    system::watch_global_style_sheet.before(EcssSet::Prepare),
+                    system::watch_window_size.before(EcssSet::Prepare),
+                    system::watch_changed_classes.before(EcssSet::Prepare),
+                    system::watch_hierarchy_changes.before(EcssSet::Prepare),
+                    system::unload_removed_style_sheets.before(EcssSet::Prepare),
+                    system::prepare.in_set(EcssSet::Prepare),
+                ),
+            )
+            .add_systems(
+                self.schedule,
                 system::watch_tracked_entities.in_set(EcssSet::ChangeDetection),
             )
-            .add_systems(PostUpdate, system::clear_state.in_set(EcssSet::Cleanup));
+            .add_systems(
+                self.schedule,
+                property::reflect::apply_reflect_properties.in_set(EcssSet::Apply),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    apply_deferred
+                        .after(EcssSet::Apply)
+                        .before(EcssSet::LateApply),
+                    system::resolve_font_families.in_set(EcssSet::LateApply),
+                ),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    system::update_cursor_icon,
+                    system::truncate_text_overflow,
+                    system::clamp_text_lines,
+                    system::resolve_css_calc,
+                    system::fit_images,
+                )
+                    .after(EcssSet::Apply),
+            )
+            .add_systems(PostUpdate, system::clear_state.in_set(EcssSet::Cleanup))
+            .add_systems(
+                PostUpdate,
+                system::scroll_nodes
+                    .after(UiSystem::Layout)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                AssetEvents,
+                (
+                    system::refresh_loaded_style_sheets,
+                    system::register_font_faces,
+                    system::warn_unknown_properties,
+                )
+                    .after(Assets::<StyleSheetAsset>::asset_events),
+            );
 
         let prepared_state = PrepareParams::new(&mut app.world);
         app.insert_resource(prepared_state);
 
+        if self.single_pass {
+            app.init_resource::<PropertyAppliers>().add_systems(
+                self.schedule,
+                property::single_pass::apply_properties.in_set(EcssSet::Apply),
+            );
+        }
+
         register_component_selector(app);
         register_properties(app);
 
         if self.hot_reload {
             app.configure_sets(AssetEvents, EcssHotReload).add_systems(
                 AssetEvents,
-                system::hot_reload_style_sheets.in_set(EcssHotReload),
+                system::hot_reload_style_sheets
+                    .in_set(EcssHotReload)
+                    .after(Assets::<StyleSheetAsset>::asset_events),
             );
         }
     }
@@ -146,6 +360,23 @@ fn register_component_selector(app: &mut bevy::prelude::App) {
     app.register_component_selector::<Style>("style");
     app.register_component_selector::<UiImage>("ui-image");
     app.register_component_selector::<Interaction>("interaction");
+
+    app.register_attribute_selector::<Interaction>("interaction", |world, entity, op, value| {
+        world.get::<Interaction>(entity).is_some_and(|interaction| {
+            let interaction = match interaction {
+                Interaction::Pressed => "pressed",
+                Interaction::Hovered => "hovered",
+                Interaction::None => "none",
+            };
+            op.compare_str(interaction, value)
+        })
+    });
+
+    app.register_attribute_selector::<Name>("name", |world, entity, op, value| {
+        world
+            .get::<Name>(entity)
+            .is_some_and(|name| op.compare_str(name.as_str(), value))
+    });
 }
 
 fn register_properties(app: &mut bevy::prelude::App) {
@@ -160,15 +391,19 @@ fn register_properties(app: &mut bevy::prelude::App) {
     app.register_property::<AlignSelfProperty>();
     app.register_property::<AlignContentProperty>();
     app.register_property::<JustifyContentProperty>();
+    app.register_property::<JustifyItemsProperty>();
+    app.register_property::<JustifySelfProperty>();
     app.register_property::<OverflowAxisXProperty>();
     app.register_property::<OverflowAxisYProperty>();
+    app.register_property::<OverflowProperty>();
 
     app.register_property::<LeftProperty>();
     app.register_property::<RightProperty>();
     app.register_property::<TopProperty>();
     app.register_property::<BottomProperty>();
-    app.register_property::<WidthProperty>();
-    app.register_property::<HeightProperty>();
+    app.register_property::<InsetProperty>();
+    app.register_animatable_property::<WidthProperty>();
+    app.register_animatable_property::<HeightProperty>();
     app.register_property::<MinWidthProperty>();
     app.register_property::<MinHeightProperty>();
     app.register_property::<MaxWidthProperty>();
@@ -176,23 +411,52 @@ fn register_properties(app: &mut bevy::prelude::App) {
     app.register_property::<FlexBasisProperty>();
     app.register_property::<FlexGrowProperty>();
     app.register_property::<FlexShrinkProperty>();
+    app.register_property::<FlexProperty>();
     app.register_property::<RowGapProperty>();
     app.register_property::<ColumnGapProperty>();
+    app.register_property::<GapProperty>();
     app.register_property::<AspectRatioProperty>();
 
     app.register_property::<MarginProperty>();
     app.register_property::<PaddingProperty>();
     app.register_property::<BorderProperty>();
+    app.register_property::<BorderWidthProperty>();
 
-    app.register_property::<FontColorProperty>();
+    app.register_animatable_property::<FontColorProperty>();
+    app.register_property::<FontColorSectionProperty>();
     app.register_property::<FontProperty>();
+    app.register_property::<FontFamilyProperty>();
     app.register_property::<FontSizeProperty>();
     app.register_property::<TextAlignProperty>();
+    app.register_property::<WhiteSpaceProperty>();
+    app.register_property::<TextOverflowProperty>();
+    app.register_property::<MaxLinesProperty>();
     app.register_property::<TextContentProperty>();
+    app.register_property::<ContentProperty>();
+    // Text transform must be applied after the text content is set
+    register_property_in_set::<TextTransformProperty>(app, EcssSet::LateApply);
 
-    app.register_property::<BackgroundColorProperty>();
+    app.register_animatable_property::<BackgroundColorProperty>();
+    app.register_property::<BackgroundProperty>();
+    app.register_property::<BoxShadowProperty>();
+    app.register_property::<TransformProperty>();
     app.register_property::<BorderColorProperty>();
+    app.register_property::<OutlineProperty>();
+    app.register_property::<OutlineColorProperty>();
+    app.register_property::<OutlineWidthProperty>();
+    app.register_property::<OutlineOffsetProperty>();
+    app.register_property::<ScrollXProperty>();
+    app.register_property::<ScrollYProperty>();
     app.register_property::<ImageProperty>();
+    app.register_property::<ObjectFitProperty>();
+    app.register_property::<CursorProperty>();
+    app.register_property::<PointerEventsProperty>();
+    app.register_property::<DebugProperty>();
+
+    // Opacity must be applied after all color properties, which overwrites alpha
+    register_property_in_set::<OpacityProperty>(app, EcssSet::LateApply);
+
+    app.register_property::<TransitionProperty>();
 }
 
 /// Utility trait which adds the [`register_component_selector`](RegisterComponentSelector::register_component_selector)
@@ -220,37 +484,288 @@ fn register_properties(app: &mut bevy::prelude::App) {
 /// app.register_component_selector::<MyFancyComponentSelector>("fancy-pants");
 /// # }
 /// ```
-
+///
+/// It's also possible to register an attribute selector, which matches entities by a component value,
+/// using [`register_attribute_selector`](RegisterComponentSelector::register_attribute_selector).
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecss::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(f32);
+/// #
+/// # fn some_main() {
+/// #    let mut app = App::new();
+/// #    app.add_plugins(DefaultPlugins).add_plugins(EcssPlugin::default());
+/// // You may use it as selector now, like
+/// // [health<50] {
+/// //      background-color: red;
+/// // }
+/// app.register_attribute_selector::<Health>("health", |world, entity, op, value| {
+///     let Ok(value) = value.parse::<f32>() else {
+///         return false;
+///     };
+///     world
+///         .get::<Health>(entity)
+///         .is_some_and(|health| op.compare(&health.0, &value))
+/// });
+/// # }
+/// ```
 pub trait RegisterComponentSelector {
     fn register_component_selector<T>(&mut self, name: &'static str) -> &mut Self
     where
         T: Component;
+
+    /// Registers an attribute selector named `name`, which matches entities with the component `T`.
+    ///
+    /// The given predicate receives the entity, the [`AttributeOperator`] and the value used on selector,
+    /// and must return `true` if the entity matches. Whenever the component `T` is changed on a matched
+    /// entity, the style sheet is applied again.
+    fn register_attribute_selector<T>(
+        &mut self,
+        name: &'static str,
+        predicate: impl Fn(&World, Entity, AttributeOperator, &str) -> bool + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: Component;
+
+    /// Registers the marker component `T` used by `:disabled` and `:enabled` pseudo-classes, so entities
+    /// which has the component are matched by `:disabled` and all others are matched by `:enabled`.
+    ///
+    /// Only a single component can be registered, so registering another one overwrites the previous.
+    /// Whenever the component is added or removed from a matched entity, the style sheet is applied again.
+    fn register_disabled_component<T>(&mut self) -> &mut Self
+    where
+        T: Component;
+
+    /// Registers a component selector named `name`, like [`register_component_selector`](RegisterComponentSelector::register_component_selector),
+    /// which fields can also be set by properties named `name.field`, like `my-component.speed: 10;`.
+    ///
+    /// The component must be reflected with `#[reflect(Component)]` and only `f32`, `bool`, `String` and
+    /// unit enum fields are supported. Hyphens on field names are replaced by underscores.
+    fn register_reflect_component_selector<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + GetTypeRegistration;
+}
+
+/// Registers the component selector `name` for the component `T`, returning the warning to be logged
+/// when it overwrites the one registered by another component.
+fn insert_component_selector<T: Component>(
+    world: &mut World,
+    name: &'static str,
+) -> Option<String> {
+    let system_state = SystemState::<Query<Entity, With<T>>>::new(world);
+    let boxed_state = Box::new(system_state);
+
+    world
+        .get_resource_or_insert_with::<ComponentFilterRegistry>(bevy::utils::default)
+        .insert_component(name, boxed_state)
+        .map(|previous| {
+            format!(
+                "Component selector \"{}\" registered by {} overwrites the one registered by {}.",
+                name,
+                std::any::type_name::<T>(),
+                previous
+            )
+        })
 }
 
 impl RegisterComponentSelector for bevy::prelude::App {
     fn register_component_selector<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component,
+    {
+        if let Some(warning) = insert_component_selector::<T>(&mut self.world, name) {
+            warn!("{}", warning);
+        }
+
+        self
+    }
+
+    fn register_attribute_selector<T>(
+        &mut self,
+        name: &'static str,
+        predicate: impl Fn(&World, Entity, AttributeOperator, &str) -> bool + Send + Sync + 'static,
+    ) -> &mut Self
     where
         T: Component,
     {
         let system_state = SystemState::<Query<Entity, With<T>>>::new(&mut self.world);
-        let boxed_state = Box::new(system_state);
+        let attribute = AttributeSelector {
+            filter: Box::new(system_state),
+            predicate: Box::new(predicate),
+        };
 
         self.world
             .get_resource_or_insert_with::<ComponentFilterRegistry>(bevy::utils::default)
-            .insert(name, boxed_state);
+            .attributes
+            .insert(name, attribute);
+
+        self
+    }
+
+    fn register_disabled_component<T>(&mut self) -> &mut Self
+    where
+        T: Component,
+    {
+        let system_state = SystemState::<Query<Entity, With<T>>>::new(&mut self.world);
+
+        if let Some(previous) = self
+            .world
+            .get_resource_or_insert_with::<ComponentFilterRegistry>(bevy::utils::default)
+            .disabled
+            .replace(Box::new(system_state))
+            .filter(|previous| previous.component_name() != std::any::type_name::<T>())
+        {
+            warn!(
+                "Disabled component {} overwrites the one registered by {}.",
+                std::any::type_name::<T>(),
+                previous.component_name()
+            );
+        }
+
+        self
+    }
+
+    fn register_reflect_component_selector<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + GetTypeRegistration,
+    {
+        self.register_component_selector::<T>(name)
+            .register_type::<T>();
+
+        self.world
+            .get_resource_or_insert_with::<RegisteredProperties>(bevy::utils::default)
+            .insert_reflect_component(name, TypeId::of::<T>());
+
+        self
+    }
+}
+
+/// Utility trait which adds the [`register_pseudo_class`](RegisterPseudoClass::register_pseudo_class)
+/// function on [`App`](bevy::prelude::App) to add a custom pseudo-class.
+///
+/// The predicate is called for each candidate entity and must return `true` if the entity matches.
+/// Since the predicate may depend on anything, changes aren't tracked, so call
+/// [`StyleSheet::refresh`] whenever the matched state changes.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecss::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Selected;
+/// #
+/// # fn some_main() {
+/// #    let mut app = App::new();
+/// #    app.add_plugins(DefaultPlugins).add_plugins(EcssPlugin::default());
+/// // You may use it as selector now, like
+/// // button:selected {
+/// //      background-color: blue;
+/// // }
+/// app.register_pseudo_class("selected", (0, 1, 0), |world, entity| {
+///     world.get::<Selected>(entity).is_some()
+/// });
+/// # }
+/// ```
+pub trait RegisterPseudoClass {
+    /// Registers a pseudo-class named `name`, which matches entities accepted by the given predicate.
+    ///
+    /// The given `weight` is used as the pseudo-class [`Specificity`], which is `(0, 1, 0)` for built-in ones.
+    /// Registering the same name again overwrites the previous pseudo-class, while built-in ones, like
+    /// `:hover`, can't be overwritten.
+    fn register_pseudo_class(
+        &mut self,
+        name: &'static str,
+        weight: Specificity,
+        predicate: impl Fn(&World, Entity) -> bool + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl RegisterPseudoClass for bevy::prelude::App {
+    fn register_pseudo_class(
+        &mut self,
+        name: &'static str,
+        weight: Specificity,
+        predicate: impl Fn(&World, Entity) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        let pseudo_class = PseudoClassSelector {
+            predicate: Box::new(predicate),
+            weight,
+        };
+
+        if self
+            .world
+            .get_resource_or_insert_with::<ComponentFilterRegistry>(bevy::utils::default)
+            .pseudo_classes
+            .insert(name, pseudo_class)
+            .is_some()
+        {
+            warn!(
+                "Pseudo-class \":{}\" overwrites a previous registered one.",
+                name
+            );
+        }
 
         self
     }
 }
 
+/// Registers the property `T` to be applied on the given `set`, which must be [`EcssSet::Apply`] or [`EcssSet::LateApply`].
+fn register_property_in_set<T: Property>(
+    app: &mut bevy::prelude::App,
+    set: EcssSet,
+) -> &mut bevy::prelude::App {
+    if let Some(previous) = app
+        .world
+        .get_resource_or_insert_with::<RegisteredProperties>(bevy::utils::default)
+        .insert::<T>()
+    {
+        warn!(
+            "Property \"{}\" registered by {} was already registered by {}. Both will be applied.",
+            T::name(),
+            std::any::type_name::<T>(),
+            previous
+        );
+    }
+
+    if let Some(mut appliers) = app.world.get_resource_mut::<PropertyAppliers>() {
+        appliers.push::<T>(set == EcssSet::LateApply);
+    } else {
+        let EcssSchedule(schedule) = *app
+            .world
+            .get_resource_or_insert_with::<EcssSchedule>(bevy::utils::default);
+        app.add_systems(
+            schedule,
+            T::apply_system
+                .in_set(set)
+                .run_if(property::have_property::<T>),
+        );
+    }
+
+    app
+}
+
 /// Utility trait which adds the [`register_property`](RegisterProperty::register_property) function
 /// on [`App`](bevy::prelude::App) to add a [`Property`] parser.
 ///
 /// You need to register only custom properties which implements [`Property`] trait.
+///
+/// Properties which also implements [`AnimatableProperty`] should be registered with
+/// [`register_animatable_property`](RegisterProperty::register_animatable_property) instead,
+/// so they can be animated by `transition` property.
 pub trait RegisterProperty {
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + 'static;
+
+    fn register_animatable_property<T>(&mut self) -> &mut Self
+    where
+        T: AnimatableProperty + 'static,
+        T::Cache: Lerp;
 }
 
 impl RegisterProperty for bevy::prelude::App {
@@ -258,8 +773,367 @@ impl RegisterProperty for bevy::prelude::App {
     where
         T: Property + 'static,
     {
-        self.add_systems(PreUpdate, T::apply_system.in_set(EcssSet::Apply));
+        register_property_in_set::<T>(self, EcssSet::Apply)
+    }
+
+    fn register_animatable_property<T>(&mut self) -> &mut Self
+    where
+        T: AnimatableProperty + 'static,
+        T::Cache: Lerp,
+    {
+        let EcssSchedule(schedule) = *self
+            .world
+            .get_resource_or_insert_with::<EcssSchedule>(bevy::utils::default);
+        self.register_property::<T>().add_systems(
+            schedule,
+            property::transition::animate_property::<T>.in_set(EcssSet::Transition),
+        );
 
         self
     }
 }
+
+/// Creates an app with [`EcssPlugin`], adds a style sheet parsed from `content` and spawns a root node
+/// using it. Entities spawned by `spawn`, which receives the root, are styled by the sheet.
+///
+/// The app is updated twice, so the sheet is applied and all commands issued by properties are flushed.
+#[cfg(test)]
+pub(crate) fn apply_test_sheet<R>(
+    content: &str,
+    spawn: impl FnOnce(&mut World, Entity) -> R,
+) -> (bevy::prelude::App, R) {
+    use bevy::prelude::{App, AssetPlugin, MinimalPlugins, NodeBundle};
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        EcssPlugin::default(),
+    ));
+
+    let sheet = app
+        .world
+        .resource_mut::<Assets<StyleSheetAsset>>()
+        .add(StyleSheetAsset::parse("", content));
+    let root = app
+        .world
+        .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+        .id();
+    let spawned = spawn(&mut app.world, root);
+
+    app.update();
+    app.update();
+
+    (app, spawned)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::query::QueryItem, prelude::*};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct ShadowColorProperty;
+
+    impl Property for ShadowColorProperty {
+        type Cache = Color;
+        type Components = &'static mut BackgroundColor;
+        type Filters = ();
+
+        fn name() -> &'static str {
+            "color"
+        }
+
+        fn parse(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            values
+                .color()
+                .ok_or_else(|| EcssError::InvalidPropertyValue(Self::name().to_string()))
+        }
+
+        fn apply(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.0 = *cache;
+        }
+    }
+
+    #[test]
+    fn error_property_name() {
+        let parse = |property: &str, value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ {property}: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            let values = &rule.properties[property];
+            match property {
+                "width" => property::impls::WidthProperty::parse(values).map(|_| ()),
+                "position-type" => property::impls::PositionTypeProperty::parse(values).map(|_| ()),
+                _ => unreachable!(),
+            }
+            .unwrap_err()
+        };
+
+        let err = parse("width", "red");
+        assert!(matches!(&err, EcssError::InvalidPropertyValue(p) if p == "width"));
+        assert_eq!(err.property(), Some("width"));
+        assert_eq!(err.to_string(), "Invalid property value: width");
+
+        let err = parse("position-type", "sticky");
+        assert_eq!(err.property(), Some("position-type"));
+        assert_eq!(
+            err.to_string(),
+            "Unsupported property value: position-type: sticky"
+        );
+
+        assert_eq!(EcssError::InvalidSelector.property(), None);
+    }
+
+    #[test]
+    fn detect_duplicated_property_name() {
+        let mut registered = RegisteredProperties::default();
+        assert_eq!(
+            registered.insert::<property::impls::FontColorProperty>(),
+            None
+        );
+        assert_eq!(
+            registered.insert::<ShadowColorProperty>(),
+            Some(std::any::type_name::<property::impls::FontColorProperty>()),
+            "Should report which property already registered the name"
+        );
+
+        // Registering on app should only warn, not panic
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .register_property::<ShadowColorProperty>();
+        assert!(app
+            .world
+            .resource::<RegisteredProperties>()
+            .contains("color"));
+    }
+
+    #[test]
+    fn detect_duplicated_component_selector() {
+        #[derive(Component)]
+        struct Foo;
+
+        #[derive(Component)]
+        struct OtherFoo;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .register_component_selector::<Foo>("foo");
+
+        let mut registry = ComponentFilterRegistry::default();
+        let foo = || {
+            Box::new(SystemState::<Query<Entity, With<Foo>>>::new(
+                &mut World::new(),
+            ))
+        };
+        let other_foo = || {
+            Box::new(SystemState::<Query<Entity, With<OtherFoo>>>::new(
+                &mut World::new(),
+            ))
+        };
+
+        assert_eq!(registry.insert_component("foo", foo()), None);
+        assert_eq!(
+            registry.insert_component("foo", foo()),
+            None,
+            "Registering the same component again isn't a collision"
+        );
+        assert_eq!(
+            registry.insert_component("foo", other_foo()),
+            Some(std::any::type_name::<Foo>()),
+            "Should report which component was overwritten"
+        );
+
+        assert_eq!(
+            insert_component_selector::<Foo>(&mut app.world, "foo"),
+            None
+        );
+        let warning = insert_component_selector::<OtherFoo>(&mut app.world, "foo")
+            .expect("Should warn about the overwritten component");
+        assert!(warning.contains(r#""foo""#));
+        assert!(warning.contains(std::any::type_name::<Foo>()));
+        assert!(warning.contains(std::any::type_name::<OtherFoo>()));
+
+        // Registering on app should only warn, not panic
+        app.register_component_selector::<OtherFoo>("foo");
+        let entity = app.world.spawn(OtherFoo).id();
+        assert!(app
+            .world
+            .resource::<ComponentFilterRegistry>()
+            .get("foo")
+            .is_some_and(|filter| filter.contains(&app.world, entity)));
+    }
+
+    #[test]
+    fn apply_on_configured_schedule() {
+        #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct UiSchedule;
+
+        let mut app = App::new();
+        app.init_schedule(UiSchedule).add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default().in_schedule(UiSchedule),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", "node { width: 10px; }"));
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(root).unwrap().width,
+            Val::Auto,
+            "Should not apply on PreUpdate"
+        );
+
+        app.world.run_schedule(UiSchedule);
+
+        assert_eq!(app.world.get::<Style>(root).unwrap().width, Val::Px(10.0));
+    }
+
+    #[test]
+    fn skip_unused_property_system() {
+        #[derive(Default, Resource)]
+        struct Runs(usize);
+
+        #[derive(Default)]
+        struct CountProperty;
+
+        impl Property for CountProperty {
+            type Cache = ();
+            type Components = ();
+            type Filters = ();
+
+            fn name() -> &'static str {
+                "count"
+            }
+
+            fn parse(_values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+                Ok(())
+            }
+
+            fn apply(
+                _cache: &Self::Cache,
+                _components: QueryItem<Self::Components>,
+                _asset_server: &AssetServer,
+                _commands: &mut Commands,
+            ) {
+            }
+
+            fn apply_system(
+                _local: Local<property::PropertyMeta<Self>>,
+                _assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+                _assets: Res<Assets<StyleSheetAsset>>,
+                _apply_sheets: Res<StyleSheetState>,
+                _q_nodes: Query<Self::Components, Self::Filters>,
+                _asset_server: Res<AssetServer>,
+                mut commands: Commands,
+            ) {
+                commands.add(|world: &mut World| world.resource_mut::<Runs>().0 += 1);
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .init_resource::<Runs>()
+        .register_property::<CountProperty>();
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let unused = assets.add(StyleSheetAsset::parse("", "node { width: 10px; }"));
+        let used = assets.add(StyleSheetAsset::parse("", "node { count: 1; }"));
+
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(unused)))
+            .id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.get::<Style>(root).unwrap().width, Val::Px(10.0));
+        assert_eq!(
+            app.world.resource::<Runs>().0,
+            0,
+            "Should skip properties not used by any sheet"
+        );
+
+        app.world
+            .get_mut::<StyleSheet>(root)
+            .unwrap()
+            .set_handles(vec![used]);
+        app.update();
+        app.update();
+
+        assert!(app.world.resource::<Runs>().0 > 0);
+    }
+
+    #[test]
+    fn apply_in_single_pass() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default().single_pass_apply(),
+        ));
+
+        let sheet =
+            app.world
+                .resource_mut::<Assets<StyleSheetAsset>>()
+                .add(StyleSheetAsset::parse(
+                    "",
+                    r#"node { opacity: 0.5; width: 10px; }
+                   #child { background-color: red; }
+                   text { text-content: "Hello"; }"#,
+                ));
+        let child = app
+            .world
+            .spawn((NodeBundle::default(), Name::new("child")))
+            .id();
+        let text = app.world.spawn(TextBundle::default()).id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .push_children(&[child, text])
+            .id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.get::<Style>(root).unwrap().width, Val::Px(10.0));
+        assert_eq!(app.world.get::<Style>(child).unwrap().width, Val::Px(10.0));
+        assert_eq!(
+            app.world.get::<BackgroundColor>(child).unwrap().0,
+            Color::RED.with_a(0.5),
+            "Opacity should be applied after background color, regardless of rule order"
+        );
+        assert_eq!(
+            app.world.get::<Text>(text).unwrap().sections[0].value,
+            "Hello"
+        );
+    }
+}
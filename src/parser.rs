@@ -1,14 +1,17 @@
-use bevy::log::prelude::error;
+use bevy::log::prelude::{debug, error};
 use cssparser::{
-    AtRuleParser, DeclarationParser, ParseError, Parser, ParserInput, QualifiedRuleParser,
-    RuleBodyItemParser, RuleBodyParser, ToCss, Token,
+    match_ignore_ascii_case, AtRuleParser, DeclarationParser, Delimiter, ParseError, Parser,
+    ParserInput, QualifiedRuleParser, RuleBodyItemParser, RuleBodyParser, ToCss, Token,
 };
 use smallvec::{smallvec, SmallVec};
 
 use crate::{
-    property::PropertyValues,
-    selector::{Selector, SelectorElement},
-    stylesheet::StyleRule,
+    property::{PropertyToken, PropertyValues},
+    selector::{
+        AttributeOperator, MediaFeature, MediaQuery, PseudoClassElement, PseudoElement, Selector,
+        SelectorElement,
+    },
+    stylesheet::{FontFace, StyleRule},
     EcssError,
 };
 
@@ -16,23 +19,63 @@ use crate::{
 pub(crate) struct StyleSheetParser;
 
 impl StyleSheetParser {
+    #[cfg(test)]
     pub(crate) fn parse(content: &str) -> SmallVec<[StyleRule; 8]> {
+        Self::parse_with_imports(content).1
+    }
+
+    /// Parses the given content and returns the paths of all `@import` rules, in source order, all style rules
+    /// and all `@font-face` rules.
+    pub(crate) fn parse_with_imports(
+        content: &str,
+    ) -> (
+        SmallVec<[String; 4]>,
+        SmallVec<[StyleRule; 8]>,
+        Vec<FontFace>,
+    ) {
         let mut input = ParserInput::new(content);
         let mut parser = Parser::new(&mut input);
 
-        cssparser::StyleSheetParser::new(&mut parser, &mut StyleSheetParser)
-            .filter_map(|result| match result {
-                Ok(rule) => Some(rule),
+        let mut imports = SmallVec::new();
+        let mut rules = SmallVec::new();
+        let mut font_faces = Vec::new();
+
+        for result in cssparser::StyleSheetParser::new(&mut parser, &mut StyleSheetParser) {
+            match result {
+                Ok(Rule::Style(rule)) => rules.push(*rule),
+                Ok(Rule::Import(path)) => imports.push(path),
+                Ok(Rule::FontFace(font_face)) => font_faces.push(font_face),
+                Ok(Rule::Media(media_rules)) => rules.extend(media_rules),
+                Ok(Rule::Ignored) => (),
                 Err((err, rule)) => {
                     error!(
                         "Failed to parse rule: {}. Error: {}",
                         rule,
                         format_error(err)
                     );
-                    None
                 }
+            }
+        }
+
+        (imports, rules, font_faces)
+    }
+
+    /// Parses a single selector, like `.menu .item:hover`, using the same rules of style sheet selectors.
+    pub(crate) fn parse_selector(content: &str) -> Result<Selector, EcssError> {
+        let mut input = ParserInput::new(content.trim());
+        let mut parser = Parser::new(&mut input);
+
+        parser
+            .parse_entirely(|input| {
+                QualifiedRuleParser::parse_prelude(&mut StyleSheetParser, input)
+            })
+            .map_err(|err| match err.kind {
+                cssparser::ParseErrorKind::Custom(err) => err,
+                cssparser::ParseErrorKind::Basic(
+                    cssparser::BasicParseErrorKind::UnexpectedToken(token),
+                ) => EcssError::UnexpectedToken(token.to_css_string()),
+                cssparser::ParseErrorKind::Basic(_) => EcssError::InvalidSelector,
             })
-            .collect()
     }
 }
 
@@ -60,6 +103,20 @@ fn format_error(error: ParseError<EcssError>) -> String {
     )
 }
 
+/// A top level rule parsed from a style sheet.
+pub(crate) enum Rule {
+    /// A rule with a selector and a list of properties.
+    Style(Box<StyleRule>),
+    /// An `@import` rule with the path of the imported style sheet.
+    Import(String),
+    /// A `@media` rule with all style rules inside it, which selectors holds the media query.
+    Media(Vec<StyleRule>),
+    /// A `@font-face` rule with a font family name and its source.
+    FontFace(FontFace),
+    /// A valid rule which has no effect, like `@charset`.
+    Ignored,
+}
+
 /// Helper enum to indicate if the next element to be processed if an element with prefix.
 enum NextElementWithPrefix {
     None,
@@ -67,11 +124,13 @@ enum NextElementWithPrefix {
     Class,
     // prefixed by a `:`
     PseudoClass,
+    // prefixed by a `::`
+    PseudoElement,
 }
 
 impl<'i> QualifiedRuleParser<'i> for StyleSheetParser {
     type Prelude = Selector;
-    type QualifiedRule = StyleRule;
+    type QualifiedRule = Rule;
     type Error = EcssError;
 
     fn parse_prelude<'t>(
@@ -96,6 +155,17 @@ impl<'i> QualifiedRuleParser<'i> for StyleSheetParser {
                         NextElementWithPrefix::PseudoClass => {
                             elements.push(SelectorElement::PseudoClass(v.into()))
                         }
+                        NextElementWithPrefix::PseudoElement => {
+                            let pseudo_element = match_ignore_ascii_case! { &v,
+                                "before" => PseudoElement::Before,
+                                "after" => PseudoElement::After,
+                                _ => {
+                                    let token = format!("::{}", v);
+                                    return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
+                                },
+                            };
+                            elements.push(SelectorElement::PseudoElement(pseudo_element))
+                        }
                     }
                     next_element_with_prefix = NextElementWithPrefix::None;
                 }
@@ -106,10 +176,56 @@ impl<'i> QualifiedRuleParser<'i> for StyleSheetParser {
                         elements.push(SelectorElement::Name(v.to_string()));
                     }
                 }
-                WhiteSpace(_) => elements.push(SelectorElement::Child),
+                // Comments are skipped, but the white spaces around it aren't, so `a /* b */ c` has two of them
+                WhiteSpace(_) => {
+                    if !matches!(
+                        elements.last(),
+                        None | Some(SelectorElement::Child | SelectorElement::DirectChild)
+                    ) {
+                        elements.push(SelectorElement::Child)
+                    }
+                }
                 Delim(c) if *c == '.' => next_element_with_prefix = NextElementWithPrefix::Class,
-                Delim(c) if *c == '*' => elements.push(SelectorElement::Any),
-                Colon => next_element_with_prefix = NextElementWithPrefix::PseudoClass,
+                // White spaces around `>` are part of it, so the previous one is replaced
+                Delim(c) if *c == '>' => {
+                    if elements.last() == Some(&SelectorElement::Child) {
+                        elements.pop();
+                    }
+
+                    if matches!(elements.last(), None | Some(SelectorElement::DirectChild)) {
+                        return Err(input.new_custom_error(EcssError::InvalidSelector));
+                    }
+
+                    elements.push(SelectorElement::DirectChild);
+                }
+                Delim(c) if *c == '*' => match elements.last_mut() {
+                    // A trailing `*` right after a name, like `#item-*`, makes it a prefix match
+                    Some(SelectorElement::Name(name)) => name.push('*'),
+                    _ => elements.push(SelectorElement::Any),
+                },
+                Colon => {
+                    next_element_with_prefix = match next_element_with_prefix {
+                        NextElementWithPrefix::PseudoClass => NextElementWithPrefix::PseudoElement,
+                        _ => NextElementWithPrefix::PseudoClass,
+                    }
+                }
+                SquareBracketBlock => {
+                    elements.push(input.parse_nested_block(parse_attribute)?);
+                }
+                Function(name)
+                    if matches!(next_element_with_prefix, NextElementWithPrefix::PseudoClass) =>
+                {
+                    let name = name.clone();
+                    let pseudo_class = match_ignore_ascii_case! { &name,
+                        "nth-of-type" => PseudoClassElement::NthOfType(input.parse_nested_block(parse_nth_index)?),
+                        _ => {
+                            let token = format!(":{}()", name);
+                            return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
+                        },
+                    };
+                    elements.push(SelectorElement::PseudoClass(pseudo_class));
+                    next_element_with_prefix = NextElementWithPrefix::None;
+                }
                 _ => {
                     let token = token.to_css_string();
                     return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
@@ -126,6 +242,21 @@ impl<'i> QualifiedRuleParser<'i> for StyleSheetParser {
             elements.remove(elements.len() - 1);
         }
 
+        // A combinator must be followed by some element, like `.menu > .item`
+        if elements.last() == Some(&SelectorElement::DirectChild) {
+            return Err(input.new_custom_error(EcssError::InvalidSelector));
+        }
+
+        // Pseudo-elements are only allowed as the last element
+        if elements
+            .iter()
+            .rev()
+            .skip(1)
+            .any(|e| matches!(e, SelectorElement::PseudoElement(_)))
+        {
+            return Err(input.new_custom_error(EcssError::InvalidSelector));
+        }
+
         Ok(Selector::new(elements))
     }
 
@@ -145,20 +276,323 @@ impl<'i> QualifiedRuleParser<'i> for StyleSheetParser {
                 Ok((name, property)) => {
                     rule.properties.insert(name, property);
                 }
-                Err((err, a)) => {
-                    error!("Failed to parse property : {:?} ({})", err, a)
-                }
+                Err((err, a)) => match parse_reflect_declaration(a) {
+                    Some((name, property)) => {
+                        rule.properties.insert(name, property);
+                    }
+                    None => error!("Failed to parse property : {:?} ({})", err, a),
+                },
+            }
+        }
+
+        Ok(Rule::Style(Box::new(rule)))
+    }
+}
+
+/// Parses a declaration which sets a reflected component field, like `my-component.speed: 10;`.
+///
+/// Property names can't have dots, so these declarations are rejected by [`RuleBodyParser`] and parsed
+/// again here, using `component.field` as property name.
+fn parse_reflect_declaration(declaration: &str) -> Option<(String, PropertyValues)> {
+    let mut input = ParserInput::new(declaration);
+    let mut parser = Parser::new(&mut input);
+
+    let result: Result<_, ParseError<'_, EcssError>> = parser.parse_entirely(|input| {
+        let component = input.expect_ident()?.to_ascii_lowercase();
+        input.expect_delim('.')?;
+        let field = input.expect_ident()?.to_ascii_lowercase();
+        input.expect_colon()?;
+
+        let (_, values) = input.parse_until_before(Delimiter::Semicolon, |input| {
+            PropertyParser.parse_value("".into(), input)
+        })?;
+        let _ = input.try_parse(|input| input.expect_semicolon());
+
+        Ok((format!("{component}.{field}"), values))
+    });
+
+    result.ok()
+}
+
+/// Parses the content of an attribute selector element, like `[interaction=pressed]` or `[health<50]`.
+fn parse_attribute<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<SelectorElement, ParseError<'i, EcssError>> {
+    let name = input.expect_ident()?.to_string();
+
+    if input.is_exhausted() {
+        return Ok(SelectorElement::Attribute {
+            name,
+            op: AttributeOperator::Exists,
+            value: String::new(),
+        });
+    }
+
+    let op = match input.next()?.clone() {
+        Token::Delim('=') => AttributeOperator::Equals,
+        Token::SubstringMatch => AttributeOperator::Contains,
+        Token::PrefixMatch => AttributeOperator::StartsWith,
+        Token::SuffixMatch => AttributeOperator::EndsWith,
+        Token::Delim(c @ ('!' | '<' | '>')) => {
+            let has_equals = input.try_parse(|input| input.expect_delim('=')).is_ok();
+            match (c, has_equals) {
+                ('!', true) => AttributeOperator::NotEquals,
+                ('<', false) => AttributeOperator::Less,
+                ('<', true) => AttributeOperator::LessOrEqual,
+                ('>', false) => AttributeOperator::Greater,
+                ('>', true) => AttributeOperator::GreaterOrEqual,
+                _ => return Err(input.new_custom_error(EcssError::InvalidSelector)),
             }
         }
+        token => {
+            return Err(input.new_custom_error(EcssError::UnexpectedToken(token.to_css_string())))
+        }
+    };
+
+    let value = match input.next()? {
+        Token::Ident(v) | Token::QuotedString(v) => v.to_string(),
+        token @ (Token::Number { .. } | Token::Dimension { .. } | Token::Percentage { .. }) => {
+            token.to_css_string()
+        }
+        token => {
+            let token = token.to_css_string();
+            return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
+        }
+    };
+
+    input.expect_exhausted()?;
+
+    Ok(SelectorElement::Attribute { name, op, value })
+}
 
-        Ok(rule)
+/// Parses the index of a structural pseudo-class, like the `2` on `:nth-of-type(2)`. Indices starts at `1`.
+fn parse_nth_index<'i>(input: &mut Parser<'i, '_>) -> Result<usize, ParseError<'i, EcssError>> {
+    let index = input.expect_integer()?;
+    input.expect_exhausted()?;
+
+    if index < 1 {
+        return Err(input.new_custom_error(EcssError::InvalidSelector));
     }
+
+    Ok(index as usize)
+}
+
+/// At-rules which are recognized by [`StyleSheetParser`].
+pub(crate) enum AtRulePrelude {
+    /// `@charset "UTF-8";`
+    Charset,
+    /// `@namespace svg url(http://www.w3.org/2000/svg);`
+    Namespace,
+    /// `@import "base.css";` or `@import url(base.css);`
+    Import(String),
+    /// `@media (max-width: 800px) { ... }`
+    Media(MediaQuery),
+    /// `@font-face { font-family: "Inter"; src: url("inter.ttf"); }`
+    FontFace,
+    /// Any other at-rule, which is ignored.
+    Unknown,
 }
 
 impl<'i> AtRuleParser<'i> for StyleSheetParser {
-    type Prelude = ();
-    type AtRule = StyleRule;
+    type Prelude = AtRulePrelude;
+    type AtRule = Rule;
     type Error = EcssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: cssparser::CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        match_ignore_ascii_case! { &name,
+            "charset" => {
+                input.expect_string()?;
+                input.expect_exhausted()?;
+                Ok(AtRulePrelude::Charset)
+            },
+            "namespace" => {
+                let _ = input.try_parse(|input| input.expect_ident_cloned());
+                input.expect_url_or_string()?;
+                input.expect_exhausted()?;
+                Ok(AtRulePrelude::Namespace)
+            },
+            "import" => {
+                let path = input.expect_url_or_string()?.to_string();
+                if !input.is_exhausted() {
+                    debug!("Ignoring media queries of @import {}", path);
+                    while input.next().is_ok() {}
+                }
+                Ok(AtRulePrelude::Import(path))
+            },
+            "media" => Ok(AtRulePrelude::Media(parse_media_query(input)?)),
+            "font-face" => {
+                input.expect_exhausted()?;
+                Ok(AtRulePrelude::FontFace)
+            },
+            _ => {
+                debug!("Ignoring unsupported at-rule @{}", name);
+                while input.next().is_ok() {}
+                Ok(AtRulePrelude::Unknown)
+            }
+        }
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &cssparser::ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            AtRulePrelude::Import(path) => Ok(Rule::Import(path)),
+            AtRulePrelude::Media(_) | AtRulePrelude::FontFace => Err(()),
+            _ => Ok(Rule::Ignored),
+        }
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &cssparser::ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
+        match prelude {
+            AtRulePrelude::Charset | AtRulePrelude::Namespace | AtRulePrelude::Import(_) => {
+                Err(input.new_error(cssparser::BasicParseErrorKind::AtRuleBodyInvalid))
+            }
+            AtRulePrelude::Media(media) => {
+                let mut rules: Vec<StyleRule> = Vec::new();
+
+                for result in cssparser::StyleSheetParser::new(input, self) {
+                    match result {
+                        Ok(Rule::Style(rule)) => rules.push(*rule),
+                        Ok(Rule::Media(nested)) => rules.extend(nested),
+                        Ok(Rule::Import(path)) => {
+                            error!("@import {} isn't allowed inside @media rules", path)
+                        }
+                        Ok(Rule::FontFace(font_face)) => error!(
+                            "@font-face {} isn't allowed inside @media rules",
+                            font_face.family
+                        ),
+                        Ok(Rule::Ignored) => (),
+                        Err((err, rule)) => {
+                            error!(
+                                "Failed to parse rule: {}. Error: {}",
+                                rule,
+                                format_error(err)
+                            );
+                        }
+                    }
+                }
+
+                for rule in &mut rules {
+                    rule.selector = std::mem::take(&mut rule.selector).with_media(media.clone());
+                }
+
+                Ok(Rule::Media(rules))
+            }
+            AtRulePrelude::FontFace => parse_font_face(input).map(Rule::FontFace),
+            AtRulePrelude::Unknown => Ok(Rule::Ignored),
+        }
+    }
+}
+
+/// Parses the body of a `@font-face` rule, which must have both `font-family` and `src` descriptors.
+///
+/// Only the first `url(...)` or string of `src` is used, so fallback sources and `format(...)` hints are ignored.
+fn parse_font_face<'i>(input: &mut Parser<'i, '_>) -> Result<FontFace, ParseError<'i, EcssError>> {
+    let mut family = None;
+    let mut src = None;
+
+    for declaration in RuleBodyParser::new(input, &mut PropertyParser) {
+        match declaration {
+            Ok((name, values)) if name == "font-family" => {
+                family = values
+                    .string()
+                    .or_else(|| values.identifier().map(str::to_string));
+            }
+            Ok((name, values)) if name == "src" => {
+                src = match values.as_slice() {
+                    [PropertyToken::Identifier(function), PropertyToken::String(path), ..]
+                        if function == "url" =>
+                    {
+                        Some(path.clone())
+                    }
+                    [PropertyToken::String(path), ..] => Some(path.clone()),
+                    _ => None,
+                }
+                .filter(|path| !path.is_empty());
+            }
+            Ok((name, _)) => debug!("Ignoring @font-face descriptor {}", name),
+            Err((err, declaration)) => error!(
+                "Failed to parse @font-face descriptor: {} ({})",
+                format_error(err),
+                declaration
+            ),
+        }
+    }
+
+    match (family, src) {
+        (Some(family), Some(src)) => Ok(FontFace {
+            family,
+            src,
+            font: None,
+        }),
+        _ => Err(input.new_custom_error(EcssError::InvalidFontFace)),
+    }
+}
+
+/// Parses a media query list like `screen and (min-width: 400px) and (max-width: 800px)`.
+///
+/// Media types are ignored, since only screens are supported, and only a single query is supported.
+fn parse_media_query<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaQuery, ParseError<'i, EcssError>> {
+    let mut features = SmallVec::new();
+
+    while let Ok(token) = input.next() {
+        match token.clone() {
+            Token::Ident(ident)
+                if matches!(
+                    ident.to_ascii_lowercase().as_str(),
+                    "only" | "screen" | "all" | "and"
+                ) => {}
+            Token::ParenthesisBlock => {
+                features.push(input.parse_nested_block(parse_media_feature)?);
+            }
+            token => {
+                let token = token.to_css_string();
+                return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
+            }
+        }
+    }
+
+    Ok(MediaQuery(features))
+}
+
+/// Parses the content of a media feature, like `max-width: 800px`. Only `px` or unitless `0` are supported.
+fn parse_media_feature<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaFeature, ParseError<'i, EcssError>> {
+    let name = input.expect_ident_cloned()?;
+    input.expect_colon()?;
+
+    let value = match input.next()? {
+        Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("px") => *value,
+        Token::Number { value, .. } if *value == 0.0 => 0.0,
+        token => {
+            let token = token.to_css_string();
+            return Err(input.new_custom_error(EcssError::UnexpectedToken(token)));
+        }
+    };
+
+    input.expect_exhausted()?;
+
+    match_ignore_ascii_case! { &name,
+        "min-width" => Ok(MediaFeature::MinWidth(value)),
+        "max-width" => Ok(MediaFeature::MaxWidth(value)),
+        "min-height" => Ok(MediaFeature::MinHeight(value)),
+        "max-height" => Ok(MediaFeature::MaxHeight(value)),
+        _ => Err(input.new_custom_error(EcssError::UnexpectedToken(name.to_string()))),
+    }
 }
 
 struct PropertyParser;
@@ -191,7 +625,14 @@ impl<'i> DeclarationParser<'i> for PropertyParser {
             }
         }
 
-        Ok((name.to_string(), PropertyValues(tokens)))
+        // Property names are case-insensitive, except for custom ones
+        let name = if name.starts_with("--") {
+            name.to_string()
+        } else {
+            name.to_ascii_lowercase()
+        };
+
+        Ok((name, PropertyValues(tokens)))
     }
 }
 
@@ -213,7 +654,25 @@ fn parse_values<'i>(
     let mut values = SmallVec::new();
 
     while let Ok(token) = parser.next_including_whitespace() {
-        values.push(token.clone())
+        match token.clone() {
+            // Functions are flattened into its name followed by its arguments, like `min 10px 5%`.
+            // Commas between arguments are dropped, so only commas between values are kept.
+            Token::Function(name) => {
+                values.push(Token::Ident(name));
+                values.extend(
+                    parser
+                        .parse_nested_block(parse_values)?
+                        .into_iter()
+                        .filter(|token| *token != Token::Comma),
+                );
+            }
+            // Unquoted urls are handled as `url("...")` function
+            Token::UnquotedUrl(url) => {
+                values.push(Token::Ident("url".into()));
+                values.push(Token::QuotedString(url));
+            }
+            token => values.push(token),
+        }
     }
 
     Ok(values)
@@ -221,7 +680,7 @@ fn parse_values<'i>(
 
 #[cfg(test)]
 mod tests {
-    use crate::{property::PropertyToken, selector::PseudoClassElement};
+    use crate::property::PropertyToken;
 
     use super::*;
 
@@ -253,6 +712,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_commented_sheet() {
+        assert!(
+            StyleSheetParser::parse("/* only a comment */").is_empty(),
+            "Should return an empty list of rules"
+        );
+
+        let rules = StyleSheetParser::parse(
+            r#"
+            /* header */
+            a /* between */ b /* before block */ {
+                /* before */ c: d /* after value */;
+                /* last */
+            }
+            /* footer */
+            "#,
+        );
+        assert_eq!(rules.len(), 1, "Should have a single rule");
+        assert_eq!(
+            rules[0].selector.get_parent_tree().len(),
+            2,
+            "Comments shouldn't add extra descendant combinators"
+        );
+        assert_eq!(rules[0].selector.to_string(), "a b");
+        assert_eq!(rules[0].properties.len(), 1);
+        assert_eq!(
+            rules[0].properties["c"].identifier(),
+            Some("d"),
+            "Comments should be ignored on properties"
+        );
+    }
+
+    #[test]
+    fn parse_empty_body_rule() {
+        let rules = StyleSheetParser::parse("* {} .a {} /* c */ b { }");
+        assert_eq!(rules.len(), 3, "Empty rules shouldn't be dropped");
+        for rule in &rules {
+            assert!(rule.properties.is_empty());
+        }
+        assert_eq!(rules[0].selector.to_string(), "*");
+        assert_eq!(rules[1].selector.to_string(), ".a");
+        assert_eq!(rules[2].selector.to_string(), "b");
+    }
+
+    #[test]
+    fn parse_nth_of_type() {
+        let rules = StyleSheetParser::parse(
+            "button:nth-of-type(2) {} a:nth-of-type(0) {} a:nth-of-type(b) {} a:nth-child(1) {}",
+        );
+        assert_eq!(rules.len(), 1, "Only valid indices should be parsed");
+
+        use SelectorElement::*;
+        assert_eq!(
+            rules[0].selector.get_parent_tree()[0].as_slice(),
+            &[
+                &Component("button".to_string()),
+                &PseudoClass(PseudoClassElement::NthOfType(2))
+            ]
+        );
+        assert_eq!(rules[0].selector.to_string(), "button:nth-of-type(2)");
+    }
+
+    #[test]
+    fn parse_visibility_pseudo_classes() {
+        let rules = StyleSheetParser::parse(".a:visible {} .b:hidden {}");
+        assert_eq!(rules.len(), 2);
+
+        use SelectorElement::*;
+        assert_eq!(
+            rules[0].selector.get_parent_tree()[0].as_slice(),
+            &[
+                &Class("a".to_string()),
+                &PseudoClass(PseudoClassElement::Visible)
+            ]
+        );
+        assert_eq!(
+            rules[1].selector.get_parent_tree()[0].as_slice(),
+            &[
+                &Class("b".to_string()),
+                &PseudoClass(PseudoClassElement::Hidden)
+            ]
+        );
+        assert_eq!(rules[1].selector.to_string(), ".b:hidden");
+    }
+
+    #[test]
+    fn parse_disabled_pseudo_classes() {
+        let rules = StyleSheetParser::parse("button:disabled {} button:enabled {}");
+        assert_eq!(rules.len(), 2);
+
+        use SelectorElement::*;
+        assert_eq!(
+            rules[0].selector.get_parent_tree()[0].as_slice(),
+            &[
+                &Component("button".to_string()),
+                &PseudoClass(PseudoClassElement::Disabled)
+            ]
+        );
+        assert_eq!(rules[1].selector.to_string(), "button:enabled");
+    }
+
+    #[test]
+    fn parse_root_pseudo_class() {
+        let rules = StyleSheetParser::parse(":root {} :root .item {}");
+        assert_eq!(rules.len(), 2);
+
+        use SelectorElement::*;
+        assert_eq!(
+            rules[0].selector.get_parent_tree()[0].as_slice(),
+            &[&PseudoClass(PseudoClassElement::Root)]
+        );
+        assert_eq!(rules[1].selector.to_string(), ":root .item");
+    }
+
     #[test]
     fn parse_single_name_selector_no_property() {
         let rules = StyleSheetParser::parse("#id {}");
@@ -273,6 +846,74 @@ mod tests {
         assert!(rule.properties.is_empty(), "Should have no token");
     }
 
+    #[test]
+    fn parse_name_prefix_selector() {
+        let rules = StyleSheetParser::parse("#right-item-* {} #item * {}");
+        assert_eq!(rules.len(), 2, "Should have two rules");
+
+        let tree = rules[0].selector.get_parent_tree();
+        assert_eq!(tree.len(), 1, "Should have a single selector node");
+        assert_eq!(
+            tree[0].as_slice(),
+            &[&SelectorElement::Name("right-item-*".to_string())],
+            "Trailing `*` should be kept on name"
+        );
+
+        let tree = rules[1].selector.get_parent_tree();
+        assert_eq!(tree.len(), 2, "Separated `*` should be an any selector");
+        assert_eq!(tree[1].as_slice(), &[&SelectorElement::Any]);
+    }
+
+    #[test]
+    fn parse_standalone_selector() {
+        let selector = Selector::parse("#menu .item:hover").unwrap();
+        assert_eq!(selector.to_string(), "#menu .item:hover");
+        assert_eq!(
+            selector,
+            StyleSheetParser::parse("#menu .item:hover {}")[0].selector,
+            "Should match the same selector parsed from a sheet"
+        );
+
+        let selector = Selector::parse("  button.enabled::before ").unwrap();
+        assert_eq!(
+            selector.get_parent_tree()[0].as_slice(),
+            &[
+                &SelectorElement::Component("button".to_string()),
+                &SelectorElement::Class("enabled".to_string()),
+                &SelectorElement::PseudoElement(PseudoElement::Before),
+            ]
+        );
+
+        let selector = Selector::parse("[interaction=pressed]").unwrap();
+        assert_eq!(selector.to_string(), "[interaction=pressed]");
+
+        assert!(matches!(
+            Selector::parse(""),
+            Err(EcssError::InvalidSelector)
+        ));
+        assert!(matches!(
+            Selector::parse("   "),
+            Err(EcssError::InvalidSelector)
+        ));
+        let selector = Selector::parse(".menu > .item:hover").unwrap();
+        assert_eq!(selector.to_string(), ".menu > .item:hover");
+        assert_eq!(selector.get_parent_tree().len(), 2);
+        assert!(selector.is_direct_child(1));
+        assert_eq!(
+            selector,
+            Selector::parse(".menu>.item:hover").unwrap(),
+            "White spaces around `>` shouldn't matter"
+        );
+
+        for invalid in ["> .item", ".menu >", ".menu > > .item"] {
+            assert!(matches!(
+                Selector::parse(invalid),
+                Err(EcssError::InvalidSelector)
+            ));
+        }
+        assert!(Selector::parse(".item {}").is_err());
+    }
+
     #[test]
     fn parse_single_class_selector_no_property() {
         let rules = StyleSheetParser::parse(".class {}");
@@ -434,7 +1075,7 @@ mod tests {
         use SelectorElement::*;
         let expected: SmallVec<[SmallVec<[SelectorElement; 8]>; 8]> = smallvec![smallvec![
             Component("a".to_string()),
-            PseudoClass(PseudoClassElement::Unsupported)
+            PseudoClass(PseudoClassElement::Custom("pseudo".to_string()))
         ],];
 
         expected
@@ -490,6 +1131,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_commas() {
+        let rules = StyleSheetParser::parse(r#"a { b: "c", d; e: rgb(1, 2, 3); }"#);
+        let properties = &rules[0].properties;
+
+        assert_eq!(
+            properties["b"].as_slice(),
+            [
+                PropertyToken::String("c".to_string()),
+                PropertyToken::Comma,
+                PropertyToken::Identifier("d".to_string())
+            ],
+            "Commas between values should be kept"
+        );
+        assert!(
+            !properties["e"].contains(&PropertyToken::Comma),
+            "Commas between function arguments should be dropped"
+        );
+    }
+
     #[test]
     fn parse_multiple_complex_properties() {
         let rules = StyleSheetParser::parse(
@@ -579,4 +1240,248 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn parse_ignorable_at_rules() {
+        let rules = StyleSheetParser::parse(
+            r#"@charset "UTF-8";
+            @namespace svg url(http://www.w3.org/2000/svg);
+            @unknown-rule something { a { b: c } }
+            a { b: c }
+            #d { e: f }"#,
+        );
+
+        assert_eq!(rules.len(), 2, "Should have only the two valid rules");
+        assert_eq!(
+            rules[0].selector.get_parent_tree()[0][0],
+            &SelectorElement::Component("a".to_string())
+        );
+        assert_eq!(
+            rules[1].selector.get_parent_tree()[0][0],
+            &SelectorElement::Name("d".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_malformed_at_rules() {
+        let rules = StyleSheetParser::parse(
+            r#"@charset UTF-8;
+            @charset "UTF-8" { a { b: c } }
+            a { b: c }"#,
+        );
+
+        assert_eq!(rules.len(), 1, "Should have skipped malformed at-rules");
+    }
+
+    #[test]
+    fn parse_attribute_selector() {
+        let rules = StyleSheetParser::parse(
+            r#"button[interaction=pressed] {}
+            [health<=50] {}
+            [interaction] {}
+            [name!="some value"] {}
+            [name*="item"] {}
+            [name^=menu] {}
+            [name$=button] {}"#,
+        );
+        assert_eq!(rules.len(), 7, "Should have 7 rules");
+
+        use SelectorElement::*;
+        let expected: [SmallVec<[SelectorElement; 8]>; 7] = [
+            smallvec![
+                Component("button".to_string()),
+                Attribute {
+                    name: "interaction".to_string(),
+                    op: AttributeOperator::Equals,
+                    value: "pressed".to_string()
+                }
+            ],
+            smallvec![Attribute {
+                name: "health".to_string(),
+                op: AttributeOperator::LessOrEqual,
+                value: "50".to_string()
+            }],
+            smallvec![Attribute {
+                name: "interaction".to_string(),
+                op: AttributeOperator::Exists,
+                value: String::new()
+            }],
+            smallvec![Attribute {
+                name: "name".to_string(),
+                op: AttributeOperator::NotEquals,
+                value: "some value".to_string()
+            }],
+            smallvec![Attribute {
+                name: "name".to_string(),
+                op: AttributeOperator::Contains,
+                value: "item".to_string()
+            }],
+            smallvec![Attribute {
+                name: "name".to_string(),
+                op: AttributeOperator::StartsWith,
+                value: "menu".to_string()
+            }],
+            smallvec![Attribute {
+                name: "name".to_string(),
+                op: AttributeOperator::EndsWith,
+                value: "button".to_string()
+            }],
+        ];
+
+        for (rule, expected) in rules.iter().zip(expected) {
+            let tree = rule.selector.get_parent_tree();
+            assert_eq!(tree.len(), 1, "Should have a single selector node");
+            assert_eq!(
+                tree[0]
+                    .iter()
+                    .copied()
+                    .cloned()
+                    .collect::<SmallVec<[_; 8]>>(),
+                expected
+            );
+        }
+
+        assert!(
+            StyleSheetParser::parse("[health<] {} [=50] {}").is_empty(),
+            "Should reject malformed attributes"
+        );
+    }
+
+    #[test]
+    fn parse_imports() {
+        let (imports, rules, _) = StyleSheetParser::parse_with_imports(
+            r#"
+            @import "base.css";
+            @import url(theme/dark.css) screen;
+            @import;
+            .a { width: 10px; }
+            "#,
+        );
+
+        assert_eq!(imports.as_slice(), ["base.css", "theme/dark.css"]);
+        assert_eq!(rules.len(), 1, "Should keep style rules");
+    }
+
+    #[test]
+    fn parse_font_faces() {
+        let (_, rules, font_faces) = StyleSheetParser::parse_with_imports(
+            r#"
+            @font-face { font-family: "Inter"; src: url("fonts/inter.ttf") format("truetype"); }
+            @font-face { font-family: mono; src: "fonts/mono.ttf", url(fallback.ttf); font-weight: bold; }
+            @font-face { font-family: "Missing src"; }
+            .a { font-family: "Inter"; }
+            "#,
+        );
+
+        assert_eq!(rules.len(), 1, "Should keep style rules");
+        assert_eq!(
+            font_faces
+                .iter()
+                .map(|face| (face.family.as_str(), face.src.as_str()))
+                .collect::<Vec<_>>(),
+            [("Inter", "fonts/inter.ttf"), ("mono", "fonts/mono.ttf")]
+        );
+        assert!(font_faces.iter().all(|face| face.font.is_none()));
+    }
+
+    #[test]
+    fn parse_media_rules() {
+        let rules = StyleSheetParser::parse(
+            r#"
+            .a { width: 10px; }
+            @media screen and (min-width: 400px) and (max-width: 800px) {
+                .a { width: 20px; }
+                @media (max-height: 300px) {
+                    .b { height: 5px; }
+                }
+            }
+            @media (max-width: 10em) { .c { width: 1px; } }
+            @media (orientation: landscape) { .d { width: 1px; } }
+            "#,
+        );
+
+        assert_eq!(rules.len(), 3, "Invalid media queries should be ignored");
+        assert_eq!(rules[0].selector.media(), None);
+
+        let media = rules[1]
+            .selector
+            .media()
+            .expect("Should have a media query");
+        assert_eq!(
+            media.features(),
+            [MediaFeature::MinWidth(400.0), MediaFeature::MaxWidth(800.0)]
+        );
+        assert_ne!(
+            rules[0].selector, rules[1].selector,
+            "Media rules should not be mistaken by rules without it"
+        );
+        assert!(media.matches(600.0, 100.0));
+        assert!(!media.matches(900.0, 100.0));
+
+        let nested = rules[2]
+            .selector
+            .media()
+            .expect("Should have a media query");
+        assert_eq!(
+            nested.features(),
+            [
+                MediaFeature::MinWidth(400.0),
+                MediaFeature::MaxWidth(800.0),
+                MediaFeature::MaxHeight(300.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_pseudo_elements() {
+        let rules = StyleSheetParser::parse(
+            r#"
+            .icon::before { content: "A"; }
+            .icon::after { content: "Z"; }
+            .icon::before .child { content: "X"; }
+            .icon::marker { content: "X"; }
+            "#,
+        );
+
+        assert_eq!(
+            rules.len(),
+            2,
+            "Only trailing and supported pseudo-elements are valid"
+        );
+        assert_eq!(
+            rules[0].selector.pseudo_element(),
+            Some(PseudoElement::Before)
+        );
+        assert_eq!(rules[0].selector.to_string(), ".icon::before");
+        assert_eq!(
+            rules[1].selector.pseudo_element(),
+            Some(PseudoElement::After)
+        );
+    }
+
+    #[test]
+    fn parse_min_max_val() {
+        use bevy::ui::Val;
+
+        let rules = StyleSheetParser::parse(
+            r#"
+            a {
+                width: min(10px, 5px);
+                height: max(10%, 50%);
+                left: min(10px, 5%);
+                right: max(auto);
+            }
+            "#,
+        );
+
+        let properties = &rules[0].properties;
+        assert_eq!(properties["width"].val(), Some(Val::Px(5.0)));
+        assert_eq!(properties["height"].val(), Some(Val::Percent(50.0)));
+        assert_eq!(
+            properties["left"].val(),
+            Some(Val::Px(10.0)),
+            "Mixed units should fallback to first value"
+        );
+        assert_eq!(properties["right"].val(), Some(Val::Auto));
+    }
 }
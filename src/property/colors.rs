@@ -1,5 +1,60 @@
+use std::iter::Peekable;
+
 use bevy::prelude::Color;
 
+use super::PropertyToken;
+
+/// Parses a single color starting at `first` token, which may be a named color, an hex color or a
+/// `rgb(...)`/`rgba(...)` function. Only the tokens used by the color are consumed from `tokens`.
+pub(super) fn parse_color<'a>(
+    first: &PropertyToken,
+    tokens: &mut Peekable<impl Iterator<Item = &'a PropertyToken>>,
+) -> Option<Color> {
+    match first {
+        PropertyToken::Identifier(function) if function == "rgb" || function == "rgba" => {
+            parse_rgb_function(function, tokens)
+        }
+        PropertyToken::Identifier(name) => parse_named_color(name),
+        PropertyToken::Hash(hash) => parse_hex_color(hash),
+        _ => None,
+    }
+}
+
+/// Parses `rgb(...)` or `rgba(...)` arguments from the given tokens, like `0 0 0 0.3` or `0 0 0 / 30%`.
+///
+/// Since functions are flattened, only the expected arguments are consumed, which are three channels
+/// followed by an alpha on `rgba` or after a `/`. Channels are in `0..=255` range and alpha in `0..=1` range,
+/// while both also accepts percentages.
+fn parse_rgb_function<'a>(
+    function: &str,
+    tokens: &mut Peekable<impl Iterator<Item = &'a PropertyToken>>,
+) -> Option<Color> {
+    let mut channel = |max: f32| match tokens.next()? {
+        PropertyToken::Number(value) => Some((value / max).clamp(0.0, 1.0)),
+        PropertyToken::Percentage(value) => Some((value / 100.0).clamp(0.0, 1.0)),
+        _ => None,
+    };
+
+    let (red, green, blue) = (channel(255.0)?, channel(255.0)?, channel(255.0)?);
+
+    let has_alpha = tokens
+        .next_if(|token| matches!(token, PropertyToken::Slash))
+        .is_some()
+        || function == "rgba";
+
+    let alpha = if has_alpha {
+        match tokens.next()? {
+            PropertyToken::Number(value) => value.clamp(0.0, 1.0),
+            PropertyToken::Percentage(value) => (value / 100.0).clamp(0.0, 1.0),
+            _ => return None,
+        }
+    } else {
+        1.0
+    };
+
+    Some(Color::rgba(red, green, blue, alpha))
+}
+
 pub(super) fn parse_hex_color(hex: &str) -> Option<Color> {
     if let Ok((r, g, b, a)) = cssparser::color::parse_hash_color(hex.as_bytes()) {
         Some(Color::rgba_u8(r, g, b, (a * 255.0) as u8))
@@ -12,8 +67,13 @@ pub(super) fn parse_hex_color(hex: &str) -> Option<Color> {
 
 /// Parses a named color, like "silver" or "azure" into a [`Color`]
 ///
-/// Accepts any [valid CSS named-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color).
+/// Accepts any [valid CSS named-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color) and `transparent`.
+/// The `currentColor` keyword isn't a named color, so it isn't accepted here.
 pub(super) fn parse_named_color(name: &str) -> Option<Color> {
+    if name.eq_ignore_ascii_case("transparent") {
+        return Some(Color::NONE);
+    }
+
     if let Ok(cssparser_color::Color::Rgba(cssparser_color::RgbaLegacy {
         red,
         green,
@@ -1,8 +1,15 @@
-use bevy::{ecs::query::QueryItem, prelude::*};
+use bevy::{ecs::query::QueryItem, prelude::*, text::BreakLineOn, ui::FocusPolicy};
+use smallvec::SmallVec;
 
-use crate::EcssError;
+use crate::{
+    Class, CssBoxShadow, CssCalc, CssCursor, CssGradient, CssOpacity, EcssError, MaxLines,
+    ObjectFit, PseudoElementNode, ScrollPosition, Selector, StyleSheetAsset, TextOverflow,
+};
 
-use super::{Property, PropertyValues};
+use super::{
+    transition::{AnimatableProperty, Transitions},
+    CalcVal, FontFamilies, Property, PropertyMeta, PropertyToken, PropertyValues, StyleSheetState,
+};
 
 pub use style::*;
 pub use text::*;
@@ -11,6 +18,7 @@ pub use text::*;
 mod style {
     use super::*;
     /// Implements a new property for [`Style`] component which expects a rect value.
+    /// The `unset` keyword resets the field to the [`Style::DEFAULT`] value.
     macro_rules! impl_style_rect {
         ($name:expr, $struct:ident, $style_prop:ident$(.$style_field:ident)*) => {
             #[doc = "Applies the `"]
@@ -32,7 +40,9 @@ mod style {
                 }
 
                 fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-                    if let Some(val) = values.rect() {
+                    if values.is_unset() {
+                        Ok(Style::DEFAULT.$style_prop$(.$style_field)*)
+                    } else if let Some(val) = values.rect() {
                         Ok(val)
                     } else {
                         Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
@@ -53,9 +63,97 @@ mod style {
 
     impl_style_rect!("margin", MarginProperty, margin);
     impl_style_rect!("padding", PaddingProperty, padding);
-    impl_style_rect!("border", BorderProperty, border);
+    impl_style_rect!("border-width", BorderWidthProperty, border);
+
+    /// Applies the `border` shorthand property, which sets [`Style::border`](`Style`) and/or [`BorderColor`] of matched entities.
+    ///
+    /// Both width and color are optional, like `2px solid red` or `2px 0`. The border style is ignored, since
+    /// there is only solid borders on `bevy_ui`, so a warning is logged for any other style, like `dashed`.
+    /// If the entity has no [`BorderColor`], a new one is inserted.
+    #[derive(Default)]
+    pub struct BorderProperty;
+
+    /// Keywords accepted as border style by `border` shorthand property.
+    const BORDER_STYLES: &[&str] = &[
+        "none", "hidden", "solid", "dashed", "dotted", "double", "groove", "ridge", "inset",
+        "outset",
+    ];
+
+    /// Returns the border style keyword which can't be rendered by `bevy_ui`, if any, like `dashed`.
+    pub(super) fn unsupported_border_style(values: &PropertyValues) -> Option<String> {
+        values
+            .identifiers()
+            .map(str::to_ascii_lowercase)
+            .find(|style| style != "solid" && BORDER_STYLES.contains(&style.as_str()))
+    }
+
+    impl Property for BorderProperty {
+        type Cache = (Option<UiRect>, Option<Color>);
+        type Components = (Entity, &'static mut Style, Option<&'static mut BorderColor>);
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "border"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            if values.is_unset() {
+                return Ok((Some(Style::DEFAULT.border), None));
+            }
+
+            // Style and color keywords are mixed with widths, so only widths are parsed as a rect
+            let widths = values
+                .iter()
+                .filter(|token| PropertyValues::token_val(token).is_some())
+                .cloned()
+                .collect::<SmallVec<_>>();
+            let width = PropertyValues(widths).rect();
+            let color = values.iter().find_map(|token| match token {
+                PropertyToken::Identifier(name) => super::super::colors::parse_named_color(name),
+                PropertyToken::Hash(hash) => super::super::colors::parse_hex_color(hash),
+                _ => None,
+            });
+
+            if width.is_none() && color.is_none() {
+                return Err(EcssError::InvalidPropertyValue(Self::name().to_string()));
+            }
+
+            if let Some(style) = unsupported_border_style(values) {
+                // Width and color are still applied, so `1px dashed red` renders as a solid red border
+                warn!(
+                    "{}: {} border style isn't supported by Bevy, a solid border is rendered instead.",
+                    Self::name(),
+                    style
+                );
+            }
+
+            Ok((width, color))
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            (entity, mut style, maybe_border_color): QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            commands: &mut Commands,
+        ) {
+            let (width, color) = *cache;
+
+            if let Some(width) = width {
+                style.border = width;
+            }
+
+            match (color, maybe_border_color) {
+                (Some(color), Some(mut border_color)) => border_color.0 = color,
+                (Some(color), None) => {
+                    commands.entity(entity).insert(BorderColor(color));
+                }
+                (None, _) => (),
+            }
+        }
+    }
 
     /// Implements a new property for [`Style`] component which expects a single value.
+    /// The `unset` keyword resets the field to the [`Style::DEFAULT`] value.
     macro_rules! impl_style_single_value {
         ($name:expr, $struct:ident, $cache:ty, $parse_func:ident, $style_prop:ident$(.$style_field:ident)*) => {
             #[doc = "Applies the `"]
@@ -77,7 +175,9 @@ mod style {
                 }
 
                 fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-                    if let Some(val) = values.$parse_func() {
+                    if values.is_unset() {
+                        Ok(Style::DEFAULT.$style_prop$(.$style_field)*)
+                    } else if let Some(val) = values.$parse_func() {
                         Ok(val)
                     } else {
                         Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
@@ -102,8 +202,143 @@ mod style {
     impl_style_single_value!("top", TopProperty, Val, val, top);
     impl_style_single_value!("bottom", BottomProperty, Val, val, bottom);
 
-    impl_style_single_value!("width", WidthProperty, Val, val, width);
-    impl_style_single_value!("height", HeightProperty, Val, val, height);
+    /// Applies the `inset` shorthand property on [`Style::left`](`Style`), [`Style::right`](`Style`),
+    /// [`Style::top`](`Style`) and [`Style::bottom`](`Style`) fields of matched [`Style`] components.
+    ///
+    /// Values follows the same order of `margin` and `padding`, so `inset: 10px;` sets all of them while
+    /// `inset: 1px 2px 3px 4px;` sets top, right, bottom and left, respectively.
+    /// The `unset` keyword resets all fields to the [`Style::DEFAULT`] value.
+    #[derive(Default)]
+    pub struct InsetProperty;
+
+    impl Property for InsetProperty {
+        type Cache = UiRect;
+        type Components = &'static mut Style;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "inset"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            if values.is_unset() {
+                let Style {
+                    left,
+                    right,
+                    top,
+                    bottom,
+                    ..
+                } = Style::DEFAULT;
+                Ok(UiRect::new(left, right, top, bottom))
+            } else {
+                values
+                    .rect()
+                    .ok_or_else(|| EcssError::InvalidPropertyValue(Self::name().to_string()))
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.left = cache.left;
+            components.right = cache.right;
+            components.top = cache.top;
+            components.bottom = cache.bottom;
+        }
+    }
+
+    /// Implements a new property for [`Style`] component which expects a size value, like `width`.
+    ///
+    /// Besides the values accepted by [`val`](PropertyValues::val), a `calc(...)` expression which can't be represented
+    /// by a [`Val`] is kept on a [`CssCalc`] component, so it can be resolved later on, check it for more.
+    macro_rules! impl_style_size {
+        ($name:expr, $struct:ident, $style_prop:ident) => {
+            #[doc = "Applies the `"]
+            #[doc = $name]
+            #[doc = "` property on [Style::"]
+            #[doc = stringify!($style_prop)]
+            #[doc = "](`Style`) field of all matched [`Style`] components."]
+            #[doc = ""]
+            #[doc = "A `calc(...)` expression which mixes units, like `calc(100% - 20px)`, is resolved by an internal system, check [`CssCalc`] for more."]
+            #[derive(Default)]
+            pub struct $struct;
+
+            impl Property for $struct {
+                // The `Val` is used right away, while the `calc(...)` expression, if any, is resolved later on
+                type Cache = (Val, Option<CalcVal>);
+                type Components = (Entity, &'static mut Style, Option<&'static mut CssCalc>);
+                type Filters = With<Node>;
+
+                fn name() -> &'static str {
+                    $name
+                }
+
+                fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+                    if values.is_unset() {
+                        return Ok((Style::DEFAULT.$style_prop, None));
+                    }
+
+                    let Some(val) = values.val() else {
+                        return Err(EcssError::InvalidPropertyValue(Self::name().to_string()));
+                    };
+
+                    let calc = values
+                        .calc()
+                        .filter(|calc| calc.to_val().is_none());
+
+                    Ok((val, calc))
+                }
+
+                fn apply<'w>(
+                    (val, calc): &Self::Cache,
+                    (entity, mut style, maybe_calc): QueryItem<Self::Components>,
+                    _asset_server: &AssetServer,
+                    commands: &mut Commands,
+                ) {
+                    style.$style_prop = *val;
+
+                    if let Some(mut css_calc) = maybe_calc {
+                        css_calc.$style_prop = *calc;
+                    } else if calc.is_some() {
+                        // Both `width` and `height` may insert the component on the same frame
+                        let calc = *calc;
+                        commands.add(move |world: &mut World| {
+                            let Some(mut entity) = world.get_entity_mut(entity) else {
+                                return;
+                            };
+
+                            if let Some(mut css_calc) = entity.get_mut::<CssCalc>() {
+                                css_calc.$style_prop = calc;
+                            } else {
+                                entity.insert(CssCalc {
+                                    $style_prop: calc,
+                                    ..Default::default()
+                                });
+                            }
+                        });
+                    }
+                }
+            }
+
+            impl AnimatableProperty for $struct {
+                type Target = Style;
+
+                fn get(target: &Self::Target) -> Self::Cache {
+                    (target.$style_prop, None)
+                }
+
+                fn set(target: &mut Self::Target, value: &Self::Cache) {
+                    target.$style_prop = value.0;
+                }
+            }
+        };
+    }
+
+    impl_style_size!("width", WidthProperty, width);
+    impl_style_size!("height", HeightProperty, height);
 
     impl_style_single_value!("min-width", MinWidthProperty, Val, val, min_width);
     impl_style_single_value!("min-height", MinHeightProperty, Val, val, min_height);
@@ -116,14 +351,116 @@ mod style {
     impl_style_single_value!("flex-grow", FlexGrowProperty, f32, f32, flex_grow);
     impl_style_single_value!("flex-shrink", FlexShrinkProperty, f32, f32, flex_shrink);
 
+    /// Applies the `flex` shorthand property on [`Style::flex_grow`](`Style`), [`Style::flex_shrink`](`Style`)
+    /// and [`Style::flex_basis`](`Style`) fields of matched [`Style`] components.
+    ///
+    /// Follows the [CSS spec](https://developer.mozilla.org/en-US/docs/Web/CSS/flex), so `flex: 1` is the same
+    /// as `flex: 1 1 0`, `flex: auto` is `flex: 1 1 auto` and `flex: none` is `flex: 0 0 auto`.
+    #[derive(Default)]
+    pub struct FlexProperty;
+
+    impl Property for FlexProperty {
+        type Cache = (f32, f32, Val);
+        type Components = &'static mut Style;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "flex"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            match values.as_slice() {
+                [PropertyToken::Identifier(id)] if id == "none" => {
+                    return Ok((0.0, 0.0, Val::Auto))
+                }
+                [PropertyToken::Identifier(id)] if id == "auto" => {
+                    return Ok((1.0, 1.0, Val::Auto))
+                }
+                _ => (),
+            }
+
+            let numbers = values
+                .iter()
+                .take(2)
+                .map_while(|token| match token {
+                    PropertyToken::Number(val) => Some(*val),
+                    _ => None,
+                })
+                .collect::<SmallVec<[_; 2]>>();
+
+            let basis = match &values[numbers.len()..] {
+                [] if !numbers.is_empty() => Some(Val::Px(0.0)),
+                [token] => PropertyValues(smallvec::smallvec![token.clone()]).val(),
+                _ => None,
+            };
+
+            match (numbers.as_slice(), basis) {
+                ([], Some(basis)) => Ok((1.0, 1.0, basis)),
+                (&[grow], Some(basis)) => Ok((grow, 1.0, basis)),
+                (&[grow, shrink], Some(basis)) => Ok((grow, shrink, basis)),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            let (grow, shrink, basis) = *cache;
+            components.flex_grow = grow;
+            components.flex_shrink = shrink;
+            components.flex_basis = basis;
+        }
+    }
+
     impl_style_single_value!("row-gap", RowGapProperty, Val, val, row_gap);
     impl_style_single_value!("column-gap", ColumnGapProperty, Val, val, column_gap);
 
+    /// Applies the `gap` shorthand property on [`Style::row_gap`](`Style`) and [`Style::column_gap`](`Style`) fields
+    /// of matched [`Style`] components.
+    ///
+    /// A single value is applied on both, while two values are applied on row and column gaps, respectively.
+    /// The `unset` keyword resets both fields to the [`Style::DEFAULT`] value.
+    #[derive(Default)]
+    pub struct GapProperty;
+
+    impl Property for GapProperty {
+        type Cache = (Val, Val);
+        type Components = &'static mut Style;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "gap"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            if values.is_unset() {
+                Ok((Style::DEFAULT.row_gap, Style::DEFAULT.column_gap))
+            } else {
+                values
+                    .pair(PropertyValues::token_val)
+                    .ok_or_else(|| EcssError::InvalidPropertyValue(Self::name().to_string()))
+            }
+        }
+
+        fn apply<'w>(
+            (row_gap, column_gap): &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.row_gap = *row_gap;
+            components.column_gap = *column_gap;
+        }
+    }
+
     impl_style_single_value!(
         "aspect-ratio",
         AspectRatioProperty,
         Option<f32>,
-        option_f32,
+        ratio,
         aspect_ratio
     );
 
@@ -150,11 +487,11 @@ mod style {
                 }
 
                 fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-                    if let Some(identifier) = values.identifier() {
+                    if let Some(keyword) = values.keyword() {
                         use $cache::*;
                         // Chain if-let when `cargofmt` supports it
                         // https://github.com/rust-lang/rustfmt/pull/5203
-                        match identifier {
+                        match keyword.as_str() {
                             $($prop => return Ok($variant)),+,
                             _ => (),
                         }
@@ -177,13 +514,65 @@ mod style {
 
     impl_style_enum!(Display, "display", DisplayProperty, display,
         "flex" => Flex,
+        "grid" => Grid,
         "none" => None
     );
 
-    impl_style_enum!(PositionType, "position-type", PositionTypeProperty, position_type,
-        "absolute" => Absolute,
-        "relative" => Relative,
-    );
+    /// Applies the `position-type` property on [`Style::position_type`](`PositionType`) field of all sections on matched [`Style`] components.
+    ///
+    /// Bevy has no `fixed` positioning, so it's approximated as `absolute` and a warning is logged when parsed.
+    /// `sticky` isn't supported and results in an [`EcssError::UnsupportedPropertyValue`] error.
+    #[derive(Default)]
+    pub struct PositionTypeProperty;
+
+    /// Returns the warning logged when the given `position-type` keyword isn't supported by Bevy and is
+    /// approximated by another one, like `fixed`, which is approximated as `absolute`.
+    pub(super) fn position_type_warning(keyword: &str) -> Option<String> {
+        (keyword == "fixed").then(|| {
+            format!(
+                "{}: fixed isn't supported by Bevy, using absolute instead. The entity will be positioned relative to its parent.",
+                PositionTypeProperty::name()
+            )
+        })
+    }
+
+    impl Property for PositionTypeProperty {
+        type Cache = PositionType;
+        type Components = &'static mut Style;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "position-type"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            let keyword = values.keyword();
+
+            // Properties are parsed only once per rule, so this is logged once
+            if let Some(warning) = keyword.as_deref().and_then(position_type_warning) {
+                warn!("{}", warning);
+            }
+
+            match keyword.as_deref() {
+                Some("absolute" | "fixed") => Ok(PositionType::Absolute),
+                Some("relative") => Ok(PositionType::Relative),
+                Some("sticky") => Err(EcssError::UnsupportedPropertyValue {
+                    property: Self::name().to_string(),
+                    value: "sticky".to_string(),
+                }),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.position_type = *cache;
+        }
+    }
 
     impl_style_enum!(Direction, "direction", DirectionProperty, direction,
         "inherit" => Inherit,
@@ -205,6 +594,8 @@ mod style {
     );
 
     impl_style_enum!(AlignItems, "align-items", AlignItemsProperty, align_items,
+        "start" => Start,
+        "end" => End,
         "flex-start" => FlexStart,
         "flex-end" => FlexEnd,
         "center" => Center,
@@ -214,6 +605,8 @@ mod style {
 
     impl_style_enum!(AlignSelf, "align-self", AlignSelfProperty, align_self,
         "auto" => Auto,
+        "start" => Start,
+        "end" => End,
         "flex-start" => FlexStart,
         "flex-end" => FlexEnd,
         "center" => Center,
@@ -222,6 +615,8 @@ mod style {
     );
 
     impl_style_enum!(AlignContent, "align-content", AlignContentProperty, align_content,
+        "start" => Start,
+        "end" => End,
         "flex-start" => FlexStart,
         "flex-end" => FlexEnd,
         "center" => Center,
@@ -231,6 +626,8 @@ mod style {
     );
 
     impl_style_enum!(JustifyContent, "justify-content", JustifyContentProperty, justify_content,
+        "start" => Start,
+        "end" => End,
         "flex-start" => FlexStart,
         "flex-end" => FlexEnd,
         "center" => Center,
@@ -239,15 +636,86 @@ mod style {
         "space-evenly" => SpaceEvenly,
     );
 
+    impl_style_enum!(JustifyItems, "justify-items", JustifyItemsProperty, justify_items,
+        "auto" => Default,
+        "start" => Start,
+        "end" => End,
+        "center" => Center,
+        "baseline" => Baseline,
+        "stretch" => Stretch,
+    );
+
+    impl_style_enum!(JustifySelf, "justify-self", JustifySelfProperty, justify_self,
+        "auto" => Auto,
+        "start" => Start,
+        "end" => End,
+        "center" => Center,
+        "baseline" => Baseline,
+        "stretch" => Stretch,
+    );
+
+    // Like on `overflow` shorthand, `scroll` and `auto` are treated as `hidden`, since `bevy_ui` doesn't support scrolling yet
     impl_style_enum!(OverflowAxis, "overflow-x", OverflowAxisXProperty, overflow.x,
         "visible" => Visible,
         "hidden" => Clip,
+        "clip" => Clip,
+        "scroll" => Clip,
+        "auto" => Clip,
     );
 
     impl_style_enum!(OverflowAxis, "overflow-y", OverflowAxisYProperty, overflow.y,
         "visible" => Visible,
         "hidden" => Clip,
+        "clip" => Clip,
+        "scroll" => Clip,
+        "auto" => Clip,
     );
+
+    /// Applies the `overflow` shorthand property on [`Style::overflow`](`Overflow`) field of matched [`Style`] components.
+    ///
+    /// A single value is applied on both axis, while two values are applied on `x` and `y` axis, respectively.
+    /// Since `bevy_ui` doesn't support scrolling yet, `scroll` and `auto` are treated as `hidden`.
+    #[derive(Default)]
+    pub struct OverflowProperty;
+
+    impl OverflowProperty {
+        fn axis(token: &PropertyToken) -> Option<OverflowAxis> {
+            match token {
+                PropertyToken::Identifier(id) => match id.as_str() {
+                    "visible" => Some(OverflowAxis::Visible),
+                    "hidden" | "clip" | "scroll" | "auto" => Some(OverflowAxis::Clip),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+    }
+
+    impl Property for OverflowProperty {
+        type Cache = Overflow;
+        type Components = &'static mut Style;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "overflow"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            values
+                .pair(Self::axis)
+                .map(|(x, y)| Overflow { x, y })
+                .ok_or_else(|| EcssError::InvalidPropertyValue(Self::name().to_string()))
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.overflow = *cache;
+        }
+    }
 }
 
 /// Impls for `bevy_text` [`Text`] component
@@ -255,12 +723,16 @@ mod text {
     use super::*;
 
     /// Applies the `color` property on [`TextStyle::color`](`TextStyle`) field of all sections on matched [`Text`] components.
+    ///
+    /// The `inherit` keyword uses the color of the first section of the parent [`Text`], if any. Since it needs
+    /// to read the parent, it isn't supported when [`EcssPlugin::single_pass_apply`](crate::EcssPlugin::single_pass_apply) is used.
     #[derive(Default)]
     pub struct FontColorProperty;
 
     impl Property for FontColorProperty {
-        type Cache = Color;
-        type Components = &'static mut Text;
+        // `None` means `inherit`
+        type Cache = Option<Color>;
+        type Components = (Option<&'static Parent>, &'static mut Text);
         type Filters = With<Node>;
 
         fn name() -> &'static str {
@@ -269,7 +741,9 @@ mod text {
 
         fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
             if let Some(color) = values.color() {
-                Ok(color)
+                Ok(Some(color))
+            } else if values.0.len() == 1 && values.keyword().is_some_and(|k| k == "inherit") {
+                Ok(None)
             } else {
                 Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
             }
@@ -277,69 +751,155 @@ mod text {
 
         fn apply<'w>(
             cache: &Self::Cache,
-            mut components: QueryItem<Self::Components>,
+            (_, mut text): QueryItem<Self::Components>,
             _asset_server: &AssetServer,
             _commands: &mut Commands,
         ) {
-            components
-                .sections
-                .iter_mut()
-                .for_each(|section| section.style.color = *cache);
+            if let Some(color) = *cache {
+                text.sections
+                    .iter_mut()
+                    .for_each(|section| section.style.color = color);
+            }
+        }
+
+        // Overridden so `inherit` can read the parent text through the same query.
+        fn apply_system(
+            mut local: Local<PropertyMeta<Self>>,
+            mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+            assets: Res<Assets<StyleSheetAsset>>,
+            apply_sheets: Res<StyleSheetState>,
+            mut q_nodes: Query<Self::Components, Self::Filters>,
+            asset_server: Res<AssetServer>,
+            mut commands: Commands,
+        ) {
+            local.for_each_selected(
+                &mut assets_events,
+                &assets,
+                &apply_sheets,
+                |cached, _, _, entities| {
+                    for entity in entities {
+                        // `inherit` reads the parent text before the entity text is mutably borrowed
+                        let color = cached.or_else(|| {
+                            q_nodes
+                                .get(*entity)
+                                .ok()
+                                .and_then(|(parent, _)| parent)
+                                .and_then(|parent| q_nodes.get(parent.get()).ok())
+                                .and_then(|(_, text)| text.sections.first())
+                                .map(|section| section.style.color)
+                        });
+
+                        if let Ok(components) = q_nodes.get_mut(*entity) {
+                            Self::apply(&color, components, &asset_server, &mut commands);
+                        }
+                    }
+                },
+            );
         }
     }
 
-    /// Applies the `font` property on [`TextStyle::font`](`TextStyle`) property of all sections on matched [`Text`] components.
+    /// Applies the `color-section` property on [`TextStyle::color`](`TextStyle`) field of a single section on matched
+    /// [`Text`] components, like `color-section: 1 red;`, leaving other sections untouched.
+    ///
+    /// Sections are indexed starting at `0`, so rich texts can have each section styled with a different color.
     #[derive(Default)]
-    pub struct FontProperty;
+    pub struct FontColorSectionProperty;
 
-    impl Property for FontProperty {
-        type Cache = String;
+    impl Property for FontColorSectionProperty {
+        type Cache = (usize, Color);
         type Components = &'static mut Text;
         type Filters = With<Node>;
 
         fn name() -> &'static str {
-            "font"
+            "color-section"
         }
 
         fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-            if let Some(path) = values.string() {
-                Ok(path)
-            } else {
-                Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+            if let [PropertyToken::Number(index), color] = values.0.as_slice() {
+                let color = PropertyValues(smallvec::smallvec![color.clone()])
+                    .color()
+                    .filter(|_| index.fract() == 0.0 && *index >= 0.0);
+
+                if let Some(color) = color {
+                    return Ok((*index as usize, color));
+                }
             }
+
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
         }
 
         fn apply<'w>(
-            cache: &Self::Cache,
+            (index, color): &Self::Cache,
             mut components: QueryItem<Self::Components>,
-            asset_server: &AssetServer,
+            _asset_server: &AssetServer,
             _commands: &mut Commands,
         ) {
-            components
-                .sections
-                .iter_mut()
-                .for_each(|section| section.style.font = asset_server.load(cache));
+            if let Some(section) = components.sections.get_mut(*index) {
+                section.style.color = *color;
+            }
         }
     }
 
-    /// Applies the `font-size` property on [`TextStyle::font_size`](`TextStyle`) property of all sections on matched [`Text`] components.
+    impl AnimatableProperty for FontColorProperty {
+        type Target = Text;
+
+        fn get(target: &Self::Target) -> Self::Cache {
+            Some(
+                target
+                    .sections
+                    .first()
+                    .map(|section| section.style.color)
+                    .unwrap_or_default(),
+            )
+        }
+
+        fn set(target: &mut Self::Target, value: &Self::Cache) {
+            if let Some(color) = *value {
+                target
+                    .sections
+                    .iter_mut()
+                    .for_each(|section| section.style.color = color);
+            }
+        }
+    }
+
+    /// Applies the `font-family` property on [`TextStyle::font`](`TextStyle`) property of all sections on matched [`Text`] components.
+    ///
+    /// Many comma-separated families may be given as fallbacks, like `"Inter", "Arial", sans-serif`, in which case
+    /// the first one which is registered on [`FontRegistry`](crate::FontRegistry) or is an asset path with an extension is used.
+    /// When none of them is, the first family is loaded as an asset path.
+    ///
+    /// Families are resolved right after all properties are applied. Check [`FontFamilies`] for more.
     #[derive(Default)]
-    pub struct FontSizeProperty;
+    pub struct FontFamilyProperty;
 
-    impl Property for FontSizeProperty {
-        type Cache = f32;
+    impl Property for FontFamilyProperty {
+        type Cache = FontFamilies;
         type Components = &'static mut Text;
         type Filters = With<Node>;
 
         fn name() -> &'static str {
-            "font-size"
+            "font-family"
         }
 
         fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-            if let Some(size) = values.f32() {
-                Ok(size)
-            } else {
+            // Families are separated by commas, so each string or identifier is a family
+            let families = values
+                .iter()
+                .filter_map(|token| match token {
+                    PropertyToken::String(family) | PropertyToken::Identifier(family)
+                        if !family.is_empty() =>
+                    {
+                        Some(family.clone())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            if families.is_empty() {
                 Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+            } else {
+                Ok(FontFamilies::new(families))
             }
         }
 
@@ -347,56 +907,268 @@ mod text {
             cache: &Self::Cache,
             mut components: QueryItem<Self::Components>,
             _asset_server: &AssetServer,
-            _commands: &mut Commands,
+            commands: &mut Commands,
         ) {
+            let font = cache.apply_placeholder(commands);
             components
                 .sections
                 .iter_mut()
-                .for_each(|section| section.style.font_size = *cache);
+                .for_each(|section| section.style.font = font.clone());
         }
     }
 
-    /// Applies the `text-align` property on [`Text::horizontal`](`JustifyText`) components.
+    /// Applies the `font` shorthand property, which sets both [`TextStyle::font_size`](`TextStyle`) and
+    /// [`TextStyle::font`](`TextStyle`) of all sections on matched [`Text`] components, like `font: 16px "Inter";`.
+    ///
+    /// The size is optional, while the family is resolved the same way as [`FontFamilyProperty`], so
+    /// `font: "fonts/inter.ttf";` and `font: url("fonts/inter.ttf");` are also accepted.
     #[derive(Default)]
-    pub struct TextAlignProperty;
+    pub struct FontProperty;
 
-    impl Property for TextAlignProperty {
-        // Using Option since Cache must impl Default, which  doesn't
-        type Cache = Option<JustifyText>;
+    impl Property for FontProperty {
+        type Cache = (Option<f32>, FontFamilies);
         type Components = &'static mut Text;
         type Filters = With<Node>;
 
         fn name() -> &'static str {
-            "text-align"
+            "font"
         }
 
         fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-            if let Some(ident) = values.identifier() {
-                match ident {
-                    "left" => return Ok(Some(JustifyText::Left)),
-                    "center" => return Ok(Some(JustifyText::Center)),
-                    "right" => return Ok(Some(JustifyText::Right)),
-                    _ => (),
+            let (size, family) = match values.as_slice() {
+                [PropertyToken::Dimension(size) | PropertyToken::Number(size), family @ ..] => {
+                    (Some(*size), family)
                 }
+                family => (None, family),
+            };
+
+            let family = match family {
+                [PropertyToken::String(family)] => Some(family.clone()),
+                [PropertyToken::Identifier(function), path @ ..] if function == "url" => match path
+                {
+                    [PropertyToken::String(path)] => Some(path.clone()),
+                    _ => None,
+                },
+                // Unquoted family names may have many words, like `Fira Sans`
+                words => words
+                    .iter()
+                    .map(|token| match token {
+                        PropertyToken::Identifier(word) => Some(word.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .map(|words| words.join(" ")),
+            };
+
+            match family {
+                Some(family) if !family.is_empty() => Ok((size, FontFamilies::new(vec![family]))),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
             }
-            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
         }
 
         fn apply<'w>(
-            cache: &Self::Cache,
+            (size, families): &Self::Cache,
             mut components: QueryItem<Self::Components>,
             _asset_server: &AssetServer,
-            _commands: &mut Commands,
+            commands: &mut Commands,
         ) {
-            components.justify = cache.expect("Should always have a inner value");
+            let font = families.apply_placeholder(commands);
+            components.sections.iter_mut().for_each(|section| {
+                section.style.font = font.clone();
+                if let Some(size) = *size {
+                    section.style.font_size = size;
+                }
+            });
         }
     }
 
-    /// Apply a custom `text-content` which updates [`TextSection::value`](`TextSection`) of all sections on matched [`Text`] components
+    /// Applies the `font-size` property on [`TextStyle::font_size`](`TextStyle`) property of all sections on matched [`Text`] components.
     #[derive(Default)]
-    pub struct TextContentProperty;
+    pub struct FontSizeProperty;
 
-    impl Property for TextContentProperty {
+    impl Property for FontSizeProperty {
+        type Cache = f32;
+        type Components = &'static mut Text;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "font-size"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            if let Some(size) = values.f32() {
+                Ok(size)
+            } else {
+                Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components
+                .sections
+                .iter_mut()
+                .for_each(|section| section.style.font_size = *cache);
+        }
+    }
+
+    /// Applies the `text-align` property on [`Text::horizontal`](`JustifyText`) components.
+    #[derive(Default)]
+    pub struct TextAlignProperty;
+
+    impl Property for TextAlignProperty {
+        // Using Option since Cache must impl Default, which  doesn't
+        type Cache = Option<JustifyText>;
+        type Components = &'static mut Text;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "text-align"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            if let Some(keyword) = values.keyword() {
+                match keyword.as_str() {
+                    "left" => return Ok(Some(JustifyText::Left)),
+                    "center" => return Ok(Some(JustifyText::Center)),
+                    "right" => return Ok(Some(JustifyText::Right)),
+                    _ => (),
+                }
+            }
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            components.justify = cache.expect("Should always have a inner value");
+        }
+    }
+
+    /// Applies the `white-space` property on [`Text::linebreak_behavior`](`BreakLineOn`) of matched components.
+    ///
+    /// `normal` wraps text on word boundaries ([`BreakLineOn::WordBoundary`]) and `nowrap` disables soft wrapping
+    /// ([`BreakLineOn::NoWrap`]). Explicit line breaks are still kept by Bevy on `nowrap`.
+    #[derive(Default)]
+    pub struct WhiteSpaceProperty;
+
+    impl Property for WhiteSpaceProperty {
+        // Using Option since Cache must impl Default, which BreakLineOn doesn't
+        type Cache = Option<BreakLineOn>;
+        type Components = &'static mut Text;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "white-space"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            match values.keyword().as_deref() {
+                Some("normal") => Ok(Some(BreakLineOn::WordBoundary)),
+                Some("nowrap") => Ok(Some(BreakLineOn::NoWrap)),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            if let Some(linebreak_behavior) = *cache {
+                components.linebreak_behavior = linebreak_behavior;
+            }
+        }
+    }
+
+    /// Applies the `text-overflow` property on [`TextOverflow`] of matched components, inserting it if needed.
+    ///
+    /// `ellipsis` truncates single line texts which doesn't fit on the node width, like the ones with `white-space: nowrap;`.
+    #[derive(Default)]
+    pub struct TextOverflowProperty;
+
+    impl Property for TextOverflowProperty {
+        type Cache = TextOverflow;
+        type Components = (Entity, Option<&'static mut TextOverflow>);
+        type Filters = (With<Node>, With<Text>);
+
+        fn name() -> &'static str {
+            "text-overflow"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            match values.keyword().as_deref() {
+                Some("clip") => Ok(TextOverflow::Clip),
+                Some("ellipsis") => Ok(TextOverflow::Ellipsis),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            (entity, maybe_overflow): QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            commands: &mut Commands,
+        ) {
+            if let Some(mut overflow) = maybe_overflow {
+                *overflow = *cache;
+            } else {
+                commands.entity(entity).insert(*cache);
+            }
+        }
+    }
+
+    /// Applies the `max-lines` property by inserting a [`MaxLines`] component on matched entities.
+    ///
+    /// The text is truncated by an internal system using the text layout computed by Bevy, check [`MaxLines`] for more.
+    #[derive(Default)]
+    pub struct MaxLinesProperty;
+
+    impl Property for MaxLinesProperty {
+        type Cache = usize;
+        type Components = Entity;
+        type Filters = (With<Node>, With<Text>);
+
+        fn name() -> &'static str {
+            "max-lines"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            match values.0.as_slice() {
+                [PropertyToken::Number(lines)] if lines.fract() == 0.0 && *lines >= 1.0 => {
+                    Ok(*lines as usize)
+                }
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            entity: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            commands: &mut Commands,
+        ) {
+            commands.entity(entity).insert(MaxLines(*cache));
+        }
+    }
+
+    /// Apply a custom `text-content` which updates [`TextSection::value`](`TextSection`) of all sections on matched [`Text`] components
+    ///
+    /// Each line of the content is placed on its own section, keeping the existing section style.
+    /// Sections are joined without separator when rendered, so every section but the last keeps its trailing line break.
+    /// New sections reuses the style of the first section. On CSS, a line break is written as `\A`, like `"first\A second"`.
+    #[derive(Default)]
+    pub struct TextContentProperty;
+
+    impl Property for TextContentProperty {
         type Cache = String;
         type Components = &'static mut Text;
         type Filters = With<Node>;
@@ -419,11 +1191,151 @@ mod text {
             _asset_server: &AssetServer,
             _commands: &mut Commands,
         ) {
+            let sections = &mut components.sections;
+            let style = sections
+                .first()
+                .map(|section| section.style.clone())
+                .unwrap_or_default();
+
+            let mut count = 0;
+            let lines = cache.split_inclusive('\n');
+            // `split_inclusive` yields nothing for an empty content, which still needs a single empty section
+            let lines = lines.chain(cache.is_empty().then_some(""));
+            for (index, line) in lines.enumerate() {
+                if let Some(section) = sections.get_mut(index) {
+                    section.value.clear();
+                    section.value.push_str(line);
+                } else {
+                    sections.push(TextSection::new(line, style.clone()));
+                }
+                count = index + 1;
+            }
+
+            sections.truncate(count);
+        }
+    }
+
+    /// Casing applied by `text-transform` property.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum TextTransform {
+        /// Text is kept as it is.
+        #[default]
+        None,
+        /// All characters are converted to uppercase.
+        Uppercase,
+        /// All characters are converted to lowercase.
+        Lowercase,
+        /// The first character of each word is converted to uppercase.
+        Capitalize,
+    }
+
+    impl TextTransform {
+        /// Applies this casing on the given text.
+        pub fn transform(&self, text: &str) -> String {
+            match self {
+                TextTransform::None => text.to_string(),
+                TextTransform::Uppercase => text.to_uppercase(),
+                TextTransform::Lowercase => text.to_lowercase(),
+                TextTransform::Capitalize => {
+                    let mut word_start = true;
+                    text.chars()
+                        .flat_map(|c| {
+                            let capitalize = word_start;
+                            word_start = c.is_whitespace();
+                            if capitalize {
+                                c.to_uppercase().collect::<SmallVec<[char; 2]>>()
+                            } else {
+                                SmallVec::from_elem(c, 1)
+                            }
+                        })
+                        .collect()
+                }
+            }
+        }
+    }
+
+    /// Applies the `text-transform` property on [`TextSection::value`](`TextSection`) of all sections on matched [`Text`] components.
+    ///
+    /// Since the original text isn't kept, `none` leaves the text as it is, instead of restoring a previous casing.
+    /// This property is applied on [`EcssSet::LateApply`](crate::EcssSet::LateApply), after `text-content`, so a text set
+    /// by style sheet is also transformed.
+    #[derive(Default)]
+    pub struct TextTransformProperty;
+
+    impl Property for TextTransformProperty {
+        type Cache = TextTransform;
+        type Components = &'static mut Text;
+        type Filters = With<Node>;
+
+        fn name() -> &'static str {
+            "text-transform"
+        }
+
+        fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+            match values.keyword().as_deref() {
+                Some("none") => Ok(TextTransform::None),
+                Some("uppercase") => Ok(TextTransform::Uppercase),
+                Some("lowercase") => Ok(TextTransform::Lowercase),
+                Some("capitalize") => Ok(TextTransform::Capitalize),
+                _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        fn apply<'w>(
+            cache: &Self::Cache,
+            mut components: QueryItem<Self::Components>,
+            _asset_server: &AssetServer,
+            _commands: &mut Commands,
+        ) {
+            if *cache == TextTransform::None {
+                return;
+            }
+
+            for section in components.sections.iter_mut() {
+                let value = cache.transform(&section.value);
+                // Avoids triggering text layout when nothing changes
+                if section.value != value {
+                    section.value = value;
+                }
+            }
+        }
+    }
+}
+
+/// Applies the `content` property on [`Text`] of pseudo-element entities, like `.icon::before { content: "A"; }`.
+///
+/// Only [`PseudoElementNode`] entities are affected, check it for more.
+#[derive(Default)]
+pub struct ContentProperty;
+
+impl Property for ContentProperty {
+    type Cache = String;
+    type Components = &'static mut Text;
+    type Filters = With<PseudoElementNode>;
+
+    fn name() -> &'static str {
+        "content"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        match values.as_slice() {
+            [PropertyToken::String(content)] => Ok(content.clone()),
+            _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        _commands: &mut Commands,
+    ) {
+        if let Some(section) = components.sections.first_mut() {
+            section.value.clone_from(cache);
+        } else {
             components
                 .sections
-                .iter_mut()
-                // TODO: Maybe change this so each line break is a new section
-                .for_each(|section| section.value.clone_from(cache));
+                .push(TextSection::new(cache.clone(), Default::default()));
         }
     }
 }
@@ -434,7 +1346,7 @@ pub struct BackgroundColorProperty;
 
 impl Property for BackgroundColorProperty {
     type Cache = Color;
-    type Components = Entity;
+    type Components = &'static mut BackgroundColor;
     type Filters = With<BackgroundColor>;
 
     fn name() -> &'static str {
@@ -451,61 +1363,150 @@ impl Property for BackgroundColorProperty {
 
     fn apply<'w>(
         cache: &Self::Cache,
-        components: QueryItem<Self::Components>,
+        mut components: QueryItem<Self::Components>,
         _asset_server: &AssetServer,
-        commands: &mut Commands,
+        _commands: &mut Commands,
     ) {
-        commands.entity(components).insert(BackgroundColor(*cache));
+        components.0 = *cache;
     }
 }
 
-/// Applies the `border-color` property on [`BorderColor`] component of matched entities.
-#[derive(Default)]
-pub struct BorderColorProperty;
+impl AnimatableProperty for BackgroundColorProperty {
+    type Target = BackgroundColor;
 
-impl Property for BorderColorProperty {
-    type Cache = Color;
-    type Components = Entity;
-    type Filters = With<BorderColor>;
+    fn get(target: &Self::Target) -> Self::Cache {
+        target.0
+    }
+
+    fn set(target: &mut Self::Target, value: &Self::Cache) {
+        target.0 = *value;
+    }
+}
+
+/// Applies the `opacity` property on the alpha channel of [`BackgroundColor`] and all [`Text`] sections
+/// of matched entities, on whichever of those components exists. Images are also faded, since [`BackgroundColor`]
+/// is the [`UiImage`] tint.
+///
+/// The alpha of colors before opacity was applied is multiplied, so translucent colors keeps their relative
+/// transparency. Those colors are kept on a [`CssOpacity`] component, so applying the style sheet again doesn't fade
+/// them once more. Opacity is applied on [`EcssSet::LateApply`](crate::EcssSet::LateApply), after colors set by
+/// style sheets are written.
+/// Accepts a number between `0` and `1` or a percentage, like `opacity: 0.5;` or `opacity: 50%;`.
+#[derive(Default)]
+pub struct OpacityProperty;
+
+impl Property for OpacityProperty {
+    type Cache = f32;
+    type Components = (
+        Entity,
+        Option<&'static mut BackgroundColor>,
+        Option<&'static mut Text>,
+        Option<&'static mut CssOpacity>,
+    );
+    type Filters = With<Node>;
 
     fn name() -> &'static str {
-        "border-color"
+        "opacity"
     }
 
     fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-        if let Some(color) = values.color() {
-            Ok(color)
-        } else {
-            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        match values.0.as_slice() {
+            [PropertyToken::Number(opacity)] => Ok(opacity.clamp(0.0, 1.0)),
+            [PropertyToken::Percentage(opacity)] => Ok((opacity / 100.0).clamp(0.0, 1.0)),
+            _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
         }
     }
 
     fn apply<'w>(
         cache: &Self::Cache,
-        components: QueryItem<Self::Components>,
+        (entity, maybe_background, maybe_text, maybe_opacity): QueryItem<Self::Components>,
         _asset_server: &AssetServer,
         commands: &mut Commands,
     ) {
-        commands.entity(components).insert(BorderColor(*cache));
+        let previous = maybe_opacity.as_deref().cloned().unwrap_or_default();
+        let mut opacity = CssOpacity {
+            opacity: *cache,
+            ..Default::default()
+        };
+
+        if let Some(mut background) = maybe_background {
+            let base = previous.base_color(background.0, previous.background);
+            background.0 = opacity.fade(base);
+            opacity.background = Some(base);
+        }
+
+        if let Some(mut text) = maybe_text {
+            opacity.sections = text
+                .sections
+                .iter_mut()
+                .enumerate()
+                .map(|(i, section)| {
+                    let base =
+                        previous.base_color(section.style.color, previous.sections.get(i).copied());
+                    section.style.color = opacity.fade(base);
+                    base
+                })
+                .collect();
+        }
+
+        if let Some(mut css_opacity) = maybe_opacity {
+            *css_opacity = opacity;
+        } else {
+            commands.entity(entity).insert(opacity);
+        }
     }
 }
 
-/// Applies the `image-path` property on [`bevy::ui::UiImage`] texture property of all sections on matched [`bevy::ui::UiImage`] components.
+/// Applies the `border-color` property on [`BorderColor`] component of matched entities.
+///
+/// The `currentColor` keyword uses the color of the first [`Text`] section of the entity, if any.
+/// Entities without [`Text`] keep their current border color.
+///
+/// The border is only visible once a width is set, like by the `border` property, so a warning is logged once for
+/// each rule which matches entities without border width.
 #[derive(Default)]
-pub struct ImageProperty;
+pub struct BorderColorProperty;
 
-impl Property for ImageProperty {
-    type Cache = String;
-    type Components = &'static mut UiImage;
-    type Filters = With<Node>;
+/// Returns the warning logged when the rule with the given `selector` applies `border-color` on entities which
+/// [`Style`] has no border width, since the border color isn't visible on those.
+fn border_color_warning<'a>(
+    selector: &Selector,
+    styles: impl IntoIterator<Item = Option<&'a Style>>,
+) -> Option<String> {
+    let count = styles
+        .into_iter()
+        .filter(|style| style.is_some_and(|style| style.border == UiRect::DEFAULT))
+        .count();
+
+    (count > 0).then(|| {
+        format!(
+            "{}: {} applied on {} entities without border width. Use `border` property to make it visible.",
+            selector,
+            BorderColorProperty::name(),
+            count
+        )
+    })
+}
+
+impl Property for BorderColorProperty {
+    // `None` means `currentColor`, which is resolved for each entity
+    type Cache = Option<Color>;
+    type Components = (
+        &'static mut BorderColor,
+        Option<&'static Style>,
+        Option<&'static Text>,
+    );
+    type Filters = With<BorderColor>;
 
     fn name() -> &'static str {
-        "image-path"
+        "border-color"
     }
 
     fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
-        if let Some(path) = values.string() {
-            Ok(path)
+        if let Some(color) = values.color() {
+            Ok(Some(color))
+        } else if values.is_current_color() {
+            Ok(None)
         } else {
             Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
         }
@@ -513,10 +1514,2434 @@ impl Property for ImageProperty {
 
     fn apply<'w>(
         cache: &Self::Cache,
-        mut components: QueryItem<Self::Components>,
-        asset_server: &AssetServer,
+        (mut border_color, _, maybe_text): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
         _commands: &mut Commands,
     ) {
-        components.texture = asset_server.load(cache);
+        let current_color = || {
+            maybe_text
+                .and_then(|text| text.sections.first())
+                .map(|section| section.style.color)
+        };
+
+        if let Some(color) = cache.or_else(current_color) {
+            border_color.0 = color;
+        }
+    }
+
+    fn apply_system(
+        mut local: Local<PropertyMeta<Self>>,
+        mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+        assets: Res<Assets<StyleSheetAsset>>,
+        apply_sheets: Res<StyleSheetState>,
+        mut q_nodes: Query<Self::Components, Self::Filters>,
+        asset_server: Res<AssetServer>,
+        mut commands: Commands,
+    ) {
+        local.for_each_selected(
+            &mut assets_events,
+            &assets,
+            &apply_sheets,
+            |cached, _, selector, entities| {
+                let styles = entities
+                    .iter()
+                    .map(|entity| q_nodes.get(*entity).ok().and_then(|(_, style, _)| style));
+                if let Some(warning) = border_color_warning(selector, styles) {
+                    warn!("{}", warning);
+                }
+
+                for entity in entities {
+                    if let Ok(components) = q_nodes.get_mut(*entity) {
+                        Self::apply(cached, components, &asset_server, &mut commands);
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Implements a new property for [`Outline`] component which expects a single value.
+///
+/// If the entity has no [`Outline`] component, a default one is inserted with the parsed value.
+macro_rules! impl_outline_single_value {
+    ($name:expr, $struct:ident, $cache:ty, $parse_func:ident, $outline_field:ident) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` property on [Outline::"]
+        #[doc = stringify!($outline_field)]
+        #[doc = "](`Outline`) field of matched entities."]
+        #[derive(Default)]
+        pub struct $struct;
+
+        impl Property for $struct {
+            type Cache = $cache;
+            type Components = (Entity, Option<&'static mut Outline>);
+            type Filters = With<Node>;
+
+            fn name() -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+                if let Some(val) = values.$parse_func() {
+                    Ok(val)
+                } else {
+                    Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+                }
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                (entity, maybe_outline): QueryItem<Self::Components>,
+                _asset_server: &AssetServer,
+                commands: &mut Commands,
+            ) {
+                if let Some(mut outline) = maybe_outline {
+                    outline.$outline_field = *cache;
+                } else {
+                    commands.entity(entity).insert(Outline {
+                        $outline_field: *cache,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    };
+}
+
+impl_outline_single_value!("outline-color", OutlineColorProperty, Color, color, color);
+impl_outline_single_value!("outline-width", OutlineWidthProperty, Val, val, width);
+impl_outline_single_value!("outline-offset", OutlineOffsetProperty, Val, val, offset);
+
+/// Implements a new property for [`ScrollPosition`] component which expects a single `px` value.
+///
+/// If the entity has no [`ScrollPosition`] component, a default one is inserted with the parsed value.
+macro_rules! impl_scroll_position {
+    ($name:expr, $struct:ident, $scroll_field:ident) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` property on [ScrollPosition::"]
+        #[doc = stringify!($scroll_field)]
+        #[doc = "](`ScrollPosition`) field of matched entities."]
+        #[derive(Default)]
+        pub struct $struct;
+
+        impl Property for $struct {
+            type Cache = f32;
+            type Components = (Entity, Option<&'static mut ScrollPosition>);
+            type Filters = With<Node>;
+
+            fn name() -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+                if let Some(Val::Px(offset)) = values.val() {
+                    Ok(offset)
+                } else {
+                    Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+                }
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                (entity, maybe_scroll): QueryItem<Self::Components>,
+                _asset_server: &AssetServer,
+                commands: &mut Commands,
+            ) {
+                if let Some(mut scroll) = maybe_scroll {
+                    scroll.$scroll_field = *cache;
+                } else {
+                    commands.entity(entity).insert(ScrollPosition {
+                        $scroll_field: *cache,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    };
+}
+
+impl_scroll_position!("scroll-x", ScrollXProperty, offset_x);
+impl_scroll_position!("scroll-y", ScrollYProperty, offset_y);
+
+/// Applies the `outline` shorthand property on [`Outline`] component of matched entities.
+///
+/// Both width and color are optional, like `2px solid red`. The outline style is ignored, since
+/// there is only solid outlines on `bevy_ui`.
+#[derive(Default)]
+pub struct OutlineProperty;
+
+impl Property for OutlineProperty {
+    type Cache = (Option<Val>, Option<Color>);
+    type Components = (Entity, Option<&'static mut Outline>);
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "outline"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        let width = values.val();
+        let color = values.iter().find_map(|token| match token {
+            PropertyToken::Identifier(name) => super::colors::parse_named_color(name),
+            PropertyToken::Hash(hash) => super::colors::parse_hex_color(hash),
+            _ => None,
+        });
+
+        if width.is_none() && color.is_none() {
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        } else {
+            Ok((width, color))
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, maybe_outline): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        let (width, color) = *cache;
+        let mut outline = maybe_outline.as_deref().copied().unwrap_or_default();
+
+        if let Some(width) = width {
+            outline.width = width;
+        }
+
+        if let Some(color) = color {
+            outline.color = color;
+        }
+
+        if let Some(mut current) = maybe_outline {
+            *current = outline;
+        } else {
+            commands.entity(entity).insert(outline);
+        }
+    }
+}
+
+/// Applies the `cursor` property by inserting a [`CssCursor`] component on matched entities.
+///
+/// The cursor icon of primary window is changed only while the entity is hovered or pressed.
+#[derive(Default)]
+pub struct CursorProperty;
+
+impl Property for CursorProperty {
+    type Cache = CursorIcon;
+    type Components = (Entity, Option<&'static mut CssCursor>);
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "cursor"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        let icon = match values.keyword().as_deref() {
+            Some("auto" | "default") => CursorIcon::Default,
+            Some("context-menu") => CursorIcon::ContextMenu,
+            Some("help") => CursorIcon::Help,
+            Some("pointer") => CursorIcon::Pointer,
+            Some("progress") => CursorIcon::Progress,
+            Some("wait") => CursorIcon::Wait,
+            Some("cell") => CursorIcon::Cell,
+            Some("crosshair") => CursorIcon::Crosshair,
+            Some("text") => CursorIcon::Text,
+            Some("vertical-text") => CursorIcon::VerticalText,
+            Some("alias") => CursorIcon::Alias,
+            Some("copy") => CursorIcon::Copy,
+            Some("move") => CursorIcon::Move,
+            Some("no-drop") => CursorIcon::NoDrop,
+            Some("not-allowed") => CursorIcon::NotAllowed,
+            Some("grab") => CursorIcon::Grab,
+            Some("grabbing") => CursorIcon::Grabbing,
+            Some("e-resize") => CursorIcon::EResize,
+            Some("n-resize") => CursorIcon::NResize,
+            Some("ne-resize") => CursorIcon::NeResize,
+            Some("nw-resize") => CursorIcon::NwResize,
+            Some("s-resize") => CursorIcon::SResize,
+            Some("se-resize") => CursorIcon::SeResize,
+            Some("sw-resize") => CursorIcon::SwResize,
+            Some("w-resize") => CursorIcon::WResize,
+            Some("ew-resize") => CursorIcon::EwResize,
+            Some("ns-resize") => CursorIcon::NsResize,
+            Some("nesw-resize") => CursorIcon::NeswResize,
+            Some("nwse-resize") => CursorIcon::NwseResize,
+            Some("col-resize") => CursorIcon::ColResize,
+            Some("row-resize") => CursorIcon::RowResize,
+            Some("all-scroll") => CursorIcon::AllScroll,
+            Some("zoom-in") => CursorIcon::ZoomIn,
+            Some("zoom-out") => CursorIcon::ZoomOut,
+            _ => return Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+        };
+
+        Ok(icon)
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, maybe_cursor): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        if let Some(mut cursor) = maybe_cursor {
+            cursor.0 = *cache;
+        } else {
+            commands.entity(entity).insert(CssCursor(*cache));
+        }
+    }
+}
+
+/// Applies the `pointer-events` property on [`FocusPolicy`] of matched entities, inserting it if needed.
+///
+/// `none` lets interactions pass through the entity to the ones below it, while `auto` blocks them.
+/// This only affects focus blocking, the entity is still rendered and may still be interacted with.
+#[derive(Default)]
+pub struct PointerEventsProperty;
+
+impl Property for PointerEventsProperty {
+    type Cache = FocusPolicy;
+    type Components = (Entity, Option<&'static mut FocusPolicy>);
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "pointer-events"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        match values.keyword().as_deref() {
+            Some("none") => Ok(FocusPolicy::Pass),
+            Some("auto") => Ok(FocusPolicy::Block),
+            _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, maybe_focus_policy): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        if let Some(mut focus_policy) = maybe_focus_policy {
+            *focus_policy = *cache;
+        } else {
+            commands.entity(entity).insert(*cache);
+        }
+    }
+}
+
+/// Logs the selector and sheet path of a rule with `debug: true;`, along with every entity matched by it and
+/// its [`Name`] and [`Class`], if any.
+///
+/// Useful to find out why a rule isn't being applied. Matched entities are logged at `info` level every time
+/// the style sheet is applied, so remember to remove it once you are done.
+#[derive(Default)]
+pub struct DebugProperty;
+
+impl Property for DebugProperty {
+    type Cache = bool;
+    type Components = (Entity, Option<&'static Name>, Option<&'static Class>);
+    type Filters = ();
+
+    fn name() -> &'static str {
+        "debug"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        match values.keyword().as_deref() {
+            Some("true") => Ok(true),
+            Some("false") => Ok(false),
+            _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, name, class): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        _commands: &mut Commands,
+    ) {
+        if *cache {
+            info!("  {}", debug_entity(entity, name, class));
+        }
+    }
+
+    // Overridden since `apply` doesn't know which rule matched the entity.
+    fn apply_system(
+        mut local: Local<PropertyMeta<Self>>,
+        mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+        assets: Res<Assets<StyleSheetAsset>>,
+        apply_sheets: Res<StyleSheetState>,
+        mut q_nodes: Query<Self::Components, Self::Filters>,
+        asset_server: Res<AssetServer>,
+        mut commands: Commands,
+    ) {
+        local.for_each_selected(
+            &mut assets_events,
+            &assets,
+            &apply_sheets,
+            |cached, rules, selector, entities| {
+                if !*cached {
+                    return;
+                }
+
+                info!("{}", debug_rule(selector, rules.path(), entities.len()));
+                for entity in entities {
+                    if let Ok(components) = q_nodes.get_mut(*entity) {
+                        Self::apply(cached, components, &asset_server, &mut commands);
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Describes a rule logged by [`DebugProperty`], like `Rule "button" from sheet "main.css" matched 2 entities:`.
+fn debug_rule(selector: &Selector, path: &str, count: usize) -> String {
+    format!(r#"Rule "{selector}" from sheet "{path}" matched {count} entities:"#)
+}
+
+/// Describes an entity logged by [`DebugProperty`], like `3v1 "save" .primary.large`.
+fn debug_entity(entity: Entity, name: Option<&Name>, class: Option<&Class>) -> String {
+    let mut description = format!("{:?}", entity);
+
+    if let Some(name) = name {
+        description.push_str(&format!(" {:?}", name.as_str()));
+    }
+
+    if let Some(class) = class.filter(|class| !class.is_empty()) {
+        description.push(' ');
+        for name in class.split_ascii_whitespace() {
+            description.push('.');
+            description.push_str(name);
+        }
+    }
+
+    description
+}
+
+/// Applies the `transform` property on [`Transform`] of matched entities.
+///
+/// The `translate(x, y)`, `rotate(angle)` and `scale(x, y)` functions are supported, where `y` is optional.
+/// Functions can be combined, like `transform: scale(1.1) rotate(10deg);`, and `none` resets rotation and scale.
+/// Angles may be written in `deg`, `rad`, `grad` or `turn` and are converted to radians of a rotation around the Z axis.
+///
+/// Bevy UI layout overrides the translation of every [`Node`] on each layout update, so `translate` is only
+/// useful on entities which aren't positioned by the layout. Rotation and scale are kept by the layout.
+#[derive(Default)]
+pub struct TransformProperty;
+
+impl Property for TransformProperty {
+    type Cache = (Option<Vec2>, Option<f32>, Option<Vec2>);
+    type Components = &'static mut Transform;
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "transform"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        let invalid = || EcssError::InvalidPropertyValue(Self::name().to_string());
+
+        if values.keyword().as_deref() == Some("none") && values.len() == 1 {
+            return Ok((None, Some(0.0), Some(Vec2::ONE)));
+        }
+
+        let length = |token: &PropertyToken| match token {
+            PropertyToken::Dimension(val) => Ok(*val),
+            PropertyToken::Number(val) if *val == 0.0 => Ok(0.0),
+            _ => Err(invalid()),
+        };
+
+        let (mut translation, mut rotation, mut scale) = (None, None, None);
+        let mut tokens = values.iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            let PropertyToken::Identifier(function) = token else {
+                return Err(invalid());
+            };
+
+            let mut args = SmallVec::<[&PropertyToken; 2]>::new();
+            while let Some(arg) =
+                tokens.next_if(|token| !matches!(token, PropertyToken::Identifier(_)))
+            {
+                args.push(arg);
+            }
+
+            match (function.to_ascii_lowercase().as_str(), args.as_slice()) {
+                ("translate", [x]) => translation = Some(Vec2::new(length(x)?, 0.0)),
+                ("translate", [x, y]) => translation = Some(Vec2::new(length(x)?, length(y)?)),
+                ("rotate", [PropertyToken::Angle(angle)]) => rotation = Some(*angle),
+                ("rotate", [PropertyToken::Number(angle)]) if *angle == 0.0 => rotation = Some(0.0),
+                ("scale", [PropertyToken::Number(s)]) => scale = Some(Vec2::splat(*s)),
+                ("scale", [PropertyToken::Number(x), PropertyToken::Number(y)]) => {
+                    scale = Some(Vec2::new(*x, *y))
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        if translation.is_none() && rotation.is_none() && scale.is_none() {
+            Err(invalid())
+        } else {
+            Ok((translation, rotation, scale))
+        }
+    }
+
+    fn apply<'w>(
+        (translation, rotation, scale): &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        _commands: &mut Commands,
+    ) {
+        if let Some(translation) = translation {
+            components.translation = translation.extend(components.translation.z);
+        }
+
+        if let Some(rotation) = rotation {
+            components.rotation = Quat::from_rotation_z(*rotation);
+        }
+
+        if let Some(scale) = scale {
+            components.scale = scale.extend(components.scale.z);
+        }
+    }
+}
+
+/// Applies the `background` shorthand property, which sets [`BackgroundColor`] and/or [`UiImage`] of matched entities.
+///
+/// Both color and image are optional, like `background: red;`, `background: url("image.png");` or
+/// `background: red url("image.png");`.
+///
+/// A `linear-gradient(...)` can also be used alone, like `background: linear-gradient(45deg, red, blue);`,
+/// which inserts a [`CssGradient`] component instead, since Bevy can't render gradients yet.
+#[derive(Default)]
+pub struct BackgroundProperty;
+
+impl Property for BackgroundProperty {
+    type Cache = (Option<Color>, Option<String>, Option<CssGradient>);
+    type Components = Entity;
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "background"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        if let [PropertyToken::Identifier(function), args @ ..] = values.as_slice() {
+            if function == "linear-gradient" {
+                return parse_linear_gradient(args)
+                    .map(|gradient| (None, None, Some(gradient)))
+                    .ok_or_else(|| EcssError::InvalidPropertyValue(Self::name().to_string()));
+            }
+        }
+
+        let mut color = None;
+        let mut image = None;
+
+        let mut tokens = values.iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                PropertyToken::Identifier(function) if function == "url" && image.is_none() => {
+                    match tokens.next() {
+                        Some(PropertyToken::String(path)) => image = Some(path.clone()),
+                        _ => return Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+                    }
+                }
+                PropertyToken::Identifier(_) | PropertyToken::Hash(_) if color.is_none() => {
+                    color = super::colors::parse_color(token, &mut tokens);
+                    if color.is_none() {
+                        return Err(EcssError::InvalidPropertyValue(Self::name().to_string()));
+                    }
+                }
+                _ => return Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        if color.is_none() && image.is_none() {
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        } else {
+            Ok((color, image, None))
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        components: QueryItem<Self::Components>,
+        asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        let (color, image, gradient) = cache;
+        let mut entity = commands.entity(components);
+
+        if let Some(gradient) = gradient {
+            entity.insert(gradient.clone());
+        } else {
+            entity.remove::<CssGradient>();
+        }
+
+        if let Some(color) = color {
+            entity.insert(BackgroundColor(*color));
+        }
+
+        if let Some(image) = image {
+            entity.insert(UiImage::new(asset_server.load(image)));
+        }
+    }
+}
+
+/// Parses `linear-gradient(...)` arguments, like `45deg, red, blue 80%` or `to right, red, blue`.
+///
+/// The direction is optional and defaults to `to bottom`. At least two color stops are required.
+fn parse_linear_gradient(args: &[PropertyToken]) -> Option<CssGradient> {
+    use std::f32::consts::PI;
+
+    let mut gradient = CssGradient::default();
+    let mut tokens = args.iter().peekable();
+
+    match tokens.peek() {
+        Some(PropertyToken::Angle(angle)) => {
+            gradient.angle = *angle;
+            tokens.next();
+        }
+        Some(PropertyToken::Identifier(to)) if to == "to" => {
+            tokens.next();
+            let (mut x, mut y) = (0.0, 0.0);
+            while let Some(PropertyToken::Identifier(side)) = tokens.peek() {
+                match side.as_str() {
+                    "top" => y += 1.0,
+                    "bottom" => y -= 1.0,
+                    "right" => x += 1.0,
+                    "left" => x -= 1.0,
+                    _ => break,
+                }
+                tokens.next();
+            }
+
+            if x == 0.0 && y == 0.0 {
+                return None;
+            }
+
+            // Clockwise from top, so `to right` is `90deg`
+            gradient.angle = f32::atan2(x, y).rem_euclid(2.0 * PI);
+        }
+        _ => (),
+    }
+
+    while let Some(token) = tokens.next() {
+        let color = super::colors::parse_color(token, &mut tokens)?;
+
+        let position = match tokens.peek() {
+            Some(PropertyToken::Percentage(position)) => {
+                tokens.next();
+                Some(position / 100.0)
+            }
+            _ => None,
+        };
+
+        gradient.stops.push((color, position));
+    }
+
+    (gradient.stops.len() >= 2).then_some(gradient)
+}
+
+/// Applies the `box-shadow` property, which inserts a [`CssBoxShadow`] on matched entities, like
+/// `box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);`.
+///
+/// Two lengths are required for the offset, followed by optional blur and spread radius. The color and the `inset`
+/// keyword may be placed before or after lengths. The `none` keyword removes the [`CssBoxShadow`] component.
+///
+/// Nothing is rendered, since Bevy doesn't support box shadows yet, check [`CssBoxShadow`] for more.
+#[derive(Default)]
+pub struct BoxShadowProperty;
+
+impl Property for BoxShadowProperty {
+    // `None` means `none`
+    type Cache = Option<CssBoxShadow>;
+    type Components = (Entity, Option<&'static mut CssBoxShadow>);
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "box-shadow"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        let invalid = || EcssError::InvalidPropertyValue(Self::name().to_string());
+
+        if values.len() == 1 && values.keyword().is_some_and(|keyword| keyword == "none") {
+            return Ok(None);
+        }
+
+        let mut shadow = CssBoxShadow::default();
+        let mut lengths = SmallVec::<[Val; 4]>::new();
+        let mut lengths_ended = false;
+        let mut color = None;
+
+        let mut tokens = values.iter().peekable();
+        while let Some(token) = tokens.next() {
+            if let Some(length) = PropertyValues::token_val(token).filter(|v| *v != Val::Auto) {
+                // Lengths must be together, like `0 2px 4px red`
+                if lengths_ended {
+                    return Err(invalid());
+                }
+                lengths.push(length);
+                continue;
+            }
+
+            lengths_ended = !lengths.is_empty();
+
+            let parsed_color = match token {
+                PropertyToken::Identifier(keyword) if keyword == "inset" && !shadow.inset => {
+                    shadow.inset = true;
+                    continue;
+                }
+                // Only a single shadow is supported, like a single `CssBoxShadow` component
+                PropertyToken::Comma => {
+                    return Err(EcssError::UnsupportedPropertyValue {
+                        property: Self::name().to_string(),
+                        value: "multiple shadows".to_string(),
+                    })
+                }
+                token => super::colors::parse_color(token, &mut tokens),
+            };
+
+            match parsed_color {
+                Some(parsed_color) if color.is_none() => color = Some(parsed_color),
+                _ => return Err(invalid()),
+            }
+        }
+
+        match lengths.as_slice() {
+            [x, y, radius @ ..] if radius.len() <= 2 => {
+                shadow.offset_x = *x;
+                shadow.offset_y = *y;
+                if let Some(blur) = radius.first() {
+                    shadow.blur = *blur;
+                }
+                if let Some(spread) = radius.get(1) {
+                    shadow.spread = *spread;
+                }
+            }
+            _ => return Err(invalid()),
+        }
+
+        if let Some(color) = color {
+            shadow.color = color;
+        }
+
+        Ok(Some(shadow))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, maybe_shadow): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        match (cache, maybe_shadow) {
+            (Some(shadow), Some(mut current)) => *current = *shadow,
+            (Some(shadow), None) => {
+                commands.entity(entity).insert(*shadow);
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<CssBoxShadow>();
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+/// Applies the `image-path` property on [`bevy::ui::UiImage`] texture property of all sections on matched [`bevy::ui::UiImage`] components.
+#[derive(Default)]
+pub struct ImageProperty;
+
+impl Property for ImageProperty {
+    type Cache = String;
+    type Components = &'static mut UiImage;
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "image-path"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        if let Some(path) = values.url() {
+            Ok(path)
+        } else {
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        asset_server: &AssetServer,
+        _commands: &mut Commands,
+    ) {
+        components.texture = asset_server.load(cache);
+    }
+}
+
+/// Applies the `object-fit` property on [`ObjectFit`] of matched components, inserting it if needed.
+///
+/// Bevy has no direct equivalent of `contain` and `cover`, so both are approximated, check [`ObjectFit`] for more.
+#[derive(Default)]
+pub struct ObjectFitProperty;
+
+impl Property for ObjectFitProperty {
+    type Cache = ObjectFit;
+    type Components = (Entity, Option<&'static mut ObjectFit>);
+    type Filters = (With<Node>, With<UiImage>);
+
+    fn name() -> &'static str {
+        "object-fit"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        match values.keyword().as_deref() {
+            Some("fill") => Ok(ObjectFit::Fill),
+            Some("contain") => Ok(ObjectFit::Contain),
+            Some("cover") => Ok(ObjectFit::Cover),
+            _ => Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        (entity, maybe_fit): QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        if let Some(mut fit) = maybe_fit {
+            *fit = *cache;
+        } else {
+            commands.entity(entity).insert(*cache);
+        }
+    }
+}
+
+/// Applies the `transition` property by inserting a [`Transitions`] component on matched entities.
+///
+/// Each transition is composed by a property name followed by a duration, like `background-color 0.2s`.
+/// Only properties which implements [`AnimatableProperty`] are animated.
+#[derive(Default)]
+pub struct TransitionProperty;
+
+impl Property for TransitionProperty {
+    type Cache = Transitions;
+    type Components = Entity;
+    type Filters = With<Node>;
+
+    fn name() -> &'static str {
+        "transition"
+    }
+
+    fn parse<'a>(values: &PropertyValues) -> Result<Self::Cache, EcssError> {
+        if values.identifier() == Some("none") {
+            return Ok(Transitions::default());
+        }
+
+        let mut transitions = Transitions::default();
+        let mut name = None;
+
+        for token in values.iter() {
+            match (token, name.take()) {
+                (PropertyToken::Identifier(ident), None) => name = Some(ident.clone()),
+                (PropertyToken::Time(duration), Some(name)) => {
+                    transitions.insert(name, *duration);
+                }
+                (PropertyToken::Comma, None) if !transitions.is_empty() => (),
+                _ => return Err(EcssError::InvalidPropertyValue(Self::name().to_string())),
+            }
+        }
+
+        if transitions.is_empty() || name.is_some() {
+            Err(EcssError::InvalidPropertyValue(Self::name().to_string()))
+        } else {
+            Ok(transitions)
+        }
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        commands.entity(components).insert(cache.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::smallvec;
+
+    use super::*;
+    use crate::{Class, FontRegistry};
+
+    #[test]
+    fn parse_border_zero() {
+        let values = PropertyValues(smallvec![PropertyToken::Number(0.0)]);
+        assert_eq!(
+            BorderWidthProperty::parse(&values).unwrap(),
+            UiRect::all(Val::Px(0.0))
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(0.0),
+            PropertyToken::Dimension(2.0)
+        ]);
+        assert_eq!(
+            BorderWidthProperty::parse(&values).unwrap(),
+            UiRect::axes(Val::Px(2.0), Val::Px(0.0))
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Number(1.0)]);
+        assert!(
+            BorderWidthProperty::parse(&values).is_err(),
+            "Only unitless zero is allowed"
+        );
+    }
+
+    #[test]
+    fn parse_border_style() {
+        let sheet = StyleSheetAsset::parse(
+            "",
+            "a { border: 2px dashed red; } b { border: 2px solid red; }",
+        );
+        let mut rules = sheet.iter();
+
+        let dashed = &rules.next().unwrap().properties["border"];
+        assert_eq!(
+            BorderProperty::parse(dashed).unwrap(),
+            (Some(UiRect::all(Val::Px(2.0))), Some(Color::RED)),
+            "Should ignore the border style"
+        );
+        assert_eq!(
+            style::unsupported_border_style(dashed).as_deref(),
+            Some("dashed"),
+            "Should warn about the border style"
+        );
+
+        let solid = &rules.next().unwrap().properties["border"];
+        assert_eq!(style::unsupported_border_style(solid), None);
+    }
+
+    #[test]
+    fn parse_border_shorthand() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ border: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            BorderProperty::parse(&rule.properties["border"])
+        };
+
+        assert_eq!(
+            parse("2px solid blue").unwrap(),
+            (Some(UiRect::all(Val::Px(2.0))), Some(Color::BLUE))
+        );
+        assert_eq!(
+            parse("2px 0").unwrap(),
+            (Some(UiRect::axes(Val::Px(0.0), Val::Px(2.0))), None)
+        );
+        assert_eq!(parse("#ff0000").unwrap(), (None, Some(Color::RED)));
+        assert!(parse("solid").is_err());
+        assert_eq!(parse("unset").unwrap(), (Some(UiRect::DEFAULT), None));
+
+        let sheet = StyleSheetAsset::parse("", "a { border-width: 2px; }");
+        let rule = sheet.iter().next().unwrap();
+        assert_eq!(
+            BorderWidthProperty::parse(&rule.properties["border-width"]).unwrap(),
+            UiRect::all(Val::Px(2.0))
+        );
+    }
+
+    #[test]
+    fn parse_rect_shorthand() {
+        use crate::StyleSheetAsset;
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .one { margin: 10px; }
+            .two { margin: 10px 20px; }
+            .three { margin: 10px 20px 30px; }
+            .four { margin: 10px 20px 30px 40px; }
+            "#,
+        );
+
+        let rules = sheet.iter().collect::<Vec<_>>();
+        let expected = [
+            UiRect::all(Val::Px(10.0)),
+            UiRect::new(Val::Px(20.0), Val::Px(20.0), Val::Px(10.0), Val::Px(10.0)),
+            UiRect::new(Val::Px(20.0), Val::Px(20.0), Val::Px(10.0), Val::Px(30.0)),
+            UiRect::new(Val::Px(40.0), Val::Px(20.0), Val::Px(10.0), Val::Px(30.0)),
+        ];
+
+        for (rule, expected) in rules.iter().zip(expected) {
+            assert_eq!(
+                MarginProperty::parse(&rule.properties["margin"]).unwrap(),
+                expected,
+                "Wrong expansion on ({})",
+                rule.selector
+            );
+        }
+
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ margin: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            MarginProperty::parse(&rule.properties["margin"])
+        };
+
+        assert!(
+            parse("10px foo 20px").is_err(),
+            "Unknown values shouldn't be skipped"
+        );
+        assert!(parse("1px 2px 3px 4px 5px").is_err());
+        assert!(parse("calc(10px + 5px) 20px").is_err());
+        assert!(parse("10px calc(5px)").is_err());
+        assert_eq!(
+            parse("calc(10px + 5px)").unwrap(),
+            UiRect::all(Val::Px(15.0)),
+            "A single function should be used on all sides"
+        );
+    }
+
+    #[test]
+    fn apply_border_zero() {
+        use crate::{EcssPlugin, StyleSheet, StyleSheetAsset};
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let hidden = assets.add(StyleSheetAsset::parse(
+            "",
+            "node { border: 10px; border-color: red; } .hidden { border: 0; }",
+        ));
+        let color_only = assets.add(StyleSheetAsset::parse("", "node { border-color: red; }"));
+
+        let hidden = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                Class::new("hidden"),
+                StyleSheet::new(hidden),
+            ))
+            .id();
+        let color_only = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(color_only)))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(hidden).unwrap().border,
+            UiRect::all(Val::Px(0.0))
+        );
+        assert_eq!(app.world.get::<BorderColor>(hidden).unwrap().0, Color::RED);
+
+        assert_eq!(
+            app.world.get::<Style>(color_only).unwrap().border,
+            UiRect::DEFAULT
+        );
+        assert_eq!(
+            app.world.get::<BorderColor>(color_only).unwrap().0,
+            Color::RED
+        );
+    }
+
+    #[test]
+    fn parse_outline_longhands() {
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("red".to_string())]);
+        assert_eq!(OutlineColorProperty::parse(&values).unwrap(), Color::RED);
+
+        let values = PropertyValues(smallvec![PropertyToken::Dimension(2.0)]);
+        assert_eq!(OutlineWidthProperty::parse(&values).unwrap(), Val::Px(2.0));
+
+        let values = PropertyValues(smallvec![PropertyToken::Percentage(10.0)]);
+        assert_eq!(
+            OutlineOffsetProperty::parse(&values).unwrap(),
+            Val::Percent(10.0)
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::String("red".to_string())]);
+        assert!(OutlineColorProperty::parse(&values).is_err());
+        assert!(OutlineWidthProperty::parse(&values).is_err());
+        assert!(OutlineOffsetProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_scroll_position() {
+        let values = PropertyValues(smallvec![PropertyToken::Dimension(20.0)]);
+        assert_eq!(ScrollXProperty::parse(&values).unwrap(), 20.0);
+        assert_eq!(ScrollYProperty::parse(&values).unwrap(), 20.0);
+
+        let values = PropertyValues(smallvec![PropertyToken::Percentage(10.0)]);
+        assert!(
+            ScrollYProperty::parse(&values).is_err(),
+            "Only px values are supported"
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("auto".to_string())]);
+        assert!(ScrollXProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_outline_shorthand() {
+        let values = PropertyValues(smallvec![
+            PropertyToken::Dimension(2.0),
+            PropertyToken::Identifier("solid".to_string()),
+            PropertyToken::Identifier("red".to_string()),
+        ]);
+
+        assert_eq!(
+            OutlineProperty::parse(&values).unwrap(),
+            (Some(Val::Px(2.0)), Some(Color::RED))
+        );
+    }
+
+    #[test]
+    fn parse_gap_shorthand() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ gap: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            GapProperty::parse(&rule.properties["gap"])
+        };
+
+        assert_eq!(parse("10px").unwrap(), (Val::Px(10.0), Val::Px(10.0)));
+        assert_eq!(
+            parse("10px 5%").unwrap(),
+            (Val::Px(10.0), Val::Percent(5.0))
+        );
+        assert_eq!(parse("unset").unwrap(), (Val::Px(0.0), Val::Px(0.0)));
+        assert!(parse("1px 2px 3px").is_err());
+        assert!(parse("red").is_err());
+    }
+
+    #[test]
+    fn parse_overflow_shorthand() {
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("hidden".to_string())]);
+        assert_eq!(OverflowProperty::parse(&values).unwrap(), Overflow::clip());
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Identifier("visible".to_string()),
+            PropertyToken::Identifier("hidden".to_string())
+        ]);
+        assert_eq!(
+            OverflowProperty::parse(&values).unwrap(),
+            Overflow::clip_y()
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("scroll".to_string())]);
+        assert_eq!(OverflowProperty::parse(&values).unwrap(), Overflow::clip());
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Identifier("visible".to_string()),
+            PropertyToken::Identifier("hidden".to_string()),
+            PropertyToken::Identifier("hidden".to_string())
+        ]);
+        assert!(OverflowProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_overflow_axis() {
+        for (keyword, axis) in [
+            ("visible", OverflowAxis::Visible),
+            ("hidden", OverflowAxis::Clip),
+            ("clip", OverflowAxis::Clip),
+            ("scroll", OverflowAxis::Clip),
+            ("auto", OverflowAxis::Clip),
+        ] {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            assert_eq!(
+                OverflowAxisXProperty::parse(&values).unwrap(),
+                axis,
+                "{keyword}"
+            );
+            assert_eq!(
+                OverflowAxisYProperty::parse(&values).unwrap(),
+                axis,
+                "{keyword}"
+            );
+        }
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("overlay".to_string())]);
+        assert!(OverflowAxisXProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_flex_shorthand() {
+        let values = PropertyValues(smallvec![PropertyToken::Number(1.0)]);
+        assert_eq!(
+            FlexProperty::parse(&values).unwrap(),
+            (1.0, 1.0, Val::Px(0.0))
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(2.0),
+            PropertyToken::Number(0.0),
+            PropertyToken::Dimension(100.0)
+        ]);
+        assert_eq!(
+            FlexProperty::parse(&values).unwrap(),
+            (2.0, 0.0, Val::Px(100.0))
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(2.0),
+            PropertyToken::Percentage(50.0)
+        ]);
+        assert_eq!(
+            FlexProperty::parse(&values).unwrap(),
+            (2.0, 1.0, Val::Percent(50.0))
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("none".to_string())]);
+        assert_eq!(FlexProperty::parse(&values).unwrap(), (0.0, 0.0, Val::Auto));
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(1.0),
+            PropertyToken::Number(1.0),
+            PropertyToken::Number(1.0)
+        ]);
+        assert!(
+            FlexProperty::parse(&values).is_err(),
+            "Unitless basis is only allowed for zero"
+        );
+    }
+
+    #[test]
+    fn parse_cursor() {
+        for (keyword, icon) in [
+            ("pointer", CursorIcon::Pointer),
+            ("default", CursorIcon::Default),
+            ("auto", CursorIcon::Default),
+            ("text", CursorIcon::Text),
+            ("grab", CursorIcon::Grab),
+            ("not-allowed", CursorIcon::NotAllowed),
+            ("col-resize", CursorIcon::ColResize),
+        ] {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            assert_eq!(CursorProperty::parse(&values).unwrap(), icon, "{keyword}");
+        }
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("hand".to_string())]);
+        assert!(CursorProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn apply_pointer_events() {
+        let (app, button) =
+            crate::apply_test_sheet("button { pointer-events: none; }", |world, root| {
+                let button = world.spawn(ButtonBundle::default()).set_parent(root).id();
+                assert_eq!(world.get::<FocusPolicy>(button), Some(&FocusPolicy::Block));
+                button
+            });
+
+        assert_eq!(
+            app.world.get::<FocusPolicy>(button),
+            Some(&FocusPolicy::Pass)
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("auto".to_string())]);
+        assert_eq!(
+            PointerEventsProperty::parse(&values).unwrap(),
+            FocusPolicy::Block
+        );
+    }
+
+    #[test]
+    fn apply_debug() {
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("true".to_string())]);
+        assert!(DebugProperty::parse(&values).unwrap());
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("FALSE".to_string())]);
+        assert!(!DebugProperty::parse(&values).unwrap());
+
+        let values = PropertyValues(smallvec![PropertyToken::Number(1.0)]);
+        assert!(DebugProperty::parse(&values).is_err());
+
+        let (app, (root, button)) =
+            crate::apply_test_sheet("button { debug: true; }", |world, root| {
+                let button = world
+                    .spawn((
+                        ButtonBundle::default(),
+                        Name::new("save"),
+                        Class::new("primary large"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                (root, button)
+            });
+
+        let entity = app.world.entity(button);
+        assert_eq!(
+            debug_entity(button, entity.get::<Name>(), entity.get::<Class>()),
+            format!(r#"{button:?} "save" .primary.large"#)
+        );
+        assert_eq!(debug_entity(root, None, None), format!("{root:?}"));
+        assert_eq!(
+            debug_rule(&Selector::parse("button").unwrap(), "main.css", 2),
+            r#"Rule "button" from sheet "main.css" matched 2 entities:"#
+        );
+    }
+
+    #[test]
+    fn apply_opacity() {
+        use crate::StyleSheet;
+
+        let (mut app, (root, node, text, translucent, tinted)) = crate::apply_test_sheet(
+            r#".faded { opacity: 0.5; background-color: red; color: blue; }
+            .translucent { opacity: 0.5; background: #ff000080; }
+            .tinted { opacity: 0.5; }"#,
+            |world, root| {
+                let node = world
+                    .spawn((NodeBundle::default(), Class::new("faded")))
+                    .set_parent(root)
+                    .id();
+                let text = world
+                    .spawn((
+                        TextBundle::from_sections([
+                            TextSection::from_style(TextStyle::default()),
+                            TextSection::from_style(TextStyle::default()),
+                        ]),
+                        Class::new("faded"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                let translucent = world
+                    .spawn((NodeBundle::default(), Class::new("translucent")))
+                    .set_parent(root)
+                    .id();
+                let tinted = world
+                    .spawn((
+                        TextBundle::from_section(
+                            "tinted",
+                            TextStyle {
+                                color: Color::GREEN,
+                                ..default()
+                            },
+                        )
+                        .with_background_color(Color::RED),
+                        Class::new("tinted"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                (root, node, text, translucent, tinted)
+            },
+        );
+
+        assert_eq!(
+            app.world.get::<BackgroundColor>(node).unwrap().0,
+            Color::RED.with_a(0.5)
+        );
+        let alpha = app.world.get::<BackgroundColor>(translucent).unwrap().0.a();
+        assert!(
+            (alpha - 128.0 / 255.0 * 0.5).abs() < f32::EPSILON,
+            "Opacity should multiply the alpha written by the background shorthand"
+        );
+        for section in &app.world.get::<Text>(text).unwrap().sections {
+            assert_eq!(section.style.color, Color::BLUE.with_a(0.5));
+        }
+
+        // Colors which aren't written by the sheet again shouldn't be faded once more
+        for _ in 0..2 {
+            app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+            app.update();
+            app.update();
+        }
+        assert_eq!(app.world.get::<BackgroundColor>(tinted).unwrap().0.a(), 0.5);
+        assert_eq!(
+            app.world.get::<Text>(tinted).unwrap().sections[0]
+                .style
+                .color,
+            Color::GREEN.with_a(0.5)
+        );
+        assert_eq!(
+            app.world.get::<BackgroundColor>(node).unwrap().0,
+            Color::RED.with_a(0.5)
+        );
+
+        // Colors written by anything else are faded from the written color
+        app.world.get_mut::<BackgroundColor>(tinted).unwrap().0 = Color::BLUE;
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+        assert_eq!(
+            app.world.get::<BackgroundColor>(tinted).unwrap().0,
+            Color::BLUE.with_a(0.5)
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Percentage(25.0)]);
+        assert_eq!(OpacityProperty::parse(&values).unwrap(), 0.25);
+
+        let values = PropertyValues(smallvec![PropertyToken::Number(2.0)]);
+        assert_eq!(OpacityProperty::parse(&values).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn parse_transform() {
+        use crate::StyleSheetAsset;
+
+        let rules = StyleSheetAsset::parse(
+            "",
+            r#"
+            a { transform: scale(1.1); }
+            b { transform: scale(2, 0.5); }
+            c { transform: rotate(90deg); }
+            d { transform: rotate(0.5turn); }
+            e { transform: translate(10px, 0); }
+            f { transform: translate(5px) rotate(0) scale(1.5); }
+            g { transform: none; }
+            h { transform: rotate(90); }
+            i { transform: skew(10deg); }
+            j { transform: 10px; }
+            "#,
+        );
+        let rules = rules.iter().collect::<Vec<_>>();
+        let parse = |i: usize| TransformProperty::parse(&rules[i].properties["transform"]);
+
+        assert_eq!(parse(0).unwrap(), (None, None, Some(Vec2::splat(1.1))));
+        assert_eq!(parse(1).unwrap(), (None, None, Some(Vec2::new(2.0, 0.5))));
+        assert_eq!(
+            parse(2).unwrap(),
+            (None, Some(std::f32::consts::FRAC_PI_2), None)
+        );
+        assert_eq!(parse(3).unwrap(), (None, Some(std::f32::consts::PI), None));
+        assert_eq!(parse(4).unwrap(), (Some(Vec2::new(10.0, 0.0)), None, None));
+        assert_eq!(
+            parse(5).unwrap(),
+            (Some(Vec2::new(5.0, 0.0)), Some(0.0), Some(Vec2::splat(1.5)))
+        );
+        assert_eq!(parse(6).unwrap(), (None, Some(0.0), Some(Vec2::ONE)));
+
+        for rule in &rules[7..] {
+            let values = &rule.properties["transform"];
+            assert!(TransformProperty::parse(values).is_err(), "{values:?}");
+        }
+    }
+
+    #[test]
+    fn apply_multi_line_text_content() {
+        let style = TextStyle {
+            font_size: 42.0,
+            ..Default::default()
+        };
+        let (app, text) =
+            crate::apply_test_sheet(r#"text { text-content: "a\A b"; }"#, |world, root| {
+                world
+                    .spawn(TextBundle::from_sections([
+                        TextSection::new("x", style.clone()),
+                        TextSection::new("y", style.clone()),
+                        TextSection::new("z", style.clone()),
+                    ]))
+                    .set_parent(root)
+                    .id()
+            });
+
+        let sections = &app.world.get::<Text>(text).unwrap().sections;
+        assert_eq!(sections.len(), 2, "Each line should be a section");
+        assert_eq!(sections[0].value, "a\n");
+        assert_eq!(sections[1].value, "b");
+        assert_eq!(
+            sections
+                .iter()
+                .map(|s| s.value.as_str())
+                .collect::<String>(),
+            "a\nb",
+            "Rendered text should keep the line break"
+        );
+        assert_eq!(sections[1].style.font_size, 42.0, "Styles should be kept");
+    }
+
+    #[test]
+    fn parse_background_shorthand() {
+        use crate::StyleSheetAsset;
+
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ background: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            BackgroundProperty::parse(&rule.properties["background"])
+        };
+
+        assert_eq!(parse("red").unwrap(), (Some(Color::RED), None, None));
+        assert_eq!(
+            parse(r#"url("x.png")"#).unwrap(),
+            (None, Some("x.png".to_string()), None)
+        );
+        assert_eq!(
+            parse(r#"red url("x.png")"#).unwrap(),
+            (Some(Color::RED), Some("x.png".to_string()), None)
+        );
+        assert_eq!(
+            parse("url(x.png) #ff0000").unwrap(),
+            (Some(Color::RED), Some("x.png".to_string()), None)
+        );
+        assert!(parse("red blue").is_err());
+        assert!(parse("reed").is_err());
+    }
+
+    #[test]
+    fn parse_box_shadow() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ box-shadow: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            BoxShadowProperty::parse(&rule.properties["box-shadow"])
+        };
+
+        let shadow = parse("1px 2px 4px 3px rgba(0, 0, 0, 0.3)")
+            .unwrap()
+            .unwrap();
+        assert_eq!(shadow.offset_x, Val::Px(1.0));
+        assert_eq!(shadow.offset_y, Val::Px(2.0));
+        assert_eq!(shadow.blur, Val::Px(4.0));
+        assert_eq!(shadow.spread, Val::Px(3.0));
+        assert_eq!(shadow.color, Color::rgba(0.0, 0.0, 0.0, 0.3));
+        assert!(!shadow.inset);
+
+        let shadow = parse("inset red 0 -2px").unwrap().unwrap();
+        assert_eq!(
+            shadow,
+            CssBoxShadow {
+                offset_y: Val::Px(-2.0),
+                color: Color::RED,
+                inset: true,
+                ..Default::default()
+            }
+        );
+
+        let shadow = parse("rgb(255 0 0 / 50%) 0 2px").unwrap().unwrap();
+        assert_eq!(shadow.color, Color::rgba(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(shadow.offset_y, Val::Px(2.0));
+
+        assert_eq!(parse("none").unwrap(), None);
+        assert!(parse("2px").is_err(), "Offset requires two lengths");
+        assert!(parse("1px 2px 3px 4px 5px").is_err());
+        assert!(parse("0 2px red, 0 1px blue").is_err());
+        assert!(
+            parse("0 2px, 0 4px").is_err(),
+            "Multiple shadows aren't supported"
+        );
+        assert!(parse("0 2px red blue").is_err());
+    }
+
+    #[test]
+    fn parse_linear_gradient() {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ background: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            BackgroundProperty::parse(&rule.properties["background"])
+                .map(|(_, _, gradient)| gradient)
+        };
+
+        let gradient = parse("linear-gradient(45deg, red, #0000ff 80%)")
+            .unwrap()
+            .unwrap();
+        assert!((gradient.angle - FRAC_PI_4).abs() < f32::EPSILON);
+        assert_eq!(
+            gradient.stops,
+            vec![(Color::RED, None), (Color::BLUE, Some(0.8))]
+        );
+
+        let angle = |value: &str| parse(value).unwrap().unwrap().angle;
+        assert_eq!(
+            angle("linear-gradient(red, blue)"),
+            PI,
+            "Should default to bottom"
+        );
+        assert!((angle("linear-gradient(to right, red, blue)") - FRAC_PI_2).abs() < 1e-6);
+        assert!((angle("linear-gradient(to left top, red, blue)") - 7.0 * FRAC_PI_4).abs() < 1e-6);
+
+        assert!(parse("linear-gradient(45deg, red)").is_err());
+        assert!(parse("linear-gradient(to, red, blue)").is_err());
+        assert!(parse("linear-gradient(45deg, red, blu)").is_err());
+    }
+
+    #[test]
+    fn parse_url_paths() {
+        use crate::StyleSheetAsset;
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .quoted { image-path: url("logo.png"); font: url("fonts/a.ttf"); }
+            .unquoted { image-path: url(logo.png); font: url(fonts/a.ttf); }
+            .bare { image-path: "logo.png"; font: "fonts/a.ttf"; }
+            .invalid { image-path: url(); font: url(); }
+            "#,
+        );
+
+        let rules = sheet.iter().collect::<Vec<_>>();
+        for rule in &rules[..3] {
+            assert_eq!(
+                ImageProperty::parse(&rule.properties["image-path"]).unwrap(),
+                "logo.png"
+            );
+            assert_eq!(
+                FontProperty::parse(&rule.properties["font"])
+                    .unwrap()
+                    .1
+                    .families(),
+                ["fonts/a.ttf"]
+            );
+        }
+
+        assert!(ImageProperty::parse(&rules[3].properties["image-path"]).is_err());
+        assert!(FontProperty::parse(&rules[3].properties["font"]).is_err());
+    }
+
+    #[test]
+    fn apply_registered_font_family() {
+        let inter = Handle::<Font>::weak_from_u128(42);
+        let (app, text) =
+            crate::apply_test_sheet(r#"text { font-family: "Inter"; }"#, |world, root| {
+                world
+                    .resource_mut::<FontRegistry>()
+                    .insert("Inter".to_string(), inter.clone());
+                world
+                    .spawn(TextBundle::from_section("text", TextStyle::default()))
+                    .set_parent(root)
+                    .id()
+            });
+
+        let text = app.world.get::<Text>(text).unwrap();
+        assert_eq!(text.sections[0].style.font, inter);
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("Inter".to_string())]);
+        assert_eq!(
+            FontFamilyProperty::parse(&values).unwrap().families(),
+            ["Inter"]
+        );
+    }
+
+    #[test]
+    fn apply_font_family_fallback() {
+        let arial = Handle::<Font>::weak_from_u128(43);
+        let (app, text) = crate::apply_test_sheet(
+            r#"text { font-family: "Inter", "Arial", sans-serif; }"#,
+            |world, root| {
+                world
+                    .resource_mut::<FontRegistry>()
+                    .insert("Arial".to_string(), arial.clone());
+                world
+                    .spawn(TextBundle::from_section("text", TextStyle::default()))
+                    .set_parent(root)
+                    .id()
+            },
+        );
+
+        let text = app.world.get::<Text>(text).unwrap();
+        assert_eq!(
+            text.sections[0].style.font, arial,
+            "Should fall back to the first registered family"
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::String("Inter".to_string()),
+            PropertyToken::String("Arial".to_string()),
+            PropertyToken::Identifier("sans-serif".to_string()),
+        ]);
+        assert_eq!(
+            FontFamilyProperty::parse(&values).unwrap().families(),
+            ["Inter", "Arial", "sans-serif"]
+        );
+    }
+
+    #[test]
+    fn apply_font_face_family() {
+        use crate::{EcssPlugin, StyleSheet, StyleSheetAsset};
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .init_asset::<Font>();
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse(
+                "",
+                r#"@font-face { font-family: "Inter"; src: url("fonts/inter.ttf"); } text { font-family: "Inter"; }"#,
+            ));
+
+        let sheet_id = sheet.id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .id();
+        let text = app
+            .world
+            .spawn(TextBundle::from_section("text", TextStyle::default()))
+            .set_parent(root)
+            .id();
+
+        app.update();
+        app.update();
+        app.update();
+
+        let inter = app.world.resource::<FontRegistry>()["Inter"].clone();
+        assert_eq!(
+            inter.path().map(ToString::to_string).as_deref(),
+            Some("fonts/inter.ttf")
+        );
+
+        let text = app.world.get::<Text>(text).unwrap();
+        assert_eq!(text.sections[0].style.font, inter);
+
+        // Reloaded sheets only keep the families they still declare
+        app.world.resource_mut::<Assets<StyleSheetAsset>>().insert(
+            sheet_id,
+            StyleSheetAsset::parse(
+                "",
+                r#"@font-face { font-family: "Mono"; src: url("fonts/mono.ttf"); }"#,
+            ),
+        );
+        app.update();
+
+        let registry = app.world.resource::<FontRegistry>();
+        assert!(!registry.contains_key("Inter"));
+        assert!(registry.contains_key("Mono"));
+
+        app.world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .remove(sheet_id);
+        app.update();
+
+        assert!(app.world.resource::<FontRegistry>().is_empty());
+    }
+
+    #[test]
+    fn parse_font_shorthand() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ font: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            FontProperty::parse(&rule.properties["font"])
+                .map(|(size, families)| (size, families.families().join(", ")))
+        };
+
+        assert_eq!(
+            parse(r#"20px "FiraSans""#).unwrap(),
+            (Some(20.0), "FiraSans".to_string())
+        );
+        assert_eq!(
+            parse("16px Fira Sans").unwrap(),
+            (Some(16.0), "Fira Sans".to_string())
+        );
+        assert_eq!(
+            parse(r#"url("fonts/a.ttf")"#).unwrap(),
+            (None, "fonts/a.ttf".to_string())
+        );
+        assert_eq!(
+            parse(r#""fonts/a.ttf""#).unwrap(),
+            (None, "fonts/a.ttf".to_string())
+        );
+        assert!(parse("20px").is_err(), "Family is required");
+        assert!(parse(r#""a" 20px"#).is_err(), "Size must come first");
+    }
+
+    #[test]
+    fn parse_transparent_color() {
+        let sheet = StyleSheetAsset::parse(
+            "",
+            "a { background-color: transparent; color: Transparent; border-color: currentColor; }",
+        );
+        let rule = sheet.iter().next().unwrap();
+
+        assert_eq!(
+            BackgroundColorProperty::parse(&rule.properties["background-color"]).unwrap(),
+            Color::NONE
+        );
+        assert_eq!(
+            FontColorProperty::parse(&rule.properties["color"]).unwrap(),
+            Some(Color::NONE)
+        );
+        assert!(
+            BackgroundColorProperty::parse(&rule.properties["border-color"]).is_err(),
+            "currentColor is only supported by some properties"
+        );
+        assert_eq!(
+            BorderColorProperty::parse(&rule.properties["border-color"]).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn apply_border_current_color() {
+        let (app, (text, node)) =
+            crate::apply_test_sheet("node { border-color: currentColor; }", |world, root| {
+                let text = world
+                    .spawn((
+                        TextBundle::from_section(
+                            "text",
+                            TextStyle {
+                                color: Color::RED,
+                                ..Default::default()
+                            },
+                        ),
+                        BorderColor(Color::BLUE),
+                    ))
+                    .set_parent(root)
+                    .id();
+                let node = world
+                    .spawn(NodeBundle {
+                        border_color: BorderColor(Color::BLUE),
+                        ..Default::default()
+                    })
+                    .set_parent(root)
+                    .id();
+                (text, node)
+            });
+
+        assert_eq!(app.world.get::<BorderColor>(text).unwrap().0, Color::RED);
+        assert_eq!(
+            app.world.get::<BorderColor>(node).unwrap().0,
+            Color::BLUE,
+            "Entities without text should be kept"
+        );
+    }
+
+    #[test]
+    fn warn_border_color_without_width() {
+        let selector = Selector::parse(".panel").unwrap();
+        let bordered = Style {
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        };
+        let borderless = Style::default();
+
+        let warning = border_color_warning(
+            &selector,
+            [Some(&borderless), Some(&bordered), Some(&borderless)],
+        )
+        .unwrap();
+        assert!(
+            warning.contains(".panel") && warning.contains("2 entities"),
+            "Should be a single warning for the whole rule: {warning}"
+        );
+        assert_eq!(border_color_warning(&selector, [Some(&bordered)]), None);
+        assert_eq!(border_color_warning(&selector, [None]), None);
+    }
+
+    #[test]
+    fn parse_white_space() {
+        for (keyword, behavior) in [
+            ("normal", BreakLineOn::WordBoundary),
+            ("nowrap", BreakLineOn::NoWrap),
+            ("NoWrap", BreakLineOn::NoWrap),
+        ] {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            assert_eq!(
+                WhiteSpaceProperty::parse(&values).unwrap(),
+                Some(behavior),
+                "{keyword}"
+            );
+        }
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("pre".to_string())]);
+        assert!(WhiteSpaceProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_text_transform() {
+        for (keyword, transform) in [
+            ("none", TextTransform::None),
+            ("uppercase", TextTransform::Uppercase),
+            ("lowercase", TextTransform::Lowercase),
+            ("Capitalize", TextTransform::Capitalize),
+        ] {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            assert_eq!(
+                TextTransformProperty::parse(&values).unwrap(),
+                transform,
+                "{keyword}"
+            );
+        }
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier(
+            "small-caps".to_string()
+        )]);
+        assert!(TextTransformProperty::parse(&values).is_err());
+
+        assert_eq!(
+            TextTransform::Capitalize.transform("hello  big\nworld"),
+            "Hello  Big\nWorld"
+        );
+    }
+
+    #[test]
+    fn apply_text_transform() {
+        let (app, (upper, title)) = crate::apply_test_sheet(
+            r#".upper { text-transform: uppercase; } .title { text-transform: capitalize; text-content: "hello world"; }"#,
+            |world, root| {
+                let upper = world
+                    .spawn((
+                        TextBundle::from_sections([
+                            TextSection::new("Some ", TextStyle::default()),
+                            TextSection::new("text", TextStyle::default()),
+                        ]),
+                        Class::new("upper"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                let title = world
+                    .spawn((
+                        TextBundle::from_section("", TextStyle::default()),
+                        Class::new("title"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                (upper, title)
+            },
+        );
+
+        let sections = &app.world.get::<Text>(upper).unwrap().sections;
+        assert_eq!(sections[0].value, "SOME ");
+        assert_eq!(sections[1].value, "TEXT");
+
+        let sections = &app.world.get::<Text>(title).unwrap().sections;
+        assert_eq!(
+            sections[0].value, "Hello World",
+            "Should be applied after text-content"
+        );
+    }
+
+    #[test]
+    fn parse_text_overflow() {
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("ellipsis".to_string())]);
+        assert_eq!(
+            TextOverflowProperty::parse(&values).unwrap(),
+            TextOverflow::Ellipsis
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("clip".to_string())]);
+        assert_eq!(
+            TextOverflowProperty::parse(&values).unwrap(),
+            TextOverflow::Clip
+        );
+
+        let values = PropertyValues(smallvec![PropertyToken::String("...".to_string())]);
+        assert!(TextOverflowProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn parse_max_lines() {
+        let values = PropertyValues(smallvec![PropertyToken::Number(3.0)]);
+        assert_eq!(MaxLinesProperty::parse(&values).unwrap(), 3);
+
+        for token in [
+            PropertyToken::Number(0.0),
+            PropertyToken::Number(-1.0),
+            PropertyToken::Number(2.5),
+            PropertyToken::Identifier("none".to_string()),
+        ] {
+            let values = PropertyValues(smallvec![token.clone()]);
+            assert!(MaxLinesProperty::parse(&values).is_err(), "{token:?}");
+        }
+    }
+
+    #[test]
+    fn parse_object_fit() {
+        for (keyword, fit) in [
+            ("fill", ObjectFit::Fill),
+            ("contain", ObjectFit::Contain),
+            ("cover", ObjectFit::Cover),
+            ("Cover", ObjectFit::Cover),
+        ] {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            assert_eq!(ObjectFitProperty::parse(&values).unwrap(), fit, "{keyword}");
+        }
+
+        let values = PropertyValues(smallvec![PropertyToken::Identifier("none".to_string())]);
+        assert!(ObjectFitProperty::parse(&values).is_err());
+    }
+
+    #[test]
+    fn apply_colors_in_place() {
+        // Root receives the `StyleSheetApplied` marker, so only the child archetype is checked
+        let (app, (entity, archetype)) = crate::apply_test_sheet(
+            "node { border: 1px; border-color: red; background-color: blue; }",
+            |world, root| {
+                let entity = world.spawn(NodeBundle::default()).set_parent(root).id();
+                (entity, world.entity(entity).archetype().id())
+            },
+        );
+
+        let entity_ref = app.world.entity(entity);
+        assert_eq!(entity_ref.get::<BorderColor>().unwrap().0, Color::RED);
+        assert_eq!(entity_ref.get::<BackgroundColor>().unwrap().0, Color::BLUE);
+        assert_eq!(
+            entity_ref.archetype().id(),
+            archetype,
+            "Components should be mutated in place"
+        );
+    }
+
+    #[test]
+    fn parse_aspect_ratio() {
+        use crate::StyleSheetAsset;
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .ratio { aspect-ratio: 16 / 9; }
+            .single { aspect-ratio: 1.5; }
+            .none { aspect-ratio: none; }
+            .zero { aspect-ratio: 16 / 0; }
+            "#,
+        );
+
+        let rules = sheet.iter().collect::<Vec<_>>();
+        let ratio = AspectRatioProperty::parse(&rules[0].properties["aspect-ratio"]).unwrap();
+        assert!((ratio.unwrap() - 16.0 / 9.0).abs() < f32::EPSILON);
+        assert_eq!(
+            AspectRatioProperty::parse(&rules[1].properties["aspect-ratio"]).unwrap(),
+            Some(1.5)
+        );
+        assert_eq!(
+            AspectRatioProperty::parse(&rules[2].properties["aspect-ratio"]).unwrap(),
+            None
+        );
+        assert!(AspectRatioProperty::parse(&rules[3].properties["aspect-ratio"]).is_err());
+    }
+
+    #[test]
+    fn parse_case_insensitive_keywords() {
+        use crate::StyleSheetAsset;
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .mixed {
+                JUSTIFY-content: Center;
+                Display: FLEX;
+                align-items: Flex_Start;
+                text-align: CENTER;
+                cursor: Not_Allowed;
+            }
+            "#,
+        );
+
+        let rule = sheet.iter().next().unwrap();
+        assert_eq!(
+            JustifyContentProperty::parse(&rule.properties["justify-content"]).unwrap(),
+            JustifyContent::Center
+        );
+        assert_eq!(
+            DisplayProperty::parse(&rule.properties["display"]).unwrap(),
+            Display::Flex
+        );
+        assert_eq!(
+            AlignItemsProperty::parse(&rule.properties["align-items"]).unwrap(),
+            AlignItems::FlexStart
+        );
+        assert_eq!(
+            TextAlignProperty::parse(&rule.properties["text-align"]).unwrap(),
+            Some(JustifyText::Center)
+        );
+        assert_eq!(
+            CursorProperty::parse(&rule.properties["cursor"]).unwrap(),
+            CursorIcon::NotAllowed
+        );
+    }
+
+    #[test]
+    fn parse_justify_items_and_self() {
+        use crate::StyleSheetAsset;
+
+        let sheet =
+            StyleSheetAsset::parse("", ".grid { justify-items: center; justify-self: auto; }");
+
+        let rule = sheet.iter().next().unwrap();
+        assert_eq!(
+            JustifyItemsProperty::parse(&rule.properties["justify-items"]).unwrap(),
+            JustifyItems::Center
+        );
+        assert_eq!(
+            JustifySelfProperty::parse(&rule.properties["justify-self"]).unwrap(),
+            JustifySelf::Auto
+        );
+    }
+
+    #[test]
+    fn parse_start_and_end_alignments() {
+        use crate::StyleSheetAsset;
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .start { align-items: start; align-self: start; align-content: start; justify-content: start; }
+            .flex-start { align-items: flex-start; align-self: flex-start; align-content: flex-start; justify-content: flex-start; }
+            .end { align-items: end; align-self: end; align-content: end; justify-content: end; }
+            "#,
+        );
+
+        let rules = sheet.iter().collect::<Vec<_>>();
+        let parse = |idx: usize| {
+            let properties = &rules[idx].properties;
+            (
+                AlignItemsProperty::parse(&properties["align-items"]).unwrap(),
+                AlignSelfProperty::parse(&properties["align-self"]).unwrap(),
+                AlignContentProperty::parse(&properties["align-content"]).unwrap(),
+                JustifyContentProperty::parse(&properties["justify-content"]).unwrap(),
+            )
+        };
+
+        assert_eq!(
+            parse(0),
+            (
+                AlignItems::Start,
+                AlignSelf::Start,
+                AlignContent::Start,
+                JustifyContent::Start
+            )
+        );
+        assert_eq!(
+            parse(1),
+            (
+                AlignItems::FlexStart,
+                AlignSelf::FlexStart,
+                AlignContent::FlexStart,
+                JustifyContent::FlexStart
+            )
+        );
+        assert_eq!(
+            parse(2),
+            (
+                AlignItems::End,
+                AlignSelf::End,
+                AlignContent::End,
+                JustifyContent::End
+            )
+        );
+    }
+
+    #[test]
+    fn parse_position_type() {
+        let parse = |keyword: &str| {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            PositionTypeProperty::parse(&values)
+        };
+
+        assert_eq!(parse("absolute").unwrap(), PositionType::Absolute);
+        assert_eq!(parse("relative").unwrap(), PositionType::Relative);
+        assert_eq!(
+            parse("fixed").unwrap(),
+            PositionType::Absolute,
+            "Fixed should be approximated as absolute"
+        );
+        assert!(position_type_warning("fixed").is_some_and(|warning| warning.contains("absolute")));
+        assert_eq!(position_type_warning("absolute"), None);
+        assert!(matches!(
+            parse("sticky"),
+            Err(EcssError::UnsupportedPropertyValue { property, value })
+                if property == "position-type" && value == "sticky"
+        ));
+        assert!(matches!(
+            parse("static"),
+            Err(EcssError::InvalidPropertyValue(property)) if property == "position-type"
+        ));
+    }
+
+    #[test]
+    fn parse_rect_shorthand_with_auto() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ margin: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            MarginProperty::parse(&rule.properties["margin"]).unwrap()
+        };
+
+        assert_eq!(
+            parse("0 auto"),
+            UiRect::new(Val::Auto, Val::Auto, Val::Px(0.0), Val::Px(0.0))
+        );
+        assert_eq!(
+            parse("auto 10px 5%"),
+            UiRect::new(Val::Px(10.0), Val::Px(10.0), Val::Auto, Val::Percent(5.0))
+        );
+        assert_eq!(
+            parse("1px 2vw auto AUTO"),
+            UiRect::new(Val::Auto, Val::Vw(2.0), Val::Px(1.0), Val::Auto)
+        );
+        assert_eq!(parse("auto"), UiRect::all(Val::Auto));
+    }
+
+    #[test]
+    fn apply_calc_size() {
+        use crate::CssCalc;
+
+        let (mut app, (a, b)) = crate::apply_test_sheet(
+            ".a { width: calc(100% - 20px); height: calc(10px + 5px); } .b { width: calc(10px + 5vw); height: calc(100% - 20px); }",
+            |world, root| {
+                let a = world
+                    .spawn((NodeBundle::default(), Class::new("a")))
+                    .set_parent(root)
+                    .id();
+                let b = world
+                    .spawn((NodeBundle::default(), Class::new("b")))
+                    .set_parent(root)
+                    .id();
+                (a, b)
+            },
+        );
+        app.update();
+
+        let calc = CalcVal {
+            percent: 100.0,
+            px: -20.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            app.world.get::<CssCalc>(a),
+            Some(&CssCalc {
+                width: Some(calc),
+                height: None,
+            })
+        );
+        assert_eq!(
+            app.world.get::<CssCalc>(b).unwrap().height,
+            Some(calc),
+            "Both sizes should be kept on the same component"
+        );
+
+        // Without a window and layout, relative terms are resolved to zero and negative sizes are clamped
+        let style = app.world.get::<Style>(a).unwrap();
+        assert_eq!(style.width, Val::Px(0.0));
+        assert_eq!(style.height, Val::Px(15.0));
+        let style = app.world.get::<Style>(b).unwrap();
+        assert_eq!(style.width, Val::Px(10.0));
+        assert_eq!(style.height, Val::Px(0.0));
+    }
+
+    #[test]
+    fn apply_color_section() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ color-section: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            FontColorSectionProperty::parse(&rule.properties["color-section"])
+        };
+
+        assert_eq!(parse("1 red").unwrap(), (1, Color::RED));
+        assert_eq!(parse("0 #00ff00").unwrap(), (0, Color::GREEN));
+        assert!(parse("red").is_err());
+        assert!(parse("-1 red").is_err());
+        assert!(parse("1.5 red").is_err());
+        assert!(parse("1 red blue").is_err());
+
+        let sections = || {
+            [
+                TextSection::new(
+                    "first",
+                    TextStyle {
+                        color: Color::BLUE,
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    "second",
+                    TextStyle {
+                        color: Color::GREEN,
+                        ..default()
+                    },
+                ),
+            ]
+        };
+        let (app, (rich, short)) = crate::apply_test_sheet(
+            ".rich { color-section: 1 red; } .short { color-section: 5 red; }",
+            |world, root| {
+                let rich = world
+                    .spawn((TextBundle::from_sections(sections()), Class::new("rich")))
+                    .set_parent(root)
+                    .id();
+                let short = world
+                    .spawn((TextBundle::from_sections(sections()), Class::new("short")))
+                    .set_parent(root)
+                    .id();
+                (rich, short)
+            },
+        );
+
+        let colors = |entity| {
+            app.world
+                .get::<Text>(entity)
+                .unwrap()
+                .sections
+                .iter()
+                .map(|section| section.style.color)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(colors(rich), [Color::BLUE, Color::RED]);
+        assert_eq!(
+            colors(short),
+            [Color::BLUE, Color::GREEN],
+            "Out of bounds sections should be ignored"
+        );
+    }
+
+    #[test]
+    fn apply_inherit_text_color() {
+        let (app, (parent, child, orphan)) = crate::apply_test_sheet(
+            ".parent { color: red; } .child { color: inherit; } .orphan { color: inherit; }",
+            |world, root| {
+                let parent = world
+                    .spawn((
+                        TextBundle::from_section("parent", default()),
+                        Class::new("parent"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                let child = world
+                    .spawn((
+                        TextBundle::from_section("child", default()),
+                        Class::new("child"),
+                    ))
+                    .set_parent(parent)
+                    .id();
+                let orphan = world
+                    .spawn((
+                        TextBundle::from_section(
+                            "orphan",
+                            TextStyle {
+                                color: Color::BLUE,
+                                ..default()
+                            },
+                        ),
+                        Class::new("orphan"),
+                    ))
+                    .set_parent(root)
+                    .id();
+                (parent, child, orphan)
+            },
+        );
+
+        let color = |entity| {
+            app.world.get::<Text>(entity).unwrap().sections[0]
+                .style
+                .color
+        };
+        assert_eq!(color(parent), Color::RED);
+        assert_eq!(color(child), Color::RED, "Should inherit parent text color");
+        assert_eq!(
+            color(orphan),
+            Color::BLUE,
+            "Parent without text shouldn't change color"
+        );
+
+        assert_eq!(
+            FontColorProperty::parse(&PropertyValues(smallvec![PropertyToken::Identifier(
+                "Inherit".to_string()
+            )]))
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_negative_values() {
+        let sheet = StyleSheetAsset::parse(
+            "",
+            r#"
+            .a {
+                margin: -10px;
+                padding: 0 -5% -2vw;
+                left: -5px;
+                top: -12.5%;
+                bottom: -1vh;
+            }
+            "#,
+        );
+        let rule = sheet.iter().next().unwrap();
+
+        assert_eq!(
+            MarginProperty::parse(&rule.properties["margin"]).unwrap(),
+            UiRect::all(Val::Px(-10.0))
+        );
+        assert_eq!(
+            PaddingProperty::parse(&rule.properties["padding"]).unwrap(),
+            UiRect::new(
+                Val::Percent(-5.0),
+                Val::Percent(-5.0),
+                Val::Px(0.0),
+                Val::Vw(-2.0)
+            )
+        );
+        assert_eq!(
+            LeftProperty::parse(&rule.properties["left"]).unwrap(),
+            Val::Px(-5.0)
+        );
+        assert_eq!(
+            TopProperty::parse(&rule.properties["top"]).unwrap(),
+            Val::Percent(-12.5)
+        );
+        assert_eq!(
+            BottomProperty::parse(&rule.properties["bottom"]).unwrap(),
+            Val::Vh(-1.0)
+        );
+    }
+
+    #[test]
+    fn parse_inset_shorthand() {
+        let parse = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ inset: {value}; }}"));
+            let rule = sheet.iter().next().unwrap();
+            InsetProperty::parse(&rule.properties["inset"])
+        };
+
+        assert_eq!(parse("10px").unwrap(), UiRect::all(Val::Px(10.0)));
+        assert_eq!(
+            parse("1px 2% -3px auto").unwrap(),
+            UiRect::new(Val::Auto, Val::Percent(2.0), Val::Px(1.0), Val::Px(-3.0))
+        );
+        assert_eq!(parse("unset").unwrap(), UiRect::all(Val::Auto));
+        assert!(parse("red").is_err());
+    }
+
+    #[test]
+    fn parse_display() {
+        let parse = |keyword: &str| {
+            let values = PropertyValues(smallvec![PropertyToken::Identifier(keyword.to_string())]);
+            DisplayProperty::parse(&values)
+        };
+
+        assert_eq!(parse("flex").unwrap(), Display::Flex);
+        assert_eq!(parse("grid").unwrap(), Display::Grid);
+        assert_eq!(parse("none").unwrap(), Display::None);
+        // Bevy has no block layout yet
+        assert!(parse("block").is_err());
+    }
+
+    #[test]
+    fn parse_unset() {
+        let sheet = StyleSheetAsset::parse(
+            "",
+            ".a { width: unset; margin: UNSET; flex-grow: unset; height: unset 10px; }",
+        );
+        let rule = sheet.iter().next().unwrap();
+
+        assert_eq!(
+            WidthProperty::parse(&rule.properties["width"]).unwrap(),
+            (Val::Auto, None)
+        );
+        assert_eq!(
+            MarginProperty::parse(&rule.properties["margin"]).unwrap(),
+            UiRect::DEFAULT
+        );
+        assert_eq!(
+            FlexGrowProperty::parse(&rule.properties["flex-grow"]).unwrap(),
+            0.0
+        );
+        assert_eq!(
+            HeightProperty::parse(&rule.properties["height"]).unwrap(),
+            (Val::Px(10.0), None),
+            "Unset should only be used alone"
+        );
     }
 }
@@ -1,14 +1,19 @@
-use std::any::Any;
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use bevy::{
     ecs::query::{QueryData, QueryFilter, QueryItem},
-    log::{error, trace},
+    log::{error, trace, warn},
     prelude::{
-        AssetId, AssetServer, Assets, Color, Commands, Deref, DerefMut, Entity, Local, Query, Res,
-        Resource,
+        AssetEvent, AssetId, AssetServer, Assets, Color, Commands, Deref, DerefMut, Entity,
+        EventReader, Font, Handle, Local, Query, Reflect, Res, Resource, Vec2, Vec4, World,
     },
     ui::{UiRect, Val},
-    utils::HashMap,
+    utils::{AHasher, HashMap, HashSet, Uuid},
 };
 
 use cssparser::Token;
@@ -18,9 +23,13 @@ use crate::{selector::Selector, EcssError, SelectorElement, StyleSheetAsset};
 
 mod colors;
 pub mod impls;
+pub(crate) mod reflect;
+pub(crate) mod single_pass;
+pub mod transition;
 
 /// A property value token which was parsed from a CSS rule.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PropertyToken {
     /// A value which was parsed percent value, like `100%` or `73.23%`.
     Percentage(f32),
@@ -37,6 +46,10 @@ pub enum PropertyToken {
     Vh(f32),
     /// A viewport width value like `10vw`
     Vw(f32),
+    /// A time value like `0.2s` or `150ms`. The value is always stored in seconds.
+    Time(f32),
+    /// An angle value like `45deg`, `0.5turn` or `1rad`. The value is always stored in radians.
+    Angle(f32),
     /// A numeric float value, like `31.1` or `43`.
     Number(f32),
     /// A plain identifier, like `none` or `center`.
@@ -45,10 +58,21 @@ pub enum PropertyToken {
     Hash(String),
     /// A quoted string, like `"some value"`.
     String(String),
+    /// A slash delimiter, like in `16 / 9`.
+    Slash,
+    /// A comma separating many values, like in `"Inter", sans-serif`. Commas inside functions aren't kept.
+    Comma,
+    /// A plus sign, like in `calc(50% + 10px)`.
+    Plus,
+    /// A minus sign, like in `calc(100% - 20px)`.
+    Minus,
+    /// An asterisk, like in `calc(25% * 2)`.
+    Asterisk,
 }
 
 /// A list of [`PropertyToken`] which was parsed from a single property.
-#[derive(Debug, Default, Clone, Deref)]
+#[derive(Debug, Default, Clone, PartialEq, Deref)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PropertyValues(pub(crate) SmallVec<[PropertyToken; 8]>);
 
 impl PropertyValues {
@@ -66,53 +90,141 @@ impl PropertyValues {
         })
     }
 
+    /// Tries to parses the current values as a single path, either as an `url("path")`, an unquoted
+    /// `url(path)` or a bare `"path"` string.
+    pub fn url(&self) -> Option<String> {
+        match self.0.as_slice() {
+            [PropertyToken::Identifier(function), PropertyToken::String(path)]
+                if function == "url" && !path.is_empty() =>
+            {
+                Some(path.clone())
+            }
+            [PropertyToken::String(path)] if !path.is_empty() => Some(path.clone()),
+            _ => None,
+        }
+    }
+
     /// Tries to parses the current values as a single [`Color`].
     ///
-    /// Currently only [named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color)
-    /// and [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color) are supported.
+    /// [Named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color), `transparent`,
+    /// [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color) and the `rgb(...)` and `rgba(...)`
+    /// functions are supported, like `rgb(255 0 0 / 50%)` or `rgba(255, 0, 0, 0.5)`.
+    ///
+    /// `currentColor` depends on the entity, so it isn't returned here. Check [`is_current_color`](Self::is_current_color).
     pub fn color(&self) -> Option<Color> {
-        if self.0.len() == 1 {
-            match &self.0[0] {
-                PropertyToken::Identifier(name) => colors::parse_named_color(name.as_str()),
-                PropertyToken::Hash(hash) => colors::parse_hex_color(hash.as_str()),
-                _ => None,
-            }
-        } else {
-            // TODO: Implement color function like rgba(255, 255, 255, 255)
-            // https://developer.mozilla.org/en-US/docs/Web/CSS/color_value
-            None
-        }
+        let mut tokens = self.0.iter().peekable();
+        let color = colors::parse_color(tokens.next()?, &mut tokens)?;
+
+        // The whole value must be a single color
+        tokens.next().is_none().then_some(color)
     }
 
     /// Tries to parses the current values as a single identifier.
     pub fn identifier(&self) -> Option<&str> {
-        self.0.iter().find_map(|token| match token {
-            PropertyToken::Identifier(id) => {
-                if id.is_empty() {
-                    None
-                } else {
-                    Some(id.as_str())
-                }
-            }
+        self.identifiers().next()
+    }
+
+    /// Returns all identifiers of the current values, in order, like `h`, `i` and `j` on `g: h i j;`.
+    pub fn identifiers(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|token| match token {
+            PropertyToken::Identifier(id) if !id.is_empty() => Some(id.as_str()),
             _ => None,
         })
     }
 
+    /// Tries to parses the current values as a single keyword, like `flex-start` or `center`.
+    ///
+    /// The identifier is trimmed, lowercased and has `_` replaced by `-`, so `Flex_Start` is the same as `flex-start`.
+    pub fn keyword(&self) -> Option<String> {
+        self.identifier()
+            .map(|id| id.trim().to_ascii_lowercase().replace('_', "-"))
+    }
+
+    /// Checks if the current values is a single `currentColor` keyword, which refers to the entity text color.
+    pub fn is_current_color(&self) -> bool {
+        self.0.len() == 1
+            && self
+                .keyword()
+                .is_some_and(|keyword| keyword == "currentcolor")
+    }
+
+    /// Checks if the current values is a single `unset` keyword, which resets the property to its default value.
+    pub fn is_unset(&self) -> bool {
+        self.0.len() == 1 && self.keyword().is_some_and(|keyword| keyword == "unset")
+    }
+
     /// Tries to parses the current values as a single [`Val`].
     ///
     /// Only [`Percentage`](PropertyToken::Percentage) and [`Dimension`](PropertyToken::Dimension`) are considered valid values,
     /// where former is converted to [`Val::Percent`] and latter is converted to [`Val::Px`].
+    /// A unitless `0` is also accepted, like in CSS, and is converted to [`Val::Px`], while `auto` is converted to [`Val::Auto`].
+    ///
+    /// The `min(...)` and `max(...)` functions are also accepted, but since [`Val`] can't represent them,
+    /// the value is approximated: if all arguments have the same unit, the min or max value is computed,
+    /// otherwise the first valid argument is used.
+    ///
+    /// A `calc(...)` expression is converted to [`Val`] when all its terms have the same unit, otherwise
+    /// it's approximated by its relative term, like `100%` on `calc(100% - 20px)`. Check [`calc`](Self::calc) for more.
     pub fn val(&self) -> Option<Val> {
-        self.0.iter().find_map(|token| match token {
+        match self.0.first() {
+            Some(PropertyToken::Identifier(function)) if function == "calc" => {
+                let calc = self.calc()?;
+                calc.to_val().or_else(|| {
+                    let val = calc.approximate();
+                    warn!(
+                        "calc({}) can't be represented by Val, using {:?}",
+                        calc, val
+                    );
+                    Some(val)
+                })
+            }
+            Some(PropertyToken::Identifier(function)) if function == "min" => {
+                Self::approximate_min_max(&self.0[1..], f32::min)
+            }
+            Some(PropertyToken::Identifier(function)) if function == "max" => {
+                Self::approximate_min_max(&self.0[1..], f32::max)
+            }
+            _ => self.vals().next(),
+        }
+    }
+
+    /// Returns all [`Val`] of the current values, in order, using the same rules of [`val`](Self::val).
+    ///
+    /// The `min(...)` and `max(...)` functions aren't approximated, so their arguments are returned as is.
+    pub fn vals(&self) -> impl Iterator<Item = Val> + '_ {
+        self.0.iter().filter_map(Self::token_val)
+    }
+
+    pub(crate) fn token_val(token: &PropertyToken) -> Option<Val> {
+        match token {
             PropertyToken::Percentage(val) => Some(Val::Percent(*val)),
             PropertyToken::Dimension(val) => Some(Val::Px(*val)),
+            PropertyToken::Number(val) if *val == 0.0 => Some(Val::Px(0.0)),
             PropertyToken::VMin(val) => Some(Val::VMin(*val)),
             PropertyToken::VMax(val) => Some(Val::VMax(*val)),
             PropertyToken::Vh(val) => Some(Val::Vh(*val)),
             PropertyToken::Vw(val) => Some(Val::Vw(*val)),
-            PropertyToken::Identifier(val) if val == "auto" => Some(Val::Auto),
+            PropertyToken::Identifier(val) if val.eq_ignore_ascii_case("auto") => Some(Val::Auto),
+            _ => None,
+        }
+    }
+
+    /// Folds the arguments of `min(...)` or `max(...)` using the given function, when all
+    /// arguments have the same unit, or returns the first valid argument otherwise.
+    fn approximate_min_max(args: &[PropertyToken], fold: fn(f32, f32) -> f32) -> Option<Val> {
+        let mut vals = args.iter().filter_map(Self::token_val);
+        let first = vals.next()?;
+
+        vals.try_fold(first, |acc, val| match (acc, val) {
+            (Val::Px(a), Val::Px(b)) => Some(Val::Px(fold(a, b))),
+            (Val::Percent(a), Val::Percent(b)) => Some(Val::Percent(fold(a, b))),
+            (Val::VMin(a), Val::VMin(b)) => Some(Val::VMin(fold(a, b))),
+            (Val::VMax(a), Val::VMax(b)) => Some(Val::VMax(fold(a, b))),
+            (Val::Vh(a), Val::Vh(b)) => Some(Val::Vh(fold(a, b))),
+            (Val::Vw(a), Val::Vw(b)) => Some(Val::Vw(fold(a, b))),
             _ => None,
         })
+        .or(Some(first))
     }
 
     /// Tries to parses the current values as a single [`f32`].
@@ -120,7 +232,12 @@ impl PropertyValues {
     /// Only [`Percentage`](PropertyToken::Percentage), [`Dimension`](PropertyToken::Dimension`) and [`Number`](PropertyToken::Number`)
     /// are considered valid values.
     pub fn f32(&self) -> Option<f32> {
-        self.0.iter().find_map(|token| match token {
+        self.f32s().next()
+    }
+
+    /// Returns all [`f32`] of the current values, in order, using the same rules of [`f32`](Self::f32).
+    pub fn f32s(&self) -> impl Iterator<Item = f32> + '_ {
+        self.0.iter().filter_map(|token| match token {
             PropertyToken::Percentage(val)
             | PropertyToken::Dimension(val)
             | PropertyToken::Number(val) => Some(*val),
@@ -128,6 +245,32 @@ impl PropertyValues {
         })
     }
 
+    /// Tries to parses the current values as a [`Vec2`], like `10 20` or `10px 20px`.
+    ///
+    /// Exactly two values are expected, using the same rules of [`f32`](Self::f32).
+    pub fn vec2(&self) -> Option<Vec2> {
+        match self.exact_f32s()?.as_slice() {
+            &[x, y] => Some(Vec2::new(x, y)),
+            _ => None,
+        }
+    }
+
+    /// Tries to parses the current values as a [`Vec4`], like `1 2 3 4`.
+    ///
+    /// Exactly four values are expected, using the same rules of [`f32`](Self::f32).
+    pub fn vec4(&self) -> Option<Vec4> {
+        match self.exact_f32s()?.as_slice() {
+            &[x, y, z, w] => Some(Vec4::new(x, y, z, w)),
+            _ => None,
+        }
+    }
+
+    /// Returns all [`f32`] of the current values, only if every value is a valid one.
+    fn exact_f32s(&self) -> Option<SmallVec<[f32; 4]>> {
+        let values = self.f32s().collect::<SmallVec<[f32; 4]>>();
+        (values.len() == self.0.len()).then_some(values)
+    }
+
     /// Tries to parses the current values as a single [`Option<f32>`].
     ///
     /// This function is useful for properties where either a numeric value or a `none` value is expected.
@@ -150,41 +293,127 @@ impl PropertyValues {
         })
     }
 
+    /// Tries to parses the current values as a ratio, like `16 / 9` or `1.5`.
+    ///
+    /// If there are two numbers separated by a [`Slash`](PropertyToken::Slash), a [`Option::Some`] with the
+    /// division result is returned, as long as the second number isn't zero.
+    /// Otherwise this behaves like [`option_f32`](Self::option_f32).
+    pub fn ratio(&self) -> Option<Option<f32>> {
+        match self.0.as_slice() {
+            [PropertyToken::Number(width), PropertyToken::Slash, PropertyToken::Number(height)] => {
+                (*height != 0.0).then_some(Some(width / height))
+            }
+            _ if self.0.contains(&PropertyToken::Slash) => None,
+            _ => self.option_f32(),
+        }
+    }
+
+    /// Tries to parses the current values as a `calc(...)` expression.
+    ///
+    /// Terms can be added or subtracted, like `calc(100% - 20px)`, and multiplied or divided by a number,
+    /// like `calc(100% / 3 - 2 * 5px)`. Each term accepts the same units of [`val`](Self::val), except `auto`.
+    /// A single value, without the `calc` function, is also accepted.
+    pub fn calc(&self) -> Option<CalcVal> {
+        let tokens = match self.0.as_slice() {
+            [PropertyToken::Identifier(function), args @ ..] if function == "calc" => args,
+            tokens => tokens,
+        };
+
+        let mut tokens = tokens.iter().peekable();
+        let mut result = Self::calc_term(&mut tokens)?;
+
+        while let Some(operator) = tokens.next() {
+            result = match operator {
+                PropertyToken::Plus => result + Self::calc_term(&mut tokens)?,
+                PropertyToken::Minus => result - Self::calc_term(&mut tokens)?,
+                _ => return None,
+            };
+        }
+
+        Some(result)
+    }
+
+    /// Parses a single `calc(...)` term, which is a value optionally multiplied or divided by numbers.
+    fn calc_term<'a>(
+        tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a PropertyToken>>,
+    ) -> Option<CalcVal> {
+        let mut term = match tokens.next()? {
+            PropertyToken::Number(factor) if *factor != 0.0 => {
+                if tokens.next()? != &PropertyToken::Asterisk {
+                    return None;
+                }
+                CalcVal::from_val(Self::token_val(tokens.next()?)?)? * *factor
+            }
+            token => CalcVal::from_val(Self::token_val(token)?)?,
+        };
+
+        while let Some(operator @ (PropertyToken::Asterisk | PropertyToken::Slash)) = tokens.peek()
+        {
+            let operator = *operator;
+            tokens.next();
+
+            let Some(PropertyToken::Number(factor)) = tokens.next() else {
+                return None;
+            };
+
+            term = match operator {
+                PropertyToken::Asterisk => term * *factor,
+                _ if *factor != 0.0 => term * factor.recip(),
+                _ => return None,
+            };
+        }
+
+        Some(term)
+    }
+
     /// Tries to parses the current values as a single [`Option<UiRect<Val>>`].
     ///
-    /// Optional values are handled by this function, so if only one value is present it is used as `top`, `right`, `bottom` and `left`,
-    /// otherwise values are applied in the following order: `top`, `right`, `bottom` and `left`.
+    /// Values follows the CSS shorthand expansion:
+    /// - One value is used as `top`, `right`, `bottom` and `left`;
+    /// - Two values are used as `top` and `bottom`, then `right` and `left`;
+    /// - Three values are used as `top`, then `right` and `left`, then `bottom`;
+    /// - Four values are used as `top`, `right`, `bottom` and `left`.
     ///
-    /// Note that it is not possible to create a [`UiRect`] with only `top` value, since it'll be understood to replicated it on all fields.
+    /// Each value accepts the same units of [`val`](Self::val), including `auto`, so `margin: 0 auto;`
+    /// sets `top` and `bottom` to zero and `right` and `left` to [`Val::Auto`].
+    ///
+    /// A single `calc(...)`, `min(...)` or `max(...)` function is used on all sides, but since function arguments
+    /// are flattened, functions can't be mixed with other values. If there are more than four values or any of them
+    /// isn't a valid one, [`None`] is returned.
     pub fn rect(&self) -> Option<UiRect> {
-        if self.0.len() == 1 {
-            self.val().map(UiRect::all)
-        } else {
-            self.0
-                .iter()
-                .fold((None, 0), |(rect, idx), token| {
-                    let val = match token {
-                        PropertyToken::Percentage(val) => Val::Percent(*val),
-                        PropertyToken::Dimension(val) => Val::Px(*val),
-                        PropertyToken::VMin(val) => Val::VMin(*val),
-                        PropertyToken::VMax(val) => Val::VMax(*val),
-                        PropertyToken::Vh(val) => Val::Vh(*val),
-                        PropertyToken::Vw(val) => Val::Vw(*val),
-                        PropertyToken::Identifier(val) if val == "auto" => Val::Auto,
-                        _ => return (rect, idx),
-                    };
-                    let mut rect: UiRect = rect.unwrap_or_default();
-
-                    match idx {
-                        0 => rect.top = val,
-                        1 => rect.right = val,
-                        2 => rect.bottom = val,
-                        3 => rect.left = val,
-                        _ => (),
-                    }
-                    (Some(rect), idx + 1)
-                })
-                .0
+        let is_function = matches!(
+            self.0.first(),
+            Some(PropertyToken::Identifier(function)) if ["calc", "min", "max"].contains(&function.as_str())
+        );
+        if self.0.len() == 1 || is_function {
+            return self.val().map(UiRect::all);
+        }
+
+        let vals = self
+            .0
+            .iter()
+            .map(Self::token_val)
+            .collect::<Option<SmallVec<[Val; 4]>>>()?;
+
+        match vals.as_slice() {
+            [vertical, horizontal] => Some(UiRect::axes(*horizontal, *vertical)),
+            [top, horizontal, bottom] => Some(UiRect::new(*horizontal, *horizontal, *top, *bottom)),
+            [top, right, bottom, left] => Some(UiRect::new(*left, *right, *top, *bottom)),
+            _ => None,
+        }
+    }
+
+    /// Tries to parses the current values as a pair of values, like `10px 20px`, converting each token with the given function.
+    ///
+    /// A single value is used on both, so `10px` is the same as `10px 10px`. This is useful for shorthand properties
+    /// which sets two axis at once, like `gap` or `overflow`.
+    ///
+    /// If there are more than two values or any of them can't be converted, [`None`] is returned.
+    pub fn pair<T: Clone>(&self, convert: impl Fn(&PropertyToken) -> Option<T>) -> Option<(T, T)> {
+        match self.0.as_slice() {
+            [both] => convert(both).map(|both| (both.clone(), both)),
+            [first, second] => Some((convert(first)?, convert(second)?)),
+            _ => None,
         }
     }
 }
@@ -198,6 +427,11 @@ impl<'i> TryFrom<Token<'i>> for PropertyToken {
             Token::Hash(val) => Ok(Self::Hash(val.to_string())),
             Token::IDHash(val) => Ok(Self::Hash(val.to_string())),
             Token::QuotedString(val) => Ok(Self::String(val.to_string())),
+            Token::Delim('/') => Ok(Self::Slash),
+            Token::Comma => Ok(Self::Comma),
+            Token::Delim('+') => Ok(Self::Plus),
+            Token::Delim('-') => Ok(Self::Minus),
+            Token::Delim('*') => Ok(Self::Asterisk),
             Token::Number { value, .. } => Ok(Self::Number(value)),
             Token::Percentage { unit_value, .. } => Ok(Self::Percentage(unit_value * 100.0)),
             Token::Dimension { value, unit, .. } => match unit.as_bytes() {
@@ -205,6 +439,12 @@ impl<'i> TryFrom<Token<'i>> for PropertyToken {
                 b"vmax" => Ok(Self::VMax(value)),
                 b"vh" => Ok(Self::Vh(value)),
                 b"vw" => Ok(Self::Vw(value)),
+                b"s" => Ok(Self::Time(value)),
+                b"ms" => Ok(Self::Time(value / 1000.0)),
+                b"deg" => Ok(Self::Angle(value.to_radians())),
+                b"grad" => Ok(Self::Angle(value * std::f32::consts::PI / 200.0)),
+                b"rad" => Ok(Self::Angle(value)),
+                b"turn" => Ok(Self::Angle(value * std::f32::consts::TAU)),
                 _ => Ok(Self::Dimension(value)),
             },
             _ => Err(()),
@@ -212,6 +452,160 @@ impl<'i> TryFrom<Token<'i>> for PropertyToken {
     }
 }
 
+/// A `calc(...)` expression parsed by [`PropertyValues::calc`].
+///
+/// Since terms can only be added, subtracted, multiplied or divided by numbers, any expression is reduced
+/// to the sum of a value per unit, like `calc(100% - 20px)` being `100%` plus `-20px`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+pub struct CalcVal {
+    /// Sum of all pixels terms.
+    pub px: f32,
+    /// Sum of all percentage terms.
+    pub percent: f32,
+    /// Sum of all viewport width terms.
+    pub vw: f32,
+    /// Sum of all viewport height terms.
+    pub vh: f32,
+    /// Sum of all viewport minimum axis terms.
+    pub vmin: f32,
+    /// Sum of all viewport maximum axis terms.
+    pub vmax: f32,
+}
+
+impl CalcVal {
+    /// Creates a new expression with a single term of the given [`Val`], if it isn't [`Val::Auto`].
+    pub fn from_val(val: Val) -> Option<Self> {
+        let mut calc = Self::default();
+        match val {
+            Val::Auto => return None,
+            Val::Px(px) => calc.px = px,
+            Val::Percent(percent) => calc.percent = percent,
+            Val::Vw(vw) => calc.vw = vw,
+            Val::Vh(vh) => calc.vh = vh,
+            Val::VMin(vmin) => calc.vmin = vmin,
+            Val::VMax(vmax) => calc.vmax = vmax,
+        }
+        Some(calc)
+    }
+
+    /// Non-zero terms of this expression, with relative units first.
+    fn terms(&self) -> impl Iterator<Item = Val> {
+        [
+            Val::Percent(self.percent),
+            Val::Vw(self.vw),
+            Val::Vh(self.vh),
+            Val::VMin(self.vmin),
+            Val::VMax(self.vmax),
+            Val::Px(self.px),
+        ]
+        .into_iter()
+        .filter(|val| {
+            !matches!(
+                val,
+                Val::Px(v) | Val::Percent(v) | Val::Vw(v) | Val::Vh(v) | Val::VMin(v) | Val::VMax(v)
+                    if *v == 0.0
+            )
+        })
+    }
+
+    /// Converts this expression to a [`Val`], if it has at most a single unit.
+    pub fn to_val(&self) -> Option<Val> {
+        let mut terms = self.terms();
+        match (terms.next(), terms.next()) {
+            (None, _) => Some(Val::Px(0.0)),
+            (Some(val), None) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Converts this expression to a [`Val`], keeping only its first relative term when it has more than
+    /// a single unit, since those usually dominates the final size, like `100%` on `calc(100% - 20px)`.
+    pub fn approximate(&self) -> Val {
+        self.terms().next().unwrap_or(Val::Px(0.0))
+    }
+
+    /// Resolves this expression to pixels, where percentages are relative to the given `basis` and viewport
+    /// units are relative to the given `viewport` size, both in logical pixels.
+    ///
+    /// Since [`Val::Px`] is scaled by [`UiScale`](bevy::ui::UiScale), relative terms are divided by the
+    /// given `ui_scale`, so they end up at the expected size.
+    pub fn resolve(&self, basis: f32, viewport: Vec2, ui_scale: f32) -> f32 {
+        let relative = self.percent * basis
+            + self.vw * viewport.x
+            + self.vh * viewport.y
+            + self.vmin * viewport.min_element()
+            + self.vmax * viewport.max_element();
+
+        self.px + relative / 100.0 / ui_scale
+    }
+}
+
+impl std::ops::Add for CalcVal {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            px: self.px + rhs.px,
+            percent: self.percent + rhs.percent,
+            vw: self.vw + rhs.vw,
+            vh: self.vh + rhs.vh,
+            vmin: self.vmin + rhs.vmin,
+            vmax: self.vmax + rhs.vmax,
+        }
+    }
+}
+
+impl std::ops::Sub for CalcVal {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + rhs * -1.0
+    }
+}
+
+impl std::ops::Mul<f32> for CalcVal {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            px: self.px * rhs,
+            percent: self.percent * rhs,
+            vw: self.vw * rhs,
+            vh: self.vh * rhs,
+            vmin: self.vmin * rhs,
+            vmax: self.vmax * rhs,
+        }
+    }
+}
+
+impl std::fmt::Display for CalcVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut terms = self.terms().peekable();
+        if terms.peek().is_none() {
+            return write!(f, "0px");
+        }
+
+        for (index, val) in terms.enumerate() {
+            let (value, unit) = match val {
+                Val::Percent(v) => (v, "%"),
+                Val::Vw(v) => (v, "vw"),
+                Val::Vh(v) => (v, "vh"),
+                Val::VMin(v) => (v, "vmin"),
+                Val::VMax(v) => (v, "vmax"),
+                Val::Px(v) => (v, "px"),
+                Val::Auto => unreachable!(),
+            };
+
+            match (index, value < 0.0) {
+                (0, _) => write!(f, "{}{}", value, unit)?,
+                (_, true) => write!(f, " - {}{}", -value, unit)?,
+                (_, false) => write!(f, " + {}{}", value, unit)?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Internal cache state. Used by [`CachedProperties`] to avoid parsing properties of the same rule on same sheet.
 #[derive(Default, Debug, Clone)]
 pub enum CacheState<T> {
@@ -229,8 +623,14 @@ pub enum CacheState<T> {
 pub struct CachedProperties<T>(HashMap<Selector, CacheState<T>>);
 
 /// Internal property cache map. Used by [`Property::apply_system`] to keep track of which properties was already parsed.
-#[derive(Debug, Default, Deref, DerefMut)]
-pub struct PropertyMeta<T: Property>(HashMap<u64, CachedProperties<T::Cache>>);
+///
+/// Caches are keyed by [`StyleSheetAsset::hash`], so sheets with the same content shares the same cache.
+/// The hash used by each asset is also tracked, so caches can be evicted when the asset is modified or removed.
+#[derive(Debug, Default)]
+pub struct PropertyMeta<T: Property> {
+    caches: HashMap<u64, CachedProperties<T::Cache>>,
+    hashes: HashMap<AssetId<StyleSheetAsset>, u64>,
+}
 
 impl<T: Property> PropertyMeta<T> {
     /// Gets a cached property value or try to parse.
@@ -238,10 +638,16 @@ impl<T: Property> PropertyMeta<T> {
     /// If there are some error while parsing, a [`CacheState::Error`] is stored to avoid trying to parse again on next try.
     fn get_or_parse(
         &mut self,
+        asset_id: AssetId<StyleSheetAsset>,
         rules: &StyleSheetAsset,
         selector: &Selector,
     ) -> &CacheState<T::Cache> {
-        let cached_properties = self.entry(rules.hash()).or_default();
+        if self.hashes.get(&asset_id) != Some(&rules.hash()) {
+            self.invalidate(asset_id);
+            self.hashes.insert(asset_id, rules.hash());
+        }
+
+        let cached_properties = self.caches.entry(rules.hash()).or_default();
 
         // Avoid using HashMap::entry since it requires ownership of key
         if cached_properties.contains_key(selector) {
@@ -253,7 +659,6 @@ impl<T: Property> PropertyMeta<T> {
                     Ok(cache) => CacheState::Ok(cache),
                     Err(err) => {
                         error!("Failed to parse property {}. Error: {}", T::name(), err);
-                        // TODO: Clear cache state when the asset is reloaded, since values may be changed.
                         CacheState::Error
                     }
                 })
@@ -263,6 +668,49 @@ impl<T: Property> PropertyMeta<T> {
             cached_properties.get(selector).unwrap()
         }
     }
+
+    /// Evicts caches of modified or removed sheets, then calls `apply` with the cached value of each selected rule
+    /// which declares the property, along with its sheet, its selector and the entities matched by it.
+    ///
+    /// Used by [`Property::apply_system`], so properties which overrides it shares the same caching.
+    fn for_each_selected(
+        &mut self,
+        assets_events: &mut EventReader<AssetEvent<StyleSheetAsset>>,
+        assets: &Assets<StyleSheetAsset>,
+        apply_sheets: &StyleSheetState,
+        mut apply: impl FnMut(&T::Cache, &StyleSheetAsset, &Selector, &[Entity]),
+    ) {
+        for evt in assets_events.read() {
+            if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = evt {
+                self.invalidate(*id);
+            }
+        }
+
+        for (asset_id, selector, entities) in apply_sheets.selected() {
+            if let Some(rules) = assets.get(asset_id) {
+                if let CacheState::Ok(cached) = self.get_or_parse(asset_id, rules, selector) {
+                    trace!(
+                        r#"Applying property "{}" from sheet "{}" ({})"#,
+                        T::name(),
+                        rules.path(),
+                        selector
+                    );
+                    apply(cached, rules, selector, entities);
+                }
+            }
+        }
+    }
+
+    /// Evicts the cache used by the given asset, unless it's still used by another asset with the same content.
+    fn invalidate(&mut self, asset_id: AssetId<StyleSheetAsset>) {
+        let Some(hash) = self.hashes.remove(&asset_id) else {
+            return;
+        };
+
+        if !self.hashes.values().any(|h| *h == hash) {
+            self.caches.remove(&hash);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Deref, DerefMut)]
@@ -272,17 +720,392 @@ pub struct TrackedEntities(HashMap<SelectorElement, SmallVec<[Entity; 8]>>);
 #[derive(Debug, Clone, Default, Deref, DerefMut)]
 pub struct SelectedEntities(SmallVec<[(Selector, SmallVec<[Entity; 8]>); 8]>);
 
-/// Maps sheets for each [`StyleSheetAsset`].
+/// Holds the tracked and selected entities of a single [`StyleSheetAsset`].
+pub type SheetState = (AssetId<StyleSheetAsset>, TrackedEntities, SelectedEntities);
+
+/// Key used on [`StyleSheetState`] to hold the [`GlobalStyleSheet`](crate::GlobalStyleSheet) state, since it has no root.
+pub(crate) const GLOBAL_ROOT: Entity = Entity::PLACEHOLDER;
+
+/// Maps sheets for each root entity with a [`StyleSheet`](crate::StyleSheet) component.
+///
+/// Rules are applied ordered by [`Selector`] specificity first. For equal specificity, the
+/// [`GlobalStyleSheet`](crate::GlobalStyleSheet) is applied first, then roots are applied ordered by their depth
+/// on the hierarchy, so sheets of descendant roots overrides the ones of its ancestors, then by [`Entity`]. Sheets of each root are kept in the same order as
+/// [`StyleSheet::handles`](crate::StyleSheet::handles), so the application order is always deterministic.
 #[derive(Debug, Clone, Default, Resource, Deref, DerefMut)]
-pub struct StyleSheetState(Vec<(AssetId<StyleSheetAsset>, TrackedEntities, SelectedEntities)>);
+pub struct StyleSheetState {
+    #[deref]
+    roots: BTreeMap<Entity, SmallVec<[SheetState; 1]>>,
+    depths: HashMap<Entity, usize>,
+    // Roots which should have its styled entities reverted once the `StyleSheet` is removed.
+    reverts: HashSet<Entity>,
+}
 
 impl StyleSheetState {
+    /// Inserts or replaces the sheets of the given root, which is `depth` levels below the top of the hierarchy.
+    pub(crate) fn insert_root(
+        &mut self,
+        root: Entity,
+        depth: usize,
+        revert_on_removal: bool,
+        sheets: SmallVec<[SheetState; 1]>,
+    ) {
+        self.depths.insert(root, depth);
+        self.roots.insert(root, sheets);
+
+        if revert_on_removal {
+            self.reverts.insert(root);
+        } else {
+            self.reverts.remove(&root);
+        }
+    }
+
+    /// Removes all state of the given root, returning if its styled entities should be reverted.
+    ///
+    /// Returns `None` if the root isn't present.
+    pub(crate) fn remove_root(&mut self, root: Entity) -> Option<bool> {
+        self.roots.remove(&root)?;
+        self.depths.remove(&root);
+        Some(self.reverts.remove(&root))
+    }
+
+    /// Updates the depth of the given root, if it's present, like when it's moved to another parent.
+    pub(crate) fn set_depth(&mut self, root: Entity, depth: usize) {
+        if let Some(current) = self.depths.get_mut(&root) {
+            *current = depth;
+        }
+    }
+
+    /// Iterates over the sheets of each root, ancestors roots first.
+    fn ordered_roots(&self) -> impl Iterator<Item = &SmallVec<[SheetState; 1]>> {
+        let mut roots = self.roots.iter().collect::<SmallVec<[_; 8]>>();
+        // Stable sort, so roots with equal depth are kept ordered by entity
+        roots.sort_by_key(|(root, _)| {
+            (
+                **root != GLOBAL_ROOT,
+                self.depths.get(*root).copied().unwrap_or_default(),
+            )
+        });
+        roots.into_iter().map(|(_, sheets)| sheets)
+    }
+
+    /// Iterates over all sheets of all roots, in application order.
+    pub fn sheets(&self) -> impl Iterator<Item = &SheetState> {
+        self.ordered_roots().flatten()
+    }
+
+    /// Iterates over all selected entities of all roots, in cascade order.
+    ///
+    /// Rules are ordered by [`Selector`] specificity, then by root depth, ancestors roots first, then by the sheet
+    /// order on [`StyleSheet::handles`](crate::StyleSheet::handles) and then by source order inside the sheet.
+    /// This way, a descendant root sheet overrides an ancestor one and later sheets overrides earlier ones on rules
+    /// of equal specificity, while a more specific rule always wins.
+    pub fn selected(
+        &self,
+    ) -> impl Iterator<Item = (AssetId<StyleSheetAsset>, &Selector, &SmallVec<[Entity; 8]>)> {
+        let mut selected = self
+            .ordered_roots()
+            .flatten()
+            .flat_map(|(id, _, selected)| {
+                selected
+                    .iter()
+                    .map(move |(selector, entities)| (*id, selector, entities))
+            })
+            .collect::<SmallVec<[_; 8]>>();
+
+        // Stable sort, so root, sheet and source order are kept for equal specificity
+        selected.sort_by_key(|(_, selector, _)| selector.weight);
+        selected.into_iter()
+    }
+
     pub(crate) fn has_any_selected_entities(&self) -> bool {
-        self.iter().any(|(_, _, v)| !v.is_empty())
+        self.sheets().any(|(_, _, v)| !v.is_empty())
     }
 
     pub(crate) fn clear_selected_entities(&mut self) {
-        self.iter_mut().for_each(|(_, _, v)| v.clear());
+        self.values_mut().flatten().for_each(|(_, _, v)| v.clear());
+    }
+}
+
+/// Run condition which checks if any sheet with selected entities declares the property `T`.
+///
+/// Used to skip [`Property::apply_system`] of registered properties which aren't used by any applied sheet.
+pub(crate) fn have_property<T: Property>(
+    apply_sheets: Res<StyleSheetState>,
+    assets: Res<Assets<StyleSheetAsset>>,
+) -> bool {
+    apply_sheets.sheets().any(|(asset_id, _, selected)| {
+        !selected.is_empty()
+            && assets
+                .get(*asset_id)
+                .is_some_and(|sheet| sheet.contains_property(T::name()))
+    })
+}
+
+/// Maps font family names to [`Font`] handles, used by the `font-family` property.
+///
+/// This resource is populated by the user or by `@font-face` rules, so style sheets can use `font-family: "Inter";`
+/// instead of the font asset path. Family names which aren't registered here are loaded as asset paths.
+#[derive(Debug, Clone, Default, Resource, Deref, DerefMut)]
+pub struct FontRegistry {
+    #[deref]
+    families: HashMap<String, Handle<Font>>,
+    // Placeholder fonts written by font properties, mapped to the families each one must be resolved to.
+    placeholders: HashMap<AssetId<Font>, Vec<String>>,
+}
+
+impl FontRegistry {
+    /// Resolves the first family name which is registered or is an asset path with an extension,
+    /// like `fonts/inter.ttf`. When none of them is, the first family is loaded as an asset path.
+    pub fn resolve(&self, families: &[String], asset_server: &AssetServer) -> Option<Handle<Font>> {
+        for family in families {
+            if let Some(font) = self.families.get(family) {
+                return Some(font.clone());
+            }
+
+            if std::path::Path::new(family).extension().is_some() {
+                return Some(asset_server.load(family.clone()));
+            }
+        }
+
+        families
+            .first()
+            .map(|family| asset_server.load(family.clone()))
+    }
+
+    /// Checks if there is any placeholder font waiting to be resolved.
+    pub(crate) fn has_placeholders(&self) -> bool {
+        !self.placeholders.is_empty()
+    }
+
+    /// Resolves the given font if it's a placeholder written by [`FontFamilies::apply_placeholder`].
+    pub(crate) fn resolve_placeholder(
+        &self,
+        font: &Handle<Font>,
+        asset_server: &AssetServer,
+    ) -> Option<Handle<Font>> {
+        self.placeholders
+            .get(&font.id())
+            .and_then(|families| self.resolve(families, asset_server))
+    }
+}
+
+/// Font families parsed by font properties, like `"Inter", "Arial", sans-serif`.
+///
+/// Since [`FontRegistry`] isn't available to [`Property::apply`], a placeholder font is written on matched [`Text`]
+/// components, so the cascade order is kept, and then replaced by the resolved font on [`EcssSet::LateApply`](crate::EcssSet::LateApply).
+///
+/// [`Text`]: bevy::text::Text
+#[derive(Debug, Default)]
+pub struct FontFamilies {
+    families: Vec<String>,
+    placeholder: Handle<Font>,
+    // If the placeholder was already added to `FontRegistry`
+    registered: AtomicBool,
+}
+
+impl FontFamilies {
+    /// Creates the font families with the given names, in fallback order.
+    pub fn new(families: Vec<String>) -> Self {
+        let mut hasher = AHasher::default();
+        families.hash(&mut hasher);
+        // High bits namespaces placeholder fonts, so they don't collide with other assets.
+        let uuid = Uuid::from_u64_pair(0xecc5_f0e7_0000_0000, hasher.finish());
+
+        Self {
+            families,
+            placeholder: Handle::Weak(AssetId::Uuid { uuid }),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    /// The family names, in fallback order.
+    pub fn families(&self) -> &[String] {
+        &self.families
+    }
+
+    /// Returns the placeholder font to be written on [`Text`](bevy::text::Text) components.
+    ///
+    /// The first time it's called, the placeholder is added to [`FontRegistry`] by a deferred command,
+    /// so it can be resolved after all properties are applied.
+    pub fn apply_placeholder(&self, commands: &mut Commands) -> Handle<Font> {
+        if !self.registered.swap(true, Ordering::Relaxed) {
+            let (id, families) = (self.placeholder.id(), self.families.clone());
+            commands.add(move |world: &mut World| {
+                world
+                    .resource_mut::<FontRegistry>()
+                    .placeholders
+                    .insert(id, families);
+            });
+        }
+
+        self.placeholder.clone()
+    }
+}
+
+/// Holds the names of all properties registered with [`RegisterProperty`](crate::RegisterProperty).
+///
+/// It's used to warn about properties used on style sheets which doesn't match any registered [`Property`],
+/// like a misspelled `backgruond-color`.
+///
+/// Each property name is mapped to the type name of the [`Property`] which registered it.
+/// Components registered with [`register_reflect_component_selector`](crate::RegisterComponentSelector::register_reflect_component_selector)
+/// are also kept here, since any `component.field` property name is valid for them.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct RegisteredProperties {
+    properties: HashMap<&'static str, &'static str>,
+    reflect_components: HashMap<&'static str, TypeId>,
+}
+
+impl RegisteredProperties {
+    /// Registers the given property name, returning the type name of the [`Property`] which
+    /// already registered the same name, if any.
+    pub(crate) fn insert<T: Property>(&mut self) -> Option<&'static str> {
+        self.properties
+            .insert(T::name(), std::any::type_name::<T>())
+    }
+
+    /// Registers the given component name as writable by reflection, using `component.field` property names.
+    pub(crate) fn insert_reflect_component(&mut self, name: &'static str, type_id: TypeId) {
+        self.reflect_components.insert(name, type_id);
+    }
+
+    /// Returns the [`TypeId`] of the component registered with the given name to be written by reflection, if any.
+    pub fn reflect_component(&self, name: &str) -> Option<TypeId> {
+        self.reflect_components.get(name).copied()
+    }
+
+    /// Checks if there is any registered property with the given name.
+    ///
+    /// Names like `component.field` are considered registered if the component is writable by reflection.
+    pub fn contains(&self, name: &str) -> bool {
+        self.properties.contains_key(name)
+            || name
+                .split_once('.')
+                .is_some_and(|(component, _)| self.reflect_components.contains_key(component))
+    }
+
+    /// Returns the names used on the given sheet which doesn't match any registered property.
+    ///
+    /// Each name is returned only once, even if it's used on many rules, and names are sorted.
+    pub fn unknown_properties<'a>(&self, sheet: &'a StyleSheetAsset) -> Vec<&'a str> {
+        let mut unknown = sheet
+            .iter()
+            .flat_map(|rule| rule.properties.keys())
+            .map(String::as_str)
+            .filter(|name| !self.contains(name))
+            .collect::<Vec<_>>();
+
+        unknown.sort_unstable();
+        unknown.dedup();
+        unknown
+    }
+}
+
+/// Snapshot of which entities were matched by each [`Selector`] on the last time a style sheet
+/// was applied on a root entity.
+///
+/// Selected entities on [`StyleSheetState`] are cleared at the end of every frame, so this resource
+/// is updated right before that, keeping the last known matches of each root until it's selected again.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::StyleQuery;
+///
+/// fn debug_items(query: Res<StyleQuery>) {
+///     for entity in query.matches(".item") {
+///         info!("{entity:?} was matched by .item");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource)]
+pub struct StyleQuery(HashMap<Entity, HashMap<String, SmallVec<[Entity; 8]>>>);
+
+impl StyleQuery {
+    /// Iterates over all root entities which had some style sheet applied.
+    pub fn roots(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// Returns the entities matched by the given selector on the given root entity.
+    ///
+    /// The selector must be written as it is displayed by [`Selector`], like `.item` or `#menu button`.
+    pub fn selected(&self, root: Entity, selector: &str) -> &[Entity] {
+        self.0
+            .get(&root)
+            .and_then(|selectors| selectors.get(selector))
+            .map(|entities| entities.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Iterates over the entities matched by the given selector on all root entities.
+    pub fn matches<'a>(&'a self, selector: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.0
+            .values()
+            .filter_map(move |selectors| selectors.get(selector))
+            .flatten()
+            .copied()
+    }
+
+    /// Removes the snapshot of the given root, returning all entities which were matched on it, without duplicates.
+    pub(crate) fn remove_root(&mut self, root: Entity) -> SmallVec<[Entity; 8]> {
+        let mut entities = SmallVec::<[Entity; 8]>::new();
+
+        for matched in self
+            .0
+            .remove(&root)
+            .into_iter()
+            .flat_map(|s| s.into_values())
+        {
+            for entity in matched {
+                if !entities.contains(&entity) {
+                    entities.push(entity);
+                }
+            }
+        }
+
+        entities
+    }
+
+    /// Iterates over all root entities which matched any of the given entities.
+    pub(crate) fn roots_matching<'a>(
+        &'a self,
+        entities: &'a [Entity],
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.0
+            .iter()
+            .filter(|(_, selectors)| {
+                selectors
+                    .values()
+                    .flatten()
+                    .any(|entity| entities.contains(entity))
+            })
+            .map(|(root, _)| *root)
+    }
+
+    /// Replaces the snapshot of every root which has selected entities on the given state.
+    pub(crate) fn update(&mut self, state: &StyleSheetState) {
+        for (root, sheets) in state.iter() {
+            if sheets.iter().all(|(_, _, selected)| selected.is_empty()) {
+                continue;
+            }
+
+            let selectors = self.0.entry(*root).or_default();
+            selectors.clear();
+
+            for (_, _, selected) in sheets {
+                for (selector, entities) in selected.iter() {
+                    let matched = selectors.entry(selector.to_string()).or_default();
+                    for entity in entities {
+                        if !matched.contains(entity) {
+                            matched.push(*entity);
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -293,24 +1116,24 @@ impl StyleSheetState {
 ///
 /// These are the associated types that must by specified by implementors:
 /// - [`Cache`](Property::Cache) is a cached value to be applied by this trait.
-/// On the first time the `system` runs it'll call [`parse`](`Property::parse`) and cache the value.
-/// Subsequential runs will only fetch the cached value.
+///   On the first time the `system` runs it'll call [`parse`](`Property::parse`) and cache the value.
+///   Subsequential runs will only fetch the cached value.
 /// - [`Components`](Property::Components) is which components will be send to [`apply`](`Property::apply`) function whenever a
-/// valid cache exists and a matching property was found on any sheet rule. Check [`QueryData`] for more.
+///   valid cache exists and a matching property was found on any sheet rule. Check [`QueryData`] for more.
 /// - [`Filters`](Property::Filters) is used to filter which entities will be applied the property modification.
-/// Entities are first filtered by [`selectors`](`Selector`), but it can be useful to also ensure some behavior for safety reasons,
-/// like only inserting [`JustifyText`](bevy::prelude::JustifyText) if the entity also has a [`Text`](bevy::prelude::Text) component.
-///  Check [`WorldQuery`] for more.
+///   Entities are first filtered by [`selectors`](`Selector`), but it can be useful to also ensure some behavior for safety reasons,
+///   like only inserting [`JustifyText`](bevy::prelude::JustifyText) if the entity also has a [`Text`](bevy::prelude::Text) component.
+///   Check [`WorldQuery`] for more.
 ///
 /// These are tree functions required to be implemented:
 /// - [`name`](Property::name) indicates which property name should matched for.
 /// - [`parse`](Property::parse) parses the [`PropertyValues`] into the [`Cache`](Property::Cache) value to be reused across multiple entities.
 /// - [`apply`](Property::apply) applies on the given [`Components`](Property::Components) the [`Cache`](Property::Cache) value.
-/// Additionally, an [`AssetServer`] and [`Commands`] parameters are provided for more complex use cases.
+///   Additionally, an [`AssetServer`] and [`Commands`] parameters are provided for more complex use cases.
 ///
 /// Also, there one function which have default implementations:
 /// - [`apply_system`](Property::apply_system) is a [`system`](https://docs.rs/bevy_ecs/latest/bevy_ecs/system/index.html) which interacts with
-/// [ecs world](`bevy::prelude::World`) and call the [`apply`](Property::apply) function on every matched entity.
+///   [ecs world](`bevy::prelude::World`) and call the [`apply`](Property::apply) function on every matched entity.
 pub trait Property: Default + Sized + Send + Sync + 'static {
     /// The cached value type to be applied by property.
     type Cache: Default + Any + Send + Sync;
@@ -345,32 +1168,236 @@ pub trait Property: Default + Sized + Send + Sync + 'static {
     /// [ecs world](`bevy::prelude::World`) and call [`apply`](Property::apply) function on every matched entity.
     ///
     /// The default implementation will cover most use cases, by just implementing [`apply`](Property::apply)
+    ///
+    /// This system is skipped on frames where no sheet with selected entities declares the property [`name`](Property::name).
     fn apply_system(
         mut local: Local<PropertyMeta<Self>>,
+        mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
         assets: Res<Assets<StyleSheetAsset>>,
         apply_sheets: Res<StyleSheetState>,
         mut q_nodes: Query<Self::Components, Self::Filters>,
         asset_server: Res<AssetServer>,
         mut commands: Commands,
     ) {
-        for (asset_id, _, selected) in apply_sheets.iter() {
-            if let Some(rules) = assets.get(*asset_id) {
-                for (selector, entities) in selected.iter() {
-                    if let CacheState::Ok(cached) = local.get_or_parse(rules, selector) {
-                        trace!(
-                            r#"Applying property "{}" from sheet "{}" ({})"#,
-                            Self::name(),
-                            rules.path(),
-                            selector
-                        );
-                        for entity in entities {
-                            if let Ok(components) = q_nodes.get_mut(*entity) {
-                                Self::apply(cached, components, &asset_server, &mut commands);
-                            }
-                        }
+        local.for_each_selected(
+            &mut assets_events,
+            &assets,
+            &apply_sheets,
+            |cached, _, _, entities| {
+                for entity in entities {
+                    if let Ok(components) = q_nodes.get_mut(*entity) {
+                        Self::apply(cached, components, &asset_server, &mut commands);
                     }
                 }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::smallvec;
+
+    use super::*;
+    use crate::property::impls::WidthProperty;
+
+    #[test]
+    fn parse_vec() {
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(10.0),
+            PropertyToken::Number(20.0)
+        ]);
+        assert_eq!(values.vec2(), Some(Vec2::new(10.0, 20.0)));
+        assert_eq!(values.vec4(), None);
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(1.0),
+            PropertyToken::Dimension(2.0),
+            PropertyToken::Percentage(3.0),
+            PropertyToken::Number(-4.0)
+        ]);
+        assert_eq!(values.vec4(), Some(Vec4::new(1.0, 2.0, 3.0, -4.0)));
+        assert_eq!(values.vec2(), None);
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Number(10.0),
+            PropertyToken::Identifier("auto".to_string())
+        ]);
+        assert_eq!(values.vec2(), None, "All values should be numbers");
+    }
+
+    #[test]
+    fn parse_color() {
+        use crate::StyleSheetAsset;
+
+        let color = |value: &str| {
+            let sheet = StyleSheetAsset::parse("", &format!("a {{ color: {value}; }}"));
+            let color = sheet.iter().next().unwrap().properties["color"].color();
+            color
+        };
+
+        assert_eq!(color("red"), Some(Color::RED));
+        assert_eq!(color("#ff0000"), Some(Color::RED));
+        assert_eq!(color("rgb(255, 0, 0)"), Some(Color::RED));
+        assert_eq!(color("rgb(100% 0 0)"), Some(Color::RED));
+        assert_eq!(
+            color("rgba(255, 0, 0, 0.5)"),
+            Some(Color::rgba(1.0, 0.0, 0.0, 0.5))
+        );
+        assert_eq!(
+            color("rgb(255 0 0 / 50%)"),
+            Some(Color::rgba(1.0, 0.0, 0.0, 0.5))
+        );
+        assert_eq!(color("rgb(255, 0)"), None);
+        assert_eq!(color("red blue"), None, "Should be a single color");
+    }
+
+    #[test]
+    fn parse_pair() {
+        let values = PropertyValues(smallvec![PropertyToken::Dimension(10.0)]);
+        assert_eq!(
+            values.pair(PropertyValues::token_val),
+            Some((Val::Px(10.0), Val::Px(10.0))),
+            "Single value should be used on both"
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Dimension(10.0),
+            PropertyToken::Percentage(5.0)
+        ]);
+        assert_eq!(
+            values.pair(PropertyValues::token_val),
+            Some((Val::Px(10.0), Val::Percent(5.0)))
+        );
+
+        let values = PropertyValues(smallvec![
+            PropertyToken::Dimension(10.0),
+            PropertyToken::String("a".to_string())
+        ]);
+        assert_eq!(values.pair(PropertyValues::token_val), None);
+
+        let values = PropertyValues(smallvec![PropertyToken::Dimension(1.0); 3]);
+        assert_eq!(values.pair(PropertyValues::token_val), None);
+        assert_eq!(
+            PropertyValues::default().pair(PropertyValues::token_val),
+            None
+        );
+    }
+
+    #[test]
+    fn iterate_all_values() {
+        let rules = StyleSheetAsset::parse("", "a { g: h i j; l: 15.3px 3% k 0; }");
+        let rule = rules.iter().next().unwrap();
+
+        let values = &rule.properties["g"];
+        assert_eq!(values.identifiers().collect::<Vec<_>>(), ["h", "i", "j"]);
+        assert_eq!(values.identifier(), Some("h"));
+        assert_eq!(values.f32s().count(), 0);
+
+        let values = &rule.properties["l"];
+        assert_eq!(values.identifiers().collect::<Vec<_>>(), ["k"]);
+        assert_eq!(values.f32s().collect::<Vec<_>>(), [15.3, 3.0, 0.0]);
+        assert_eq!(
+            values.vals().collect::<Vec<_>>(),
+            [Val::Px(15.3), Val::Percent(3.0), Val::Px(0.0)]
+        );
+        assert_eq!(values.val(), Some(Val::Px(15.3)));
+    }
+
+    #[test]
+    fn parse_calc() {
+        let parse = |value: &str| {
+            let rules = StyleSheetAsset::parse("", &format!("a {{ width: {value}; }}"));
+            let values = rules.iter().next().unwrap().properties["width"].clone();
+            values
+        };
+
+        let values = parse("calc(100% - 20px)");
+        let calc = values.calc().unwrap();
+        assert_eq!(
+            calc,
+            CalcVal {
+                percent: 100.0,
+                px: -20.0,
+                ..Default::default()
             }
+        );
+        assert_eq!(calc.to_string(), "100% - 20px");
+        assert_eq!(calc.to_val(), None);
+        assert_eq!(
+            values.val(),
+            Some(Val::Percent(100.0)),
+            "Should approximate"
+        );
+
+        assert_eq!(
+            parse("calc(100% / 4 - 2 * 5px + 10vw * 0.5)").calc(),
+            Some(CalcVal {
+                percent: 25.0,
+                px: -10.0,
+                vw: 5.0,
+                ..Default::default()
+            })
+        );
+        assert_eq!(parse("calc(10px + 5px)").val(), Some(Val::Px(15.0)));
+        assert_eq!(parse("calc(50% - 50%)").val(), Some(Val::Px(0.0)));
+
+        for invalid in [
+            "calc(10px 5px)",
+            "calc(10px - )",
+            "calc(10px * 5px)",
+            "calc(10px / 0)",
+            "calc(auto + 10px)",
+        ] {
+            assert_eq!(parse(invalid).calc(), None, "{invalid}");
         }
     }
+
+    #[test]
+    fn resolve_calc() {
+        let calc = CalcVal {
+            percent: 100.0,
+            px: -20.0,
+            ..Default::default()
+        };
+        assert_eq!(calc.resolve(200.0, Vec2::ZERO, 1.0), 180.0);
+        assert_eq!(
+            calc.resolve(200.0, Vec2::ZERO, 2.0),
+            80.0,
+            "Relative terms should be scaled down"
+        );
+
+        let calc = CalcVal {
+            vw: 10.0,
+            vmax: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(calc.resolve(0.0, Vec2::new(800.0, 600.0), 1.0), 120.0);
+    }
+
+    #[test]
+    fn evict_cache_of_modified_sheet() {
+        let id = AssetId::<StyleSheetAsset>::default();
+        let selector = Selector::new(smallvec![SelectorElement::Class("a".to_string())]);
+        let mut meta = PropertyMeta::<WidthProperty>::default();
+
+        let invalid = StyleSheetAsset::parse("", ".a { width: red; }");
+        assert!(matches!(
+            meta.get_or_parse(id, &invalid, &selector),
+            CacheState::Error
+        ));
+
+        let valid = StyleSheetAsset::parse("", ".a { width: 10px; }");
+        assert!(matches!(
+            meta.get_or_parse(id, &valid, &selector),
+            CacheState::Ok((Val::Px(val), None)) if *val == 10.0
+        ));
+        assert_eq!(meta.caches.len(), 1, "Old cache should be evicted");
+
+        meta.invalidate(id);
+        assert!(
+            meta.caches.is_empty(),
+            "Removed sheet cache should be evicted"
+        );
+    }
 }
@@ -0,0 +1,203 @@
+use bevy::{
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, ReflectMut, TypeInfo, VariantInfo},
+};
+
+use crate::{EcssError, StyleSheetAsset};
+
+use super::{PropertyValues, RegisteredProperties, StyleSheetState};
+
+/// Applies `component.field` properties on components registered with
+/// [`register_reflect_component_selector`](crate::RegisterComponentSelector::register_reflect_component_selector).
+///
+/// Unlike [`Property`](super::Property) implementations, values are parsed using the reflected field type, so
+/// they aren't cached. Only `f32`, `bool`, `String` and unit enum fields are supported.
+pub(crate) fn apply_reflect_properties(world: &mut World) {
+    let changes = {
+        let state = world.resource::<StyleSheetState>();
+        if !state.has_any_selected_entities() {
+            return;
+        }
+
+        let assets = world.resource::<Assets<StyleSheetAsset>>();
+        let registered = world.resource::<RegisteredProperties>();
+
+        let mut changes = Vec::new();
+        for (asset_id, selector, entities) in state.selected() {
+            let Some(rule) = assets
+                .get(asset_id)
+                .and_then(|sheet| sheet.iter().find(|rule| &rule.selector == selector))
+            else {
+                continue;
+            };
+
+            for (name, values) in &rule.properties {
+                let Some(type_id) = name
+                    .split_once('.')
+                    .and_then(|(component, _)| registered.reflect_component(component))
+                else {
+                    continue;
+                };
+
+                changes.extend(
+                    entities
+                        .iter()
+                        .map(|entity| (*entity, type_id, name.clone(), values.clone())),
+                );
+            }
+        }
+
+        changes
+    };
+
+    if changes.is_empty() {
+        return;
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    for (entity, type_id, name, values) in changes {
+        let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id) else {
+            error!(
+                "Failed to apply property {}. Component isn't reflected with #[reflect(Component)]",
+                name
+            );
+            continue;
+        };
+
+        let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+
+        let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) else {
+            continue;
+        };
+
+        trace!("Applying reflected property {} on {:?}", name, entity);
+        if let Err(err) = set_field(&mut *component, &name, &values) {
+            error!("Failed to apply property {}. Error: {}", name, err);
+        }
+    }
+}
+
+/// Sets the field of the given `component.field` property name on the reflected component.
+///
+/// Hyphens on field name are replaced by underscores, so `max-speed` sets `max_speed` field.
+fn set_field(
+    component: &mut dyn Reflect,
+    name: &str,
+    values: &PropertyValues,
+) -> Result<(), EcssError> {
+    let invalid = || EcssError::InvalidPropertyValue(name.to_string());
+    let unsupported = || EcssError::UnsupportedProperty(name.to_string());
+
+    let field_name = name
+        .split_once('.')
+        .map(|(_, field)| field.replace('-', "_"))
+        .ok_or_else(unsupported)?;
+
+    let ReflectMut::Struct(component) = component.reflect_mut() else {
+        return Err(unsupported());
+    };
+
+    let field = component.field_mut(&field_name).ok_or_else(unsupported)?;
+
+    if let Some(value) = field.downcast_mut::<f32>() {
+        *value = values.f32().ok_or_else(invalid)?;
+    } else if let Some(value) = field.downcast_mut::<bool>() {
+        *value = match values.keyword().as_deref() {
+            Some("true") => true,
+            Some("false") => false,
+            _ => return Err(invalid()),
+        };
+    } else if let Some(value) = field.downcast_mut::<String>() {
+        *value = values
+            .string()
+            .or_else(|| values.identifier().map(str::to_string))
+            .ok_or_else(invalid)?;
+    } else if let Some(TypeInfo::Enum(info)) = field.get_represented_type_info() {
+        // Variants are matched ignoring case and hyphens, so `fast-forward` matches `FastForward`
+        let keyword = values.keyword().ok_or_else(invalid)?.replace('-', "");
+        let variant = info
+            .iter()
+            .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+            .find(|variant| variant.name().eq_ignore_ascii_case(&keyword))
+            .ok_or_else(invalid)?;
+
+        field.apply(&DynamicEnum::new(variant.name(), DynamicVariant::Unit));
+    } else {
+        return Err(unsupported());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EcssPlugin, RegisterComponentSelector, StyleSheet};
+
+    #[derive(Debug, Default, PartialEq, Reflect)]
+    enum Mode {
+        #[default]
+        Walk,
+        FastForward,
+    }
+
+    #[derive(Debug, Default, Component, Reflect)]
+    #[reflect(Component)]
+    struct Movement {
+        max_speed: f32,
+        enabled: bool,
+        label: String,
+        mode: Mode,
+    }
+
+    #[test]
+    fn apply_reflected_fields() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .register_reflect_component_selector::<Movement>("movement");
+
+        let content = r#"
+            movement {
+                movement.max-speed: 10;
+                movement.enabled: true;
+                movement.label: "runner";
+                movement.mode: fast-forward;
+                movement.unknown: 1;
+            }
+        "#;
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", content));
+
+        let root = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                Movement::default(),
+                StyleSheet::new(sheet),
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        let movement = app.world.get::<Movement>(root).unwrap();
+        assert_eq!(movement.max_speed, 10.0);
+        assert!(movement.enabled);
+        assert_eq!(movement.label, "runner");
+        assert_eq!(movement.mode, Mode::FastForward);
+
+        let registered = app.world.resource::<RegisteredProperties>();
+        assert!(registered.contains("movement.max-speed"));
+        assert!(!registered.contains("moving.max-speed"));
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::{
+    ecs::{event::ManualEventReader, system::SystemState},
+    log::trace,
+    prelude::*,
+    utils::HashMap,
+};
+use smallvec::SmallVec;
+
+use crate::{selector::Selector, StyleSheetAsset};
+
+use super::{CacheState, Property, PropertyMeta, StyleSheetState};
+
+/// A selected rule which has a property handled by a [`PropertyApplier`].
+type SelectedRule<'a> = (
+    AssetId<StyleSheetAsset>,
+    &'a StyleSheetAsset,
+    &'a Selector,
+    &'a [Entity],
+);
+
+/// Same params used by [`Property::apply_system`] to apply a property.
+type ApplierParams<T> = (
+    Query<'static, 'static, <T as Property>::Components, <T as Property>::Filters>,
+    Res<'static, AssetServer>,
+    Commands<'static, 'static>,
+);
+
+/// Type erased [`Property`] applier, so all properties can be dispatched by a single system.
+trait PropertyApplier: Send + Sync {
+    /// Evicts the cache used by the given asset.
+    fn invalidate(&mut self, asset_id: AssetId<StyleSheetAsset>);
+
+    /// Applies the property of each selected rule on its entities.
+    fn apply(&mut self, world: &mut World, selected: &[SelectedRule]);
+
+    /// Applies commands issued by [`Property::apply`].
+    fn apply_deferred(&mut self, world: &mut World);
+}
+
+struct Applier<T: Property> {
+    meta: PropertyMeta<T>,
+    params: Option<SystemState<ApplierParams<T>>>,
+}
+
+impl<T: Property> Default for Applier<T> {
+    fn default() -> Self {
+        Self {
+            meta: Default::default(),
+            params: None,
+        }
+    }
+}
+
+impl<T: Property> PropertyApplier for Applier<T> {
+    fn invalidate(&mut self, asset_id: AssetId<StyleSheetAsset>) {
+        self.meta.invalidate(asset_id);
+    }
+
+    fn apply(&mut self, world: &mut World, selected: &[SelectedRule]) {
+        let params = self.params.get_or_insert_with(|| SystemState::new(world));
+        let (mut q_nodes, asset_server, mut commands) = params.get_mut(world);
+
+        for (asset_id, rules, selector, entities) in selected {
+            if let CacheState::Ok(cached) = self.meta.get_or_parse(*asset_id, rules, selector) {
+                trace!(
+                    r#"Applying property "{}" from sheet "{}" ({})"#,
+                    T::name(),
+                    rules.path(),
+                    selector
+                );
+                for entity in *entities {
+                    if let Ok(components) = q_nodes.get_mut(*entity) {
+                        T::apply(cached, components, &asset_server, &mut commands);
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        if let Some(params) = &mut self.params {
+            params.apply(world);
+        }
+    }
+}
+
+/// Holds all properties registered when [`EcssPlugin::single_pass_apply`](crate::EcssPlugin::single_pass_apply)
+/// is used, in registration order.
+#[derive(Default, Resource)]
+pub(crate) struct PropertyAppliers {
+    appliers: Vec<Box<dyn PropertyApplier>>,
+    // Whether each applier must run after all others had their commands applied, like `opacity`.
+    late: Vec<bool>,
+    names: HashMap<&'static str, SmallVec<[usize; 1]>>,
+    events: ManualEventReader<AssetEvent<StyleSheetAsset>>,
+}
+
+impl PropertyAppliers {
+    /// Adds the given property to be applied by [`apply_properties`].
+    ///
+    /// `late` properties are applied after all others, once their commands are applied.
+    pub(crate) fn push<T: Property>(&mut self, late: bool) {
+        self.names
+            .entry(T::name())
+            .or_default()
+            .push(self.appliers.len());
+        self.appliers.push(Box::<Applier<T>>::default());
+        self.late.push(late);
+    }
+}
+
+/// Applies all properties registered on [`PropertyAppliers`] with a single walk over [`StyleSheetState`].
+///
+/// Selected rules are grouped by the properties they use, so only properties used by some rule are applied.
+/// Properties are still applied in registration order, except late ones, like `opacity`, which are applied
+/// after all others had their commands applied, the same as [`EcssSet::LateApply`](crate::EcssSet::LateApply).
+pub(crate) fn apply_properties(world: &mut World) {
+    world.resource_scope(|world, mut appliers: Mut<PropertyAppliers>| {
+        let PropertyAppliers {
+            appliers,
+            late,
+            names,
+            events,
+        } = &mut *appliers;
+
+        for evt in events.read(world.resource::<Events<AssetEvent<StyleSheetAsset>>>()) {
+            if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = evt {
+                appliers
+                    .iter_mut()
+                    .for_each(|applier| applier.invalidate(*id));
+            }
+        }
+
+        if !world
+            .resource::<StyleSheetState>()
+            .has_any_selected_entities()
+        {
+            return;
+        }
+
+        world.resource_scope(|world, state: Mut<StyleSheetState>| {
+            world.resource_scope(|world, assets: Mut<Assets<StyleSheetAsset>>| {
+                let mut selected = vec![Vec::<SelectedRule>::new(); appliers.len()];
+
+                for (asset_id, selector, entities) in state.selected() {
+                    let Some(rules) = assets.get(asset_id) else {
+                        continue;
+                    };
+
+                    let Some(rule) = rules.rule_for(selector) else {
+                        continue;
+                    };
+
+                    for name in rule.properties.keys() {
+                        for &index in names.get(name.as_str()).into_iter().flatten() {
+                            selected[index].push((asset_id, rules, selector, entities));
+                        }
+                    }
+                }
+
+                for pass_late in [false, true] {
+                    for ((applier, selected), _) in appliers
+                        .iter_mut()
+                        .zip(&selected)
+                        .zip(late.iter())
+                        .filter(|(_, &late)| late == pass_late)
+                    {
+                        if !selected.is_empty() {
+                            applier.apply(world, selected);
+                        }
+                    }
+
+                    appliers
+                        .iter_mut()
+                        .for_each(|applier| applier.apply_deferred(world));
+                }
+            });
+        });
+    });
+}
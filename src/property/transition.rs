@@ -0,0 +1,311 @@
+use bevy::{
+    prelude::{Color, Commands, Component, Deref, DerefMut, Entity, Query, Res, Time},
+    ui::Val,
+    utils::HashMap,
+};
+
+use super::{CalcVal, Property};
+
+/// Holds the transition duration, in seconds, of each property name which should be animated.
+///
+/// This component is inserted by `transition` property and it's used by animation systems
+/// of every [`AnimatableProperty`] to know if a value change should be interpolated or not.
+///
+/// The special property name `all` matches any [`AnimatableProperty`].
+#[derive(Debug, Default, Clone, Component, Deref, DerefMut)]
+pub struct Transitions(pub(crate) HashMap<String, f32>);
+
+impl Transitions {
+    /// Returns the transition duration, in seconds, of the given property name, if any.
+    pub fn duration(&self, name: &str) -> Option<f32> {
+        self.0.get(name).or_else(|| self.0.get("all")).copied()
+    }
+}
+
+/// Linear interpolation between two values. Used to animate [`AnimatableProperty`] values.
+pub trait Lerp: Clone + PartialEq {
+    /// Interpolates between `self` and `to`, where `t` is in the range `0.0..=1.0`.
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let [r, g, b, a] = self.as_rgba_f32();
+        let [to_r, to_g, to_b, to_a] = to.as_rgba_f32();
+
+        Color::rgba(
+            r.lerp(&to_r, t),
+            g.lerp(&to_g, t),
+            b.lerp(&to_b, t),
+            a.lerp(&to_a, t),
+        )
+    }
+}
+
+impl<T: Lerp> Lerp for Option<T> {
+    /// Only values which are both `Some` are interpolated, otherwise the `to` value is returned.
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (Some(a), Some(b)) => Some(a.lerp(b, t)),
+            _ => to.clone(),
+        }
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        (self.0.lerp(&to.0, t), self.1.lerp(&to.1, t))
+    }
+}
+
+impl Lerp for CalcVal {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        *self + (*to - *self) * t
+    }
+}
+
+impl Lerp for Val {
+    /// Only values of the same unit are interpolated, otherwise the `to` value is returned.
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (Val::Px(a), Val::Px(b)) => Val::Px(a.lerp(b, t)),
+            (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.lerp(b, t)),
+            (Val::Vw(a), Val::Vw(b)) => Val::Vw(a.lerp(b, t)),
+            (Val::Vh(a), Val::Vh(b)) => Val::Vh(a.lerp(b, t)),
+            (Val::VMin(a), Val::VMin(b)) => Val::VMin(a.lerp(b, t)),
+            (Val::VMax(a), Val::VMax(b)) => Val::VMax(a.lerp(b, t)),
+            _ => *to,
+        }
+    }
+}
+
+/// A [`Property`] which value can be smoothly changed over time, when a `transition` is set.
+///
+/// Each implementation of this trait should be registered with
+/// [`register_animatable_property`](crate::RegisterProperty::register_animatable_property).
+///
+/// The animation doesn't interfere with how [`Property::apply`] works. Instead, a system watches
+/// for changes on [`Target`](AnimatableProperty::Target) component and, whenever the value is
+/// changed, it's reverted to the current value and interpolated toward the new value.
+pub trait AnimatableProperty: Property
+where
+    Self::Cache: Lerp,
+{
+    /// Which component holds the value modified by this property.
+    type Target: Component;
+
+    /// Reads the current property value on the given [`Target`](AnimatableProperty::Target).
+    fn get(target: &Self::Target) -> Self::Cache;
+
+    /// Writes the given value on the given [`Target`](AnimatableProperty::Target).
+    fn set(target: &mut Self::Target, value: &Self::Cache);
+}
+
+/// Holds the animation state of an [`AnimatableProperty`] on an entity.
+#[derive(Debug, Component)]
+pub struct PropertyTransition<P>
+where
+    P: AnimatableProperty,
+    P::Cache: Lerp,
+{
+    from: P::Cache,
+    to: P::Cache,
+    current: P::Cache,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl<P> PropertyTransition<P>
+where
+    P: AnimatableProperty,
+    P::Cache: Lerp,
+{
+    /// Creates an idle transition, which current value is the given one.
+    fn idle(value: P::Cache) -> Self {
+        Self {
+            from: value.clone(),
+            to: value.clone(),
+            current: value,
+            elapsed: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    /// Starts a new transition from current value toward the given one.
+    fn start(&mut self, to: P::Cache, duration: f32) {
+        self.from = self.current.clone();
+        self.to = to;
+        self.elapsed = 0.0;
+        self.duration = duration;
+
+        if self.is_finished() {
+            self.current = self.to.clone();
+        }
+    }
+
+    /// Advances the transition by the given delta, in seconds, and returns the new current value.
+    fn advance(&mut self, delta: f32) -> &P::Cache {
+        self.elapsed += delta;
+
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        };
+
+        // Lerp may not yield exactly the final value, so it's set once the duration has elapsed
+        self.current = if t >= 1.0 {
+            self.to.clone()
+        } else {
+            self.from.lerp(&self.to, t)
+        };
+        &self.current
+    }
+
+    /// Checks if the transition duration has elapsed, so the current value is the final one.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Query used by [`animate_property`] to fetch the animation state of all targets.
+type AnimationTargetQuery<'w, 's, P> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transitions,
+        &'static mut <P as AnimatableProperty>::Target,
+        Option<&'static mut PropertyTransition<P>>,
+    ),
+>;
+
+/// Animates the given [`AnimatableProperty`] on all entities which has a [`Transitions`] for it.
+pub(crate) fn animate_property<P>(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut q_targets: AnimationTargetQuery<P>,
+) where
+    P: AnimatableProperty,
+    P::Cache: Lerp,
+{
+    for (entity, transitions, mut target, maybe_transition) in &mut q_targets {
+        let Some(duration) = transitions.duration(P::name()) else {
+            continue;
+        };
+
+        let value = P::get(&target);
+
+        let Some(mut transition) = maybe_transition else {
+            // There is nothing to animate from, so just keep track of the current value.
+            commands
+                .entity(entity)
+                .insert(PropertyTransition::<P>::idle(value));
+            continue;
+        };
+
+        if value != transition.current {
+            transition.start(value, duration);
+        }
+
+        if !transition.is_finished() {
+            P::set(&mut target, transition.advance(time.delta_seconds()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::property::impls::BackgroundColorProperty;
+
+    #[test]
+    fn transition_partial_duration() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, animate_property::<BackgroundColorProperty>);
+
+        let entity = app
+            .world
+            .spawn((
+                BackgroundColor(Color::BLACK),
+                Transitions([("background-color".to_string(), 1.0)].into()),
+            ))
+            .id();
+
+        // First run only starts tracking the current value
+        app.update();
+        app.world.get_mut::<BackgroundColor>(entity).unwrap().0 = Color::WHITE;
+
+        // Since the value was changed, it should be reverted and animated toward the new one
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        app.update();
+
+        let [r, g, b, a] = app
+            .world
+            .get::<BackgroundColor>(entity)
+            .unwrap()
+            .0
+            .as_rgba_f32();
+        assert!((r - 0.5).abs() < 0.001, "Should be halfway: {r}");
+        assert!((g - 0.5).abs() < 0.001, "Should be halfway: {g}");
+        assert!((b - 0.5).abs() < 0.001, "Should be halfway: {b}");
+        assert_eq!(a, 1.0);
+
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(600));
+        app.update();
+
+        assert_eq!(
+            app.world.get::<BackgroundColor>(entity).unwrap().0,
+            Color::WHITE,
+            "Should have finished the transition"
+        );
+        assert!(app
+            .world
+            .get::<PropertyTransition<BackgroundColorProperty>>(entity)
+            .unwrap()
+            .is_finished());
+    }
+
+    #[test]
+    fn transition_finishes_after_duration() {
+        let to = Color::rgb(0.7, 0.9, 0.4);
+        let mut transition =
+            PropertyTransition::<BackgroundColorProperty>::idle(Color::rgb(0.1, 0.2, 0.3));
+        assert!(transition.is_finished(), "Idle transitions are finished");
+
+        transition.start(to, 0.3);
+        transition.advance(0.2);
+        assert!(!transition.is_finished());
+
+        transition.advance(0.2);
+        assert!(transition.is_finished());
+        assert_eq!(
+            *transition.advance(0.0),
+            to,
+            "Should end exactly on final value"
+        );
+
+        transition.start(Color::RED, 0.0);
+        assert!(transition.is_finished());
+        assert_eq!(
+            transition.current,
+            Color::RED,
+            "Transitions without duration should jump to the final value"
+        );
+    }
+}
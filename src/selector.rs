@@ -1,13 +1,22 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    hash::{Hash, Hasher},
+    ops::Range,
+};
 
-use bevy::utils::AHasher;
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    utils::AHasher,
+};
 use cssparser::CowRcStr;
 use smallvec::{smallvec, SmallVec};
 
+use crate::{parser::StyleSheetParser, EcssError};
+
 /// Represents a selector element on a style sheet rule.
 /// A single selector can have multiple elements, for instance a selector of `button.enabled`
 /// Would generated two elements, one for `button` and another for `.enabled`.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectorElement {
     /// A name selector element, like `#score_window`. On CSS used on web, this is as known as id.
     Name(String),
@@ -17,29 +26,150 @@ pub enum SelectorElement {
     Class(String),
     /// Indicates a parent-child relation between previous elements and next elements, like `window .border`
     Child,
+    /// Indicates the next elements must be direct children of previous elements, like `window > .border`
+    DirectChild,
     /// A keyword added to a selector that specifies a special state of the selected element(s), like `button:hover`
     PseudoClass(PseudoClassElement),
     /// Selects any component, like `*` on CSS.
     Any,
+    /// A pseudo-element, like `.icon::before`. It must be the last element of a selector.
+    PseudoElement(PseudoElement),
+    /// An attribute selector element, which matches a registered attribute value, like `[interaction=pressed]`
+    Attribute {
+        /// Registered attribute name, like `interaction`.
+        name: String,
+        /// Operator used to compare attribute value.
+        op: AttributeOperator,
+        /// Value to be compared with, like `pressed`.
+        value: String,
+    },
 }
 
-/// Represents a pseudo-class as per (mdn docs)[https://developer.mozilla.org/en-US/docs/Web/CSS/Pseudo-classes]
-/// Not all pseudo-classes are supported, in which case, an `Unsupported` variant will be used.
+/// Operator used on an attribute selector element, like `=` on `[interaction=pressed]`.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttributeOperator {
+    /// Only checks if the attribute exists, like `[interaction]`.
+    Exists,
+    /// `[name=value]`
+    Equals,
+    /// `[name!=value]`
+    NotEquals,
+    /// `[name<value]`
+    Less,
+    /// `[name<=value]`
+    LessOrEqual,
+    /// `[name>value]`
+    Greater,
+    /// `[name>=value]`
+    GreaterOrEqual,
+    /// `[name*=value]`, only supported by text attributes.
+    Contains,
+    /// `[name^=value]`, only supported by text attributes.
+    StartsWith,
+    /// `[name$=value]`, only supported by text attributes.
+    EndsWith,
+}
+
+impl AttributeOperator {
+    /// Compares the given attribute value with the selector value using this operator.
+    ///
+    /// This is a convenience function to be used by attribute selectors predicates.
+    /// Substring operators never match, use [`compare_str`](AttributeOperator::compare_str) for text attributes.
+    pub fn compare<T: PartialOrd + ?Sized>(&self, attribute: &T, value: &T) -> bool {
+        match self {
+            AttributeOperator::Exists => true,
+            AttributeOperator::Equals => attribute == value,
+            AttributeOperator::NotEquals => attribute != value,
+            AttributeOperator::Less => attribute < value,
+            AttributeOperator::LessOrEqual => attribute <= value,
+            AttributeOperator::Greater => attribute > value,
+            AttributeOperator::GreaterOrEqual => attribute >= value,
+            AttributeOperator::Contains
+            | AttributeOperator::StartsWith
+            | AttributeOperator::EndsWith => false,
+        }
+    }
+
+    /// Compares the given text attribute value with the selector value using this operator.
+    ///
+    /// Unlike [`compare`](AttributeOperator::compare), this also supports substring operators.
+    pub fn compare_str(&self, attribute: &str, value: &str) -> bool {
+        match self {
+            AttributeOperator::Contains => attribute.contains(value),
+            AttributeOperator::StartsWith => attribute.starts_with(value),
+            AttributeOperator::EndsWith => attribute.ends_with(value),
+            _ => self.compare(attribute, value),
+        }
+    }
+}
+
+impl std::fmt::Display for AttributeOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeOperator::Exists => Ok(()),
+            AttributeOperator::Equals => write!(f, "="),
+            AttributeOperator::NotEquals => write!(f, "!="),
+            AttributeOperator::Less => write!(f, "<"),
+            AttributeOperator::LessOrEqual => write!(f, "<="),
+            AttributeOperator::Greater => write!(f, ">"),
+            AttributeOperator::GreaterOrEqual => write!(f, ">="),
+            AttributeOperator::Contains => write!(f, "*="),
+            AttributeOperator::StartsWith => write!(f, "^="),
+            AttributeOperator::EndsWith => write!(f, "$="),
+        }
+    }
+}
+
+/// Represents a pseudo-class as per (mdn docs)[https://developer.mozilla.org/en-US/docs/Web/CSS/Pseudo-classes]
+/// Pseudo-classes which aren't built-in are kept as a `Custom` variant, which can be registered with
+/// [`register_pseudo_class`](crate::RegisterPseudoClass::register_pseudo_class).
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PseudoClassElement {
     Hover,
     Active,
-    Unsupported,
+    OnlyChild,
+    Empty,
+    /// Matches the nth sibling, starting at `1`, which has the same component used on the selector,
+    /// like `button:nth-of-type(2)`.
+    NthOfType(usize),
+    /// Matches entities which are effectively visible, considering inherited [`Visibility`](bevy::prelude::Visibility).
+    Visible,
+    /// Matches entities which are effectively hidden, considering inherited [`Visibility`](bevy::prelude::Visibility).
+    Hidden,
+    /// Matches entities which has the component registered with
+    /// [`register_disabled_component`](crate::RegisterComponentSelector::register_disabled_component).
+    Disabled,
+    /// Matches entities which doesn't have the component registered with
+    /// [`register_disabled_component`](crate::RegisterComponentSelector::register_disabled_component).
+    Enabled,
+    /// Matches only the entity which owns the [`StyleSheet`](crate::StyleSheet) being applied.
+    ///
+    /// On global style sheets nothing is matched, since those aren't owned by any entity.
+    Root,
+    /// A pseudo-class which isn't built-in, like `:selected`. It only matches entities when registered with
+    /// [`register_pseudo_class`](crate::RegisterPseudoClass::register_pseudo_class).
+    Custom(String),
 }
 
 impl PseudoClassElement {
-    /// Computes a weight value for the pseudo-element.
+    /// Computes a weight value for the pseudo-class, which counts as a class.
     /// This is based on [Specifity](https://developer.mozilla.org/en-US/docs/Web/CSS/Specificity).
-    fn weight(&self) -> u32 {
+    fn weight(&self) -> Specificity {
         match self {
-            PseudoClassElement::Hover => 10,
-            PseudoClassElement::Active => 10,
-            PseudoClassElement::Unsupported => 0,
+            PseudoClassElement::Hover => (0, 1, 0),
+            PseudoClassElement::Active => (0, 1, 0),
+            PseudoClassElement::OnlyChild => (0, 1, 0),
+            PseudoClassElement::Empty => (0, 1, 0),
+            PseudoClassElement::NthOfType(_) => (0, 1, 0),
+            PseudoClassElement::Visible => (0, 1, 0),
+            PseudoClassElement::Hidden => (0, 1, 0),
+            PseudoClassElement::Disabled => (0, 1, 0),
+            PseudoClassElement::Enabled => (0, 1, 0),
+            PseudoClassElement::Root => (0, 1, 0),
+            // Registered weight is used instead, when preparing the style sheet
+            PseudoClassElement::Custom(_) => (0, 1, 0),
         }
     }
 }
@@ -49,7 +179,15 @@ impl std::fmt::Display for PseudoClassElement {
         match self {
             PseudoClassElement::Hover => write!(f, "hover"),
             PseudoClassElement::Active => write!(f, "active"),
-            PseudoClassElement::Unsupported => write!(f, "unsupported"),
+            PseudoClassElement::OnlyChild => write!(f, "only-child"),
+            PseudoClassElement::Empty => write!(f, "empty"),
+            PseudoClassElement::NthOfType(index) => write!(f, "nth-of-type({})", index),
+            PseudoClassElement::Visible => write!(f, "visible"),
+            PseudoClassElement::Hidden => write!(f, "hidden"),
+            PseudoClassElement::Disabled => write!(f, "disabled"),
+            PseudoClassElement::Enabled => write!(f, "enabled"),
+            PseudoClassElement::Root => write!(f, "root"),
+            PseudoClassElement::Custom(name) => write!(f, "{name}"),
         }
     }
 }
@@ -59,73 +197,334 @@ impl<'a> From<&'a CowRcStr<'a>> for PseudoClassElement {
         match value.as_ref() {
             "hover" => PseudoClassElement::Hover,
             "active" => PseudoClassElement::Active,
-            _ => PseudoClassElement::Unsupported,
+            "only-child" => PseudoClassElement::OnlyChild,
+            "empty" => PseudoClassElement::Empty,
+            "visible" => PseudoClassElement::Visible,
+            "hidden" => PseudoClassElement::Hidden,
+            "disabled" => PseudoClassElement::Disabled,
+            "enabled" => PseudoClassElement::Enabled,
+            "root" => PseudoClassElement::Root,
+            name => PseudoClassElement::Custom(name.to_string()),
+        }
+    }
+}
+
+/// Represents a pseudo-element as per (mdn docs)[https://developer.mozilla.org/en-US/docs/Web/CSS/Pseudo-elements]
+///
+/// Pseudo-elements are spawned as child text entities of the selected entity,
+/// check [`PseudoElementNode`](crate::PseudoElementNode) for more.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PseudoElement {
+    /// `::before`, which is spawned as the first child.
+    Before,
+    /// `::after`, which is spawned as the last child.
+    After,
+}
+
+impl std::fmt::Display for PseudoElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PseudoElement::Before => write!(f, "before"),
+            PseudoElement::After => write!(f, "after"),
         }
     }
 }
 
+/// A single media feature condition, like `(max-width: 800px)`.
+///
+/// Values are in logical pixels and are compared against primary window size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MediaFeature {
+    /// `(min-width: 800px)`
+    MinWidth(f32),
+    /// `(max-width: 800px)`
+    MaxWidth(f32),
+    /// `(min-height: 600px)`
+    MinHeight(f32),
+    /// `(max-height: 600px)`
+    MaxHeight(f32),
+}
+
+impl MediaFeature {
+    /// Checks if the given window size satisfies this condition.
+    pub fn matches(&self, width: f32, height: f32) -> bool {
+        match *self {
+            MediaFeature::MinWidth(value) => width >= value,
+            MediaFeature::MaxWidth(value) => width <= value,
+            MediaFeature::MinHeight(value) => height >= value,
+            MediaFeature::MaxHeight(value) => height <= value,
+        }
+    }
+}
+
+impl std::fmt::Display for MediaFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaFeature::MinWidth(value) => write!(f, "(min-width: {value}px)"),
+            MediaFeature::MaxWidth(value) => write!(f, "(max-width: {value}px)"),
+            MediaFeature::MinHeight(value) => write!(f, "(min-height: {value}px)"),
+            MediaFeature::MaxHeight(value) => write!(f, "(max-height: {value}px)"),
+        }
+    }
+}
+
+/// A media query parsed from a `@media` rule, like `@media (min-width: 400px) and (max-width: 800px)`.
+///
+/// A query matches when all of its features matches, so an empty query, like `@media screen`, always matches.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MediaQuery(pub(crate) SmallVec<[MediaFeature; 2]>);
+
+impl MediaQuery {
+    /// List of features which must match.
+    pub fn features(&self) -> &[MediaFeature] {
+        &self.0
+    }
+
+    /// Checks if the given window size satisfies all features of this query.
+    pub fn matches(&self, width: f32, height: f32) -> bool {
+        self.0.iter().all(|feature| feature.matches(width, height))
+    }
+}
+
+impl Hash for MediaQuery {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for feature in &self.0 {
+            std::mem::discriminant(feature).hash(state);
+            match *feature {
+                MediaFeature::MinWidth(value)
+                | MediaFeature::MaxWidth(value)
+                | MediaFeature::MinHeight(value)
+                | MediaFeature::MaxHeight(value) => value.to_bits().hash(state),
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MediaQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let features = self
+            .0
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" and ");
+
+        write!(f, "{features}")
+    }
+}
+
+/// Specificity of a [`Selector`], as a `(ids, classes, types)` tuple which is compared lexicographically.
+///
+/// Names count as ids, classes, pseudo-classes and attributes count as classes while components and
+/// pseudo-elements count as types. This is based on [Specifity](https://developer.mozilla.org/en-US/docs/Web/CSS/Specificity).
+pub type Specificity = (u32, u32, u32);
+
 /// A selector parsed from a `css` rule. Each selector has a internal hash used to differentiate between many rules in the same sheet.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "SerializedSelector", into = "SerializedSelector")
+)]
 pub struct Selector {
     hash: u64,
     elements: SmallVec<[SelectorElement; 8]>,
-    pub(crate) weight: u32,
+    // Range of `elements` of each parent tree node, computed once since elements never changes after creation.
+    nodes: SmallVec<[Range<usize>; 8]>,
+    media: Option<MediaQuery>,
+    pub(crate) weight: Specificity,
+}
+
+/// Serialized form of a [`Selector`]. Only the source data is kept, since the internal hash isn't stable
+/// across builds and the parent tree nodes must match the elements, so both are computed again.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedSelector {
+    elements: SmallVec<[SelectorElement; 8]>,
+    media: Option<MediaQuery>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedSelector> for Selector {
+    fn from(SerializedSelector { elements, media }: SerializedSelector) -> Self {
+        let selector = Selector::new(elements);
+        match media {
+            Some(media) => selector.with_media(media),
+            None => selector,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Selector> for SerializedSelector {
+    fn from(
+        Selector {
+            elements, media, ..
+        }: Selector,
+    ) -> Self {
+        Self { elements, media }
+    }
+}
+
+impl Default for Selector {
+    fn default() -> Self {
+        Self::new(SmallVec::new())
+    }
 }
 
 impl Selector {
     /// Creates a new selector for the given elements.
     pub fn new(elements: SmallVec<[SelectorElement; 8]>) -> Self {
-        let hasher = AHasher::default();
-
-        let hasher = elements.iter().fold(hasher, |mut hasher, el| {
-            el.hash(&mut hasher);
-            hasher
-        });
-
-        let hash = hasher.finish();
-        let weight = Self::weight(&elements);
+        let hash = Self::compute_hash(&elements, None);
+        let weight = Self::weight(&elements, |_| None);
+        let nodes = Self::split_nodes(&elements);
 
         Self {
             elements,
             hash,
+            nodes,
+            media: None,
             weight,
         }
     }
 
+    /// Parses a selector from the given string, like `#menu .item:hover`.
+    ///
+    /// This uses the same rules as selectors on style sheet rules, so it can be used to build selectors in code.
+    pub fn parse(content: &str) -> Result<Self, EcssError> {
+        StyleSheetParser::parse_selector(content)
+    }
+
+    /// Restricts this selector to only be applied when the given [`MediaQuery`] matches.
+    ///
+    /// If this selector already has a media query, like on nested `@media` rules, both queries must match.
+    /// The given query is considered the outer one, so it's features are placed first.
+    pub fn with_media(mut self, mut media: MediaQuery) -> Self {
+        if let Some(inner) = self.media.take() {
+            media.0.extend(inner.0);
+        }
+
+        self.hash = Self::compute_hash(&self.elements, Some(&media));
+        self.media = Some(media);
+        self
+    }
+
+    /// The [`PseudoElement`] selected by this selector, if any.
+    pub fn pseudo_element(&self) -> Option<PseudoElement> {
+        match self.elements.last() {
+            Some(SelectorElement::PseudoElement(pseudo_element)) => Some(*pseudo_element),
+            _ => None,
+        }
+    }
+
+    /// The [`MediaQuery`] which must match for this selector to be applied, if any.
+    pub fn media(&self) -> Option<&MediaQuery> {
+        self.media.as_ref()
+    }
+
+    /// Checks if the given entity is matched by this selector, using the same rules used when applying
+    /// style sheets, but without being scoped to a [`StyleSheet`](crate::StyleSheet) root.
+    ///
+    /// Descendant elements, like `.menu .item`, are matched against the entity ancestors.
+    /// The [`MediaQuery`] of this selector, if any, is ignored and selectors ending with a pseudo-element,
+    /// like `.item::before`, matches the host entity. Since there is no root, `:root` matches any entity
+    /// which has a [`StyleSheet`](crate::StyleSheet).
+    pub fn matches_entity(&self, world: &World, entity: Entity) -> bool {
+        crate::system::matches_entity(world, entity, self)
+    }
+
+    fn compute_hash(elements: &[SelectorElement], media: Option<&MediaQuery>) -> u64 {
+        let mut hasher = AHasher::default();
+
+        elements.iter().for_each(|el| el.hash(&mut hasher));
+        media.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
     /// Builds a selector tree for this selector.
     /// Each node in the tree is composed of many elements, also each node is parent of the next one.
     pub fn get_parent_tree(&self) -> SmallVec<[SmallVec<[&SelectorElement; 8]>; 8]> {
-        let mut tree = SmallVec::new();
-        let mut current_level = SmallVec::new();
-        for element in &self.elements {
-            match element {
-                SelectorElement::Child => {
-                    tree.push(current_level);
-                    current_level = SmallVec::new();
-                }
-                _ => current_level.push(element),
+        self.parent_tree()
+            .map(|node| node.iter().collect())
+            .collect()
+    }
+
+    /// Iterates over the selector tree nodes, without allocating.
+    /// Each node is the same as returned by [`Selector::get_parent_tree`].
+    pub(crate) fn parent_tree(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &[SelectorElement]> + ExactSizeIterator {
+        self.nodes.iter().map(|range| &self.elements[range.clone()])
+    }
+
+    /// Checks if the selector tree node at the given index must be a direct child of the previous node,
+    /// like `.item` on `.menu > .item`.
+    pub(crate) fn is_direct_child(&self, node: usize) -> bool {
+        self.nodes.get(node).is_some_and(|range| {
+            range.start > 0 && self.elements[range.start - 1] == SelectorElement::DirectChild
+        })
+    }
+
+    /// Splits the given elements on each [`SelectorElement::Child`] or [`SelectorElement::DirectChild`],
+    /// returning the range of each node.
+    fn split_nodes(elements: &[SelectorElement]) -> SmallVec<[Range<usize>; 8]> {
+        let mut nodes = SmallVec::new();
+        let mut start = 0;
+        for (i, element) in elements.iter().enumerate() {
+            if let SelectorElement::Child | SelectorElement::DirectChild = element {
+                nodes.push(start..i);
+                start = i + 1;
             }
         }
-        tree.push(current_level);
+        nodes.push(start..elements.len());
+
+        nodes
+    }
+
+    /// The [`Specificity`] of this selector, used on precedence order when applying styles.
+    pub fn specificity(&self) -> Specificity {
+        self.weight
+    }
 
-        tree
+    /// Computes the [`Specificity`] of this selector again, using the given weight of each
+    /// [`PseudoClassElement::Custom`] element, when there is one.
+    pub(crate) fn with_pseudo_class_weights(
+        mut self,
+        custom_weight: impl Fn(&str) -> Option<Specificity>,
+    ) -> Self {
+        self.weight = Self::weight(&self.elements, custom_weight);
+        self
     }
 
-    /// Computes a weight value for this selector, to be used on precedence order when applying styles.
+    /// Computes the [`Specificity`] of this selector, to be used on precedence order when applying styles.
     ///
     /// This is based on [Specifity](https://developer.mozilla.org/en-US/docs/Web/CSS/Specificity).
-    fn weight(elements: &SmallVec<[SelectorElement; 8]>) -> u32 {
-        elements.iter().fold(0, |acc, element| {
-            let element_weight = match element {
-                SelectorElement::Name(_) => 100,
-                SelectorElement::Component(_) => 1,
-                SelectorElement::Class(_) => 10,
-                SelectorElement::Child => 0,
-                SelectorElement::PseudoClass(pseudo_class) => pseudo_class.weight(),
-                SelectorElement::Any => 0,
-            };
-            acc + element_weight
-        })
+    fn weight(
+        elements: &SmallVec<[SelectorElement; 8]>,
+        custom_weight: impl Fn(&str) -> Option<Specificity>,
+    ) -> Specificity {
+        elements
+            .iter()
+            .fold((0, 0, 0), |(ids, classes, types), element| {
+                let (i, c, t) = match element {
+                    SelectorElement::Name(_) => (1, 0, 0),
+                    SelectorElement::Component(_) => (0, 0, 1),
+                    SelectorElement::Class(_) => (0, 1, 0),
+                    SelectorElement::Child | SelectorElement::DirectChild => (0, 0, 0),
+                    SelectorElement::PseudoClass(PseudoClassElement::Custom(name)) => {
+                        custom_weight(name).unwrap_or((0, 1, 0))
+                    }
+                    SelectorElement::PseudoClass(pseudo_class) => pseudo_class.weight(),
+                    SelectorElement::Any => (0, 0, 0),
+                    SelectorElement::Attribute { .. } => (0, 1, 0),
+                    SelectorElement::PseudoElement(_) => (0, 0, 1),
+                };
+                (ids + i, classes + c, types + t)
+            })
     }
 }
 
@@ -145,11 +544,19 @@ impl std::fmt::Display for Selector {
                     result.push_str(c);
                 }
                 SelectorElement::Child => result.push(' '),
+                SelectorElement::DirectChild => result.push_str(" > "),
                 SelectorElement::PseudoClass(c) => {
                     result.push(':');
                     result.push_str(&c.to_string());
                 }
                 SelectorElement::Any => result.push('*'),
+                SelectorElement::PseudoElement(p) => {
+                    result.push_str("::");
+                    result.push_str(&p.to_string());
+                }
+                SelectorElement::Attribute { name, op, value } => {
+                    result.push_str(&format!("[{name}{op}{value}]"));
+                }
             }
         }
 
@@ -214,3 +621,57 @@ impl<'i> From<Vec<CowRcStr<'i>>> for Selector {
         Self::new(elements)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memoized_parent_tree() {
+        for content in [
+            "button",
+            "#menu .item:hover",
+            "window .border .item::before",
+            ".a .b .c .d .e .f .g .h .i .j",
+            "*",
+        ] {
+            let selector = Selector::parse(content).unwrap();
+
+            // Same algorithm used before memoizing the tree
+            let mut expected: SmallVec<[SmallVec<[&SelectorElement; 8]>; 8]> = SmallVec::new();
+            let mut current_level = SmallVec::new();
+            for element in &selector.elements {
+                match element {
+                    SelectorElement::Child => {
+                        expected.push(current_level);
+                        current_level = SmallVec::new();
+                    }
+                    _ => current_level.push(element),
+                }
+            }
+            expected.push(current_level);
+
+            assert_eq!(selector.get_parent_tree(), expected, "{content}");
+            assert_eq!(selector.clone().get_parent_tree(), expected, "{content}");
+        }
+
+        assert_eq!(Selector::default().get_parent_tree().len(), 1);
+    }
+
+    #[test]
+    fn compare_specificity() {
+        let specificity = |content| Selector::parse(content).unwrap().specificity();
+
+        assert_eq!(specificity("#id"), (1, 0, 0));
+        assert_eq!(specificity(".class"), (0, 1, 0));
+        assert_eq!(specificity("component"), (0, 0, 1));
+        assert_eq!(specificity("button.item:hover::before"), (0, 2, 2));
+
+        assert!(specificity("#id") > specificity(".class"));
+        assert!(specificity(".class") > specificity("component"));
+        assert!(
+            specificity("#id") > specificity(".a .b .c .d component"),
+            "Many classes shouldn't outweigh an id"
+        );
+    }
+}
@@ -1,17 +1,24 @@
 use std::hash::{Hash, Hasher};
 
 use bevy::{
-    asset::{io::Reader, AssetLoader, AsyncReadExt},
-    prelude::Asset,
+    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt, LoadContext},
+    log::{debug, error, warn},
+    prelude::{Asset, Handle},
     reflect::TypePath,
-    utils::{AHasher, HashMap},
+    text::Font,
+    utils::{AHasher, BoxedFuture, HashMap, HashSet},
 };
 use smallvec::SmallVec;
 use thiserror::Error;
 
-use crate::{parser::StyleSheetParser, property::PropertyValues, selector::Selector};
+use crate::{
+    parser::StyleSheetParser,
+    property::{PropertyToken, PropertyValues},
+    selector::Selector,
+};
 
 #[derive(Debug, TypePath, Asset)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A cascading style sheet (`css`) asset file.
 ///
 /// _Note_: This asset only store intermediate data, like rules and properties.
@@ -21,6 +28,7 @@ pub struct StyleSheetAsset {
     path: String,
     hash: u64,
     rules: SmallVec<[StyleRule; 8]>,
+    font_faces: Vec<FontFace>,
 }
 
 impl StyleSheetAsset {
@@ -33,26 +41,133 @@ impl StyleSheetAsset {
         content.hash(&mut hasher);
         let hash = hasher.finish();
 
+        let (imports, rules, font_faces) = StyleSheetParser::parse_with_imports(content);
+        if !imports.is_empty() {
+            debug!(
+                "Ignoring imports {:?}, since there is no loader to resolve them",
+                imports
+            );
+        }
+
         Self {
             path: path.to_string(),
             hash,
-            rules: StyleSheetParser::parse(content),
+            rules,
+            font_faces,
         }
     }
 
     /// Returns the [`PropertyValues`] on the given [`Selector`] with the given name.
     pub fn get_properties(&self, selector: &Selector, name: &str) -> Option<&PropertyValues> {
-        self.rules
-            .iter()
-            .find(|&rule| &rule.selector == selector)
+        self.rule_for(selector)
             .and_then(|rule| rule.properties.get(name))
     }
 
+    /// Returns the first [`StyleRule`] with the given [`Selector`], if any.
+    pub fn rule_for(&self, selector: &Selector) -> Option<&StyleRule> {
+        self.rules.iter().find(|&rule| &rule.selector == selector)
+    }
+
     /// Iterates over all existing rules
     pub fn iter(&self) -> impl Iterator<Item = &StyleRule> {
         self.rules.iter()
     }
 
+    /// All existing rules, in the same order as on the source file.
+    pub fn rules(&self) -> &[StyleRule] {
+        &self.rules
+    }
+
+    /// Number of existing rules.
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// All `@font-face` rules, including imported ones, in the same order as on the source file.
+    pub fn font_faces(&self) -> &[FontFace] {
+        &self.font_faces
+    }
+
+    /// Compares this sheet against `other` and returns which rules were added, removed or modified.
+    ///
+    /// Rules are matched by [`Selector`], using the first rule of each selector, just like [`rule_for`](Self::rule_for).
+    /// Removed and modified rules are reported in the same order as on this sheet, followed by added rules in the same
+    /// order as on `other`. Properties of a modified rule are sorted by name.
+    pub fn diff(&self, other: &StyleSheetAsset) -> Vec<RuleDiff> {
+        let mut diffs = Vec::new();
+        let mut seen = HashSet::new();
+
+        for rule in &self.rules {
+            if !seen.insert(&rule.selector) {
+                continue;
+            }
+
+            let Some(other_rule) = other.rule_for(&rule.selector) else {
+                diffs.push(RuleDiff::Removed(rule.selector.clone()));
+                continue;
+            };
+
+            let mut properties = rule
+                .properties
+                .iter()
+                .filter_map(|(name, values)| match other_rule.properties.get(name) {
+                    None => Some(PropertyDiff::Removed(name.clone())),
+                    Some(other_values) if other_values != values => {
+                        Some(PropertyDiff::Changed(name.clone()))
+                    }
+                    _ => None,
+                })
+                .chain(
+                    other_rule
+                        .properties
+                        .keys()
+                        .filter(|name| !rule.properties.contains_key(*name))
+                        .map(|name| PropertyDiff::Added(name.clone())),
+                )
+                .collect::<Vec<_>>();
+
+            if !properties.is_empty() {
+                properties.sort_by(|a, b| a.name().cmp(b.name()));
+                diffs.push(RuleDiff::Modified {
+                    selector: rule.selector.clone(),
+                    properties,
+                });
+            }
+        }
+
+        for rule in &other.rules {
+            if seen.insert(&rule.selector) {
+                diffs.push(RuleDiff::Added(rule.selector.clone()));
+            }
+        }
+
+        diffs
+    }
+
+    /// Checks if any rule declares the property `name`.
+    pub fn contains_property(&self, name: &str) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.properties.contains_key(name))
+    }
+
+    /// Checks if any property uses viewport units, like `50vw` or `10vmin`, which depends on the window size.
+    pub fn uses_viewport_units(&self) -> bool {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.properties.values())
+            .flat_map(|values| values.iter())
+            .any(|token| {
+                matches!(
+                    token,
+                    PropertyToken::VMin(_)
+                        | PropertyToken::VMax(_)
+                        | PropertyToken::Vh(_)
+                        | PropertyToken::Vw(_)
+                )
+            })
+    }
+
     /// Internal hash computed from content and used for equality and ordering comparison
     pub fn hash(&self) -> u64 {
         self.hash
@@ -70,6 +185,7 @@ impl StyleSheetAsset {
 /// Note that this struct holds intermediate data, the final value is parsed by [`Property`](crate::Property) on
 /// the first time it's [`system`](crate::Property::apply_system) is invoked.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleRule {
     /// Selector used to match entities to apply properties.
     pub selector: Selector,
@@ -77,8 +193,70 @@ pub struct StyleRule {
     pub properties: HashMap<String, PropertyValues>,
 }
 
-#[derive(Default)]
-pub(crate) struct StyleSheetLoader;
+/// A single difference between two [`StyleSheetAsset`]s, as returned by [`StyleSheetAsset::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleDiff {
+    /// A rule which exists only on the other sheet.
+    Added(Selector),
+    /// A rule which doesn't exist on the other sheet anymore.
+    Removed(Selector),
+    /// A rule which exists on both sheets, but with different properties.
+    Modified {
+        /// Selector of the modified rule.
+        selector: Selector,
+        /// Properties which differs between both rules.
+        properties: Vec<PropertyDiff>,
+    },
+}
+
+/// A single property difference inside a [`RuleDiff::Modified`] rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyDiff {
+    /// A property which exists only on the other rule.
+    Added(String),
+    /// A property which doesn't exist on the other rule anymore.
+    Removed(String),
+    /// A property which exists on both rules, but with different values.
+    Changed(String),
+}
+
+impl PropertyDiff {
+    /// Name of the property, like `width` or `background-color`.
+    pub fn name(&self) -> &str {
+        match self {
+            PropertyDiff::Added(name)
+            | PropertyDiff::Removed(name)
+            | PropertyDiff::Changed(name) => name,
+        }
+    }
+}
+
+/// A font family declared by a `@font-face` rule, like `@font-face { font-family: "Inter"; src: url("inter.ttf"); }`.
+///
+/// Declared families are added to [`FontRegistry`](crate::FontRegistry) whenever the style sheet is loaded,
+/// so `font-family: "Inter";` can be used without registering the font by hand.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontFace {
+    /// Font family name, like `Inter`.
+    pub family: String,
+    /// Font asset path. When loaded by the asset loader, it's relative to the assets folder instead of the style sheet.
+    pub src: String,
+    /// Font loaded as a dependency of the style sheet, when loaded by the asset loader.
+    /// Otherwise, the font is loaded from [`src`](Self::src) when it's registered.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub font: Option<Handle<Font>>,
+}
+
+pub(crate) struct StyleSheetLoader {
+    extensions: Vec<&'static str>,
+}
+
+impl StyleSheetLoader {
+    pub(crate) fn new(extensions: Vec<&'static str>) -> Self {
+        Self { extensions }
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum StyleSheetLoaderError {
@@ -103,13 +281,335 @@ impl AssetLoader for StyleSheetLoader {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
             let content = std::str::from_utf8(&bytes)?;
-            let stylesheet =
-                StyleSheetAsset::parse(load_context.path().to_str().unwrap_or_default(), content);
-            Ok(stylesheet)
+
+            let path = load_context.asset_path().clone();
+            let mut visited = HashSet::from([path.clone()]);
+            let mut hasher = AHasher::default();
+            let mut font_faces = Vec::new();
+            let rules = load_with_imports(
+                load_context,
+                &path,
+                content,
+                &mut visited,
+                &mut hasher,
+                &mut font_faces,
+            )
+            .await;
+
+            Ok(StyleSheetAsset {
+                path: load_context.path().to_str().unwrap_or_default().to_string(),
+                hash: hasher.finish(),
+                rules,
+                font_faces,
+            })
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["css"]
+        &self.extensions
+    }
+}
+
+/// Parses the given content and loads all `@import` rules recursively, relative to the given path.
+///
+/// Imported rules are placed before the rules of the importing sheet, so they can be overridden by it.
+/// Each sheet is imported only once, so cyclic imports are ignored. Imported contents are also hashed,
+/// so a change on any imported sheet is seen as a change on the importing one.
+///
+/// Fonts of `@font-face` rules are also loaded as dependencies, relative to the sheet which declared them.
+fn load_with_imports<'a>(
+    load_context: &'a mut LoadContext,
+    path: &'a AssetPath<'static>,
+    content: &'a str,
+    visited: &'a mut HashSet<AssetPath<'static>>,
+    hasher: &'a mut AHasher,
+    font_faces: &'a mut Vec<FontFace>,
+) -> BoxedFuture<'a, SmallVec<[StyleRule; 8]>> {
+    Box::pin(async move {
+        content.hash(hasher);
+
+        let (imports, own_rules, own_font_faces) = StyleSheetParser::parse_with_imports(content);
+        let mut rules = SmallVec::new();
+
+        for import in imports {
+            let import_path = match path.resolve_embed(&import) {
+                Ok(import_path) => import_path,
+                Err(err) => {
+                    error!("Invalid @import \"{}\" on {}: {}", import, path, err);
+                    continue;
+                }
+            };
+
+            if !visited.insert(import_path.clone()) {
+                warn!(
+                    "Ignoring @import {} on {}, since it was already imported",
+                    import_path, path
+                );
+                continue;
+            }
+
+            let bytes = match load_context.read_asset_bytes(import_path.clone()).await {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    error!("Failed to @import {} on {}: {}", import_path, path, err);
+                    continue;
+                }
+            };
+
+            let Ok(import_content) = std::str::from_utf8(&bytes) else {
+                error!(
+                    "Failed to @import {} on {}: Invalid file format",
+                    import_path, path
+                );
+                continue;
+            };
+
+            rules.extend(
+                load_with_imports(
+                    load_context,
+                    &import_path,
+                    import_content,
+                    visited,
+                    hasher,
+                    font_faces,
+                )
+                .await,
+            );
+        }
+
+        for mut font_face in own_font_faces {
+            let font_path = match path.resolve_embed(&font_face.src) {
+                Ok(font_path) => font_path,
+                Err(err) => {
+                    error!(
+                        "Invalid @font-face src \"{}\" on {}: {}",
+                        font_face.src, path, err
+                    );
+                    continue;
+                }
+            };
+
+            font_face.src = font_path.to_string();
+            font_face.font = Some(load_context.load(font_path));
+            font_faces.push(font_face);
+        }
+
+        rules.extend(own_rules);
+        rules
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{EcssPlugin, SelectorElement};
+
+    #[test]
+    fn access_rules() {
+        let sheet = StyleSheetAsset::parse("", ".a { width: 10px; } #b .c { height: 5px; }");
+
+        assert_eq!(sheet.rule_count(), 2);
+        assert_eq!(sheet.rules().len(), sheet.iter().count());
+        assert_eq!(sheet.rules()[1].selector.to_string(), "#b .c");
+
+        let selector = Selector::parse("#b .c").unwrap();
+        let rule = sheet.rule_for(&selector).unwrap();
+        assert_eq!(rule.selector, selector);
+        assert!(rule.properties.contains_key("height"));
+
+        assert!(sheet
+            .rule_for(&Selector::parse(".missing").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn diff_added_rule() {
+        let old = StyleSheetAsset::parse("", ".a { width: 10px; }");
+        let new = StyleSheetAsset::parse("", ".a { width: 10px; } #b { height: 5px; }");
+
+        assert_eq!(
+            old.diff(&new),
+            vec![RuleDiff::Added(Selector::parse("#b").unwrap())]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn diff_removed_rule() {
+        let old = StyleSheetAsset::parse("", ".a { width: 10px; } #b { height: 5px; }");
+        let new = StyleSheetAsset::parse("", "#b { height: 5px; }");
+
+        assert_eq!(
+            old.diff(&new),
+            vec![RuleDiff::Removed(Selector::parse(".a").unwrap())]
+        );
+    }
+
+    #[test]
+    fn diff_changed_property() {
+        let old = StyleSheetAsset::parse("", ".a { width: 10px; height: 5px; }");
+        let new = StyleSheetAsset::parse("", ".a { width: 20px; color: red; }");
+
+        assert_eq!(
+            old.diff(&new),
+            vec![RuleDiff::Modified {
+                selector: Selector::parse(".a").unwrap(),
+                properties: vec![
+                    PropertyDiff::Added("color".to_string()),
+                    PropertyDiff::Removed("height".to_string()),
+                    PropertyDiff::Changed("width".to_string()),
+                ],
+            }]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_round_trip() {
+        let sheet = StyleSheetAsset::parse(
+            "sheets/theme.css",
+            r#"
+            @font-face { font-family: "Inter"; src: url("fonts/inter.ttf"); }
+            #menu .item:hover::before { content: "a"; width: calc(100% - 20px); }
+            [health<50] button:nth-of-type(2) { background-color: #ff0000; }
+            @media (max-width: 800px) { .b { margin: 1px 2px; } }
+            "#,
+        );
+
+        let serialized = ron::to_string(&sheet).unwrap();
+        let deserialized = ron::from_str::<StyleSheetAsset>(&serialized).unwrap();
+
+        assert_eq!(deserialized.path(), sheet.path());
+        assert_eq!(deserialized.hash(), sheet.hash());
+        assert_eq!(deserialized.font_faces(), sheet.font_faces());
+        assert_eq!(deserialized.rule_count(), sheet.rule_count());
+        assert!(
+            sheet.diff(&deserialized).is_empty(),
+            "Should have same rules"
+        );
+
+        for (rule, expected) in deserialized.iter().zip(sheet.iter()) {
+            assert_eq!(rule.selector.to_string(), expected.selector.to_string());
+            assert_eq!(rule.selector.specificity(), expected.selector.specificity());
+            assert_eq!(rule.selector.media(), expected.selector.media());
+            assert_eq!(
+                rule.selector.get_parent_tree(),
+                expected.selector.get_parent_tree()
+            );
+        }
+
+        assert!(
+            !serialized.contains("nodes:") && !serialized.contains("weight:"),
+            "Selector internal state shouldn't be serialized"
+        );
+    }
+
+    #[test]
+    fn load_imported_rules() {
+        let dir = std::env::temp_dir().join(format!("bevy_ecss_import_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("theme")).unwrap();
+        std::fs::write(
+            dir.join("main.css"),
+            r#"@import "theme/base.css"; .b { height: 20px; }"#,
+        )
+        .unwrap();
+        // Imports are relative to the importing sheet, and cyclic imports are ignored
+        std::fs::write(
+            dir.join("theme/base.css"),
+            r#"@import "../main.css"; .a { width: 10px; } .b { height: 5px; }"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            EcssPlugin::default(),
+        ));
+
+        let handle: Handle<StyleSheetAsset> = app.world.resource::<AssetServer>().load("main.css");
+
+        for _ in 0..100 {
+            app.update();
+            if app
+                .world
+                .resource::<Assets<StyleSheetAsset>>()
+                .contains(&handle)
+            {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let assets = app.world.resource::<Assets<StyleSheetAsset>>();
+        let sheet = assets.get(&handle).expect("Sheet should be loaded");
+
+        let selector = Selector::new(smallvec::smallvec![SelectorElement::Class("a".to_string())]);
+        assert!(
+            sheet.get_properties(&selector, "width").is_some(),
+            "Should expose imported rules"
+        );
+
+        let heights = sheet
+            .iter()
+            .filter_map(|rule| rule.properties.get("height"))
+            .collect::<Vec<_>>();
+        assert_eq!(heights.len(), 2, "Each sheet should be imported only once");
+        assert_eq!(
+            heights[1].val(),
+            Some(Val::Px(20.0)),
+            "Importing sheet rules should come after imported ones"
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn load_custom_extension() {
+        let dir = std::env::temp_dir().join(format!("bevy_ecss_ext_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.ecss"), ".a { width: 10px; }").unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            EcssPlugin::with_extensions(&["css", "ecss"]),
+        ));
+
+        let handle: Handle<StyleSheetAsset> = app.world.resource::<AssetServer>().load("main.ecss");
+
+        for _ in 0..100 {
+            app.update();
+            if app
+                .world
+                .resource::<Assets<StyleSheetAsset>>()
+                .contains(&handle)
+            {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let assets = app.world.resource::<Assets<StyleSheetAsset>>();
+        let sheet = assets.get(&handle).expect("Sheet should be loaded");
+
+        let selector = Selector::new(smallvec::smallvec![SelectorElement::Class("a".to_string())]);
+        assert_eq!(
+            sheet.get_properties(&selector, "width").unwrap().val(),
+            Some(Val::Px(10.0))
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
     }
 }
@@ -1,38 +1,79 @@
+use std::sync::{Mutex, PoisonError};
+
 use bevy::{
     ecs::{
         component::ComponentTicks,
+        entity::{EntityHashMap, EntityHashSet},
         system::{SystemParam, SystemState},
     },
-    log::{debug, error, trace},
+    hierarchy::{BuildWorldChildren, DespawnRecursiveExt, HierarchyQueryExt},
+    log::{debug, error, trace, warn},
     prelude::{
-        AssetEvent, AssetId, Assets, Changed, Children, Component, Deref, DerefMut, Entity,
-        EventReader, Mut, Name, Query, Res, ResMut, Resource, With, World,
+        Added, AssetEvent, AssetId, AssetServer, Assets, BackgroundColor, BorderColor, Changed,
+        Children, Color, Commands, Component, Deref, DerefMut, DetectChanges, DetectChangesMut,
+        Entity, EventReader, Font, Handle, Has, Local, Mut, Name, Or, Parent, Query, Ref,
+        RemovedComponents, Res, ResMut, Resource, Style, Text, Transform, UiImage, Val, Vec2,
+        Visibility, With, Without, World,
     },
-    ui::{Interaction, Node},
+    text::TextLayoutInfo,
+    ui::{node_bundles::TextBundle, widget::UiImageSize, Interaction, Node, UiScale},
     utils::HashMap,
+    window::{CursorIcon, PrimaryWindow, Window},
 };
 use smallvec::SmallVec;
 
 use crate::{
-    component::{Class, MatchSelectorElement, StyleSheet},
-    property::{SelectedEntities, StyleSheetState, TrackedEntities},
-    selector::{PseudoClassElement, Selector, SelectorElement},
+    component::{
+        Class, CssBoxShadow, CssCalc, CssCursor, CssGradient, DefaultStyleSheet, GlobalStyleSheet,
+        MatchSelectorElement, MaxLines, NoAutoRestyle, ObjectFit, PseudoElementNode,
+        ScrollPosition, StyleSheet, StyleSheetApplied, TextOverflow,
+    },
+    property::{
+        FontRegistry, RegisteredProperties, SelectedEntities, SheetState, StyleQuery,
+        StyleSheetState, TrackedEntities, GLOBAL_ROOT,
+    },
+    selector::{
+        AttributeOperator, PseudoClassElement, PseudoElement, Selector, SelectorElement,
+        Specificity,
+    },
     StyleSheetAsset,
 };
 
 /// Utility trait which helps to deal with dynamic components
 /// Each trait is implemented for a [`SystemState<T>`] with a single `[Component]`
 pub(crate) trait ComponentFilter {
-    /// Query the world and returns only the which has the component.
-    fn filter(&mut self, world: &World) -> SmallVec<[Entity; 8]>;
+    /// Checks if the given entity has the component.
+    fn contains(&self, world: &World, entity: Entity) -> bool;
+
+    /// Collects all entities which has the component.
+    fn entities(&self, world: &World) -> EntityHashSet;
 
     /// Return the change ticks of the component on the given entity.
     fn get_change_ticks(&self, world: &World, entity: Entity) -> Option<ComponentTicks>;
+
+    /// Checks if the component was recently removed from the given entity.
+    fn was_removed(&self, world: &World, entity: Entity) -> bool;
+
+    /// Returns the type name of the component, for debug reasons only.
+    fn component_name(&self) -> &'static str;
 }
 
 impl<'w, 's, T: Component> ComponentFilter for SystemState<Query<'w, 's, Entity, With<T>>> {
-    fn filter(&mut self, world: &World) -> SmallVec<[Entity; 8]> {
-        self.get(world).iter().collect()
+    fn contains(&self, world: &World, entity: Entity) -> bool {
+        world.get_entity(entity).is_some_and(|e| e.contains::<T>())
+    }
+
+    fn entities(&self, world: &World) -> EntityHashSet {
+        let Some(component_id) = world.component_id::<T>() else {
+            return EntityHashSet::default();
+        };
+
+        world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+            .flat_map(|archetype| archetype.entities().iter().map(|e| e.id()))
+            .collect()
     }
 
     fn get_change_ticks(&self, world: &World, entity: Entity) -> Option<ComponentTicks> {
@@ -40,18 +81,118 @@ impl<'w, 's, T: Component> ComponentFilter for SystemState<Query<'w, 's, Entity,
             .get_entity(entity)
             .and_then(|e| e.get_change_ticks::<T>())
     }
+
+    fn was_removed(&self, world: &World, entity: Entity) -> bool {
+        world.removed::<T>().any(|e| e == entity)
+    }
+
+    fn component_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 /// Holds the registered [`ComponentFilter`] using the component name as key.
+/// Also holds the registered [`AttributeSelector`] using the attribute name as key
+/// and the component used by `:disabled` and `:enabled` pseudo-classes.
+/// Custom pseudo-classes are held as [`PseudoClassSelector`] using the pseudo-class name as key.
+/// While [`prepare`] runs, entities of component selectors are cached on a [`ComponentCache`].
 #[derive(Default, Resource, Deref, DerefMut)]
-pub(crate) struct ComponentFilterRegistry(
-    pub HashMap<&'static str, Box<dyn ComponentFilter + Send + Sync>>,
-);
+pub(crate) struct ComponentFilterRegistry {
+    #[deref]
+    pub components: HashMap<&'static str, Box<dyn ComponentFilter + Send + Sync>>,
+    pub attributes: HashMap<&'static str, AttributeSelector>,
+    pub disabled: Option<Box<dyn ComponentFilter + Send + Sync>>,
+    pub pseudo_classes: HashMap<&'static str, PseudoClassSelector>,
+    pub cache: Mutex<Option<ComponentCache>>,
+}
+
+/// Entities which has the component of each component selector, keyed by the component selector name.
+///
+/// Entities are collected on the first time a component selector is used, so repeated selectors on the
+/// same [`prepare`] run reuses them, instead of looking up the component on each entity again.
+#[derive(Default)]
+pub(crate) struct ComponentCache {
+    entities: HashMap<&'static str, EntityHashSet>,
+    /// How many times entities were collected, which is once per used component selector.
+    collected: usize,
+}
+
+impl ComponentCache {
+    /// Returns the entities which has the component of the given selector name, collecting them if needed.
+    fn entities(
+        &mut self,
+        name: &'static str,
+        filter: &(dyn ComponentFilter + Send + Sync),
+        world: &World,
+    ) -> &EntityHashSet {
+        self.entities.entry(name).or_insert_with(|| {
+            self.collected += 1;
+            filter.entities(world)
+        })
+    }
+}
+
+impl ComponentFilterRegistry {
+    /// Starts caching entities of component selectors, until [`take_cache`](Self::take_cache) is called.
+    ///
+    /// The cache must only be used while the world doesn't change, like during a single [`prepare`] run.
+    pub fn enable_cache(&mut self) {
+        *self.cache.get_mut().unwrap_or_else(PoisonError::into_inner) = Some(Default::default());
+    }
+
+    /// Stops caching entities of component selectors, returning the current cache, if any.
+    pub fn take_cache(&mut self) -> Option<ComponentCache> {
+        self.cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Registers the given component filter, returning the type name of the component previously
+    /// registered with the same name, if it was a different one.
+    pub fn insert_component(
+        &mut self,
+        name: &'static str,
+        filter: Box<dyn ComponentFilter + Send + Sync>,
+    ) -> Option<&'static str> {
+        let component_name = filter.component_name();
+
+        self.components
+            .insert(name, filter)
+            .map(|previous| previous.component_name())
+            .filter(|previous| *previous != component_name)
+    }
+}
+
+/// A predicate which checks if an entity attribute matches the given operator and value.
+pub(crate) type AttributePredicate =
+    Box<dyn Fn(&World, Entity, AttributeOperator, &str) -> bool + Send + Sync>;
+
+/// A registered attribute selector.
+pub(crate) struct AttributeSelector {
+    /// Filter entities which has the attribute component and also used to check for changes.
+    pub filter: Box<dyn ComponentFilter + Send + Sync>,
+    /// Checks if the attribute value matches the selector.
+    pub predicate: AttributePredicate,
+}
+
+/// A predicate which checks if an entity matches a custom pseudo-class.
+pub(crate) type PseudoClassPredicate = Box<dyn Fn(&World, Entity) -> bool + Send + Sync>;
+
+/// A registered custom pseudo-class.
+pub(crate) struct PseudoClassSelector {
+    /// Checks if the entity matches the pseudo-class.
+    pub predicate: PseudoClassPredicate,
+    /// Weight used instead of the default one when computing the selector [`Specificity`].
+    pub weight: Specificity,
+}
 
 /// An utility [`SystemParam`] query which is used in [`prepare`] system.
 #[derive(SystemParam)]
 pub(crate) struct CssQueryParam<'w, 's> {
     assets: Res<'w, Assets<StyleSheetAsset>>,
+    default_sheet: Option<Res<'w, DefaultStyleSheet>>,
+    global_sheet: Option<Res<'w, GlobalStyleSheet>>,
     nodes: Query<
         'w,
         's,
@@ -60,8 +201,64 @@ pub(crate) struct CssQueryParam<'w, 's> {
     >,
     names: Query<'w, 's, (Entity, &'static Name)>,
     classes: Query<'w, 's, (Entity, &'static Class)>,
-    children: Query<'w, 's, &'static Children, With<Node>>,
+    // Not filtered by `Node`, so descendants of non-`Node` entities are also traversed.
+    children: Query<'w, 's, &'static Children>,
     any: Query<'w, 's, Entity, With<Node>>,
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    pseudo_elements: Query<'w, 's, (), With<PseudoElementNode>>,
+    // Any entity moved to another parent, which may change the depth of roots.
+    reparented: Query<'w, 's, (), Changed<Parent>>,
+}
+
+/// A [`SystemParam`] which selects entities matching a [`Selector`], using the same rules used when
+/// applying style sheets.
+///
+/// This param has read access to the whole [`World`], so it can't be used along with params which
+/// mutably access the world, like [`ResMut`] or a [`Query`] with `&mut` components.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_ecss::{CssSelect, Selector};
+///
+/// fn inspect(select: CssSelect, q_roots: Query<Entity, With<Node>>) {
+///     let selector = Selector::parse(".item").unwrap();
+///     for root in &q_roots {
+///         info!("{:?} matches {:?}", root, select.select(root, &selector));
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct CssSelect<'w, 's> {
+    world: &'w World,
+    registry: Res<'w, ComponentFilterRegistry>,
+    css_query: CssQueryParam<'w, 's>,
+}
+
+impl CssSelect<'_, '_> {
+    /// Returns the given root entity and its descendants which matches the given [`Selector`].
+    ///
+    /// Selectors ending with a pseudo-element, like `.item::before`, returns the host entities.
+    pub fn select(&self, root: Entity, selector: &Selector) -> Vec<Entity> {
+        select_entities(
+            root,
+            self.world.get::<Children>(root),
+            selector,
+            self.world,
+            &self.css_query,
+            &self.registry,
+            &mut TrackedEntities::default(),
+        )
+        .into_vec()
+    }
+
+    /// Checks if the given entity is matched by the given [`Selector`], regardless of any root.
+    ///
+    /// Check [`Selector::matches_entity`] for more.
+    pub fn matches(&self, entity: Entity, selector: &Selector) -> bool {
+        matches_entity(self.world, entity, selector)
+    }
 }
 
 /// Holds an previous prepared [`CssQueryParam`];
@@ -75,65 +272,329 @@ impl PrepareParams {
 }
 
 /// Exclusive system which selects all entities and prepare the internal state used by [`Property`](crate::Property) systems.
+///
+/// Only roots which had it's [`StyleSheet`] changed are selected again, all other roots are kept untouched.
 pub(crate) fn prepare(world: &mut World) {
     world.resource_scope(|world, mut params: Mut<PrepareParams>| {
         world.resource_scope(|world, mut registry: Mut<ComponentFilterRegistry>| {
             let css_query = params.get(world);
-            let state = prepare_state(world, css_query, &mut registry);
+            let reparented = !css_query.reparented.is_empty();
 
-            if state.has_any_selected_entities() {
+            // Entities doesn't change while selecting, so each component selector is collected once
+            registry.enable_cache();
+            let mut changed_roots = prepare_state(world, css_query, &registry);
+            registry.take_cache();
+
+            sync_pseudo_elements(world, &mut changed_roots);
+
+            if !changed_roots.is_empty() {
+                let roots_info = changed_roots
+                    .iter()
+                    .map(|(root, _)| {
+                        let revert = world
+                            .get::<StyleSheet>(*root)
+                            .is_some_and(StyleSheet::reverts_on_removal);
+                        (hierarchy_depth(world, *root), revert)
+                    })
+                    .collect::<Vec<_>>();
+                mark_applied_roots(world, &changed_roots);
                 let mut state_res = world
                     .get_resource_mut::<StyleSheetState>()
                     .expect("Should be added by plugin");
 
-                *state_res = state;
+                for ((root, sheets), (depth, revert)) in changed_roots.into_iter().zip(roots_info) {
+                    state_res.insert_root(root, depth, revert, sheets);
+                }
+            }
+
+            if reparented {
+                update_root_depths(world);
             }
         });
     });
 }
 
-/// Prepare state to be used by [`Property`](crate::Property) systems
+/// Recomputes the hierarchy depth of all roots, since any of them or its ancestors may be moved to another parent.
+fn update_root_depths(world: &mut World) {
+    let depths = world
+        .resource::<StyleSheetState>()
+        .keys()
+        .filter(|root| **root != GLOBAL_ROOT)
+        .map(|root| (*root, hierarchy_depth(world, *root)))
+        .collect::<Vec<_>>();
+
+    let mut state_res = world.resource_mut::<StyleSheetState>();
+    for (root, depth) in depths {
+        state_res.set_depth(root, depth);
+    }
+}
+
+/// Inserts [`StyleSheetApplied`] on each changed root which has all style sheets loaded, removing it otherwise.
+fn mark_applied_roots(world: &mut World, changed_roots: &[(Entity, SmallVec<[SheetState; 1]>)]) {
+    for (root, _) in changed_roots {
+        let Some(sheet) = world.get::<StyleSheet>(*root) else {
+            continue;
+        };
+
+        let assets = world.resource::<Assets<StyleSheetAsset>>();
+        let loaded = sheet.handles().iter().all(|handle| assets.contains(handle));
+
+        let mut entity = world.entity_mut(*root);
+        if !loaded {
+            entity.remove::<StyleSheetApplied>();
+        } else if !entity.contains::<StyleSheetApplied>() {
+            entity.insert(StyleSheetApplied);
+        }
+    }
+}
+
+/// Prepare state of each changed root to be used by [`Property`](crate::Property) systems
 pub(crate) fn prepare_state(
     world: &World,
     css_query: CssQueryParam,
-    registry: &mut ComponentFilterRegistry,
-) -> StyleSheetState {
-    let mut state = StyleSheetState::default();
+    registry: &ComponentFilterRegistry,
+) -> Vec<(Entity, SmallVec<[SheetState; 1]>)> {
+    let mut changed_roots = Vec::new();
+    let window_size = css_query
+        .windows
+        .get_single()
+        .ok()
+        .map(|window| (window.width(), window.height()));
 
     for (root, maybe_children, sheet_handle) in &css_query.nodes {
-        for id in sheet_handle.handles().iter().map(|h| h.id()) {
+        let mut root_state = SmallVec::new();
+
+        // Default style sheet goes first, so it has the lowest priority
+        let default_sheet = css_query
+            .default_sheet
+            .as_deref()
+            .map(DefaultStyleSheet::handle)
+            .filter(|handle| !sheet_handle.handles().contains(handle));
+
+        for id in default_sheet
+            .into_iter()
+            .chain(sheet_handle.handles())
+            .map(|h| h.id())
+        {
             if let Some(sheet) = css_query.assets.get(id) {
-                let mut tracked_entities = TrackedEntities::default();
-                let mut selected_entities = SelectedEntities::default();
-                debug!("Applying style {}", sheet.path());
-
-                for rule in sheet.iter() {
-                    let entities = select_entities(
-                        root,
-                        maybe_children,
-                        &rule.selector,
+                root_state.push(prepare_sheet(
+                    id,
+                    sheet,
+                    window_size,
+                    registry,
+                    |selector, tracked| {
+                        select_entities(
+                            root,
+                            maybe_children,
+                            selector,
+                            world,
+                            &css_query,
+                            registry,
+                            tracked,
+                        )
+                    },
+                ));
+            }
+        }
+
+        changed_roots.push((root, root_state));
+    }
+
+    if let Some(global) = css_query.global_sheet.as_ref().filter(|g| g.is_changed()) {
+        if let Some(sheet) = css_query.assets.get(global.handle()) {
+            // Global style sheets aren't bound to any hierarchy, so all nodes may be selected
+            let entity_tree = css_query
+                .any
+                .iter()
+                .filter(|e| !css_query.pseudo_elements.contains(*e))
+                .collect::<SmallVec<[_; 8]>>();
+
+            let sheet_state = prepare_sheet(
+                global.id(),
+                sheet,
+                window_size,
+                registry,
+                |selector, tracked| {
+                    if selector.pseudo_element().is_some() {
+                        trace!("Skipping rule ({}) on global style sheet", selector);
+                        return SmallVec::new();
+                    }
+
+                    select_entities_in_tree(
+                        None,
+                        entity_tree.clone(),
+                        selector,
                         world,
                         &css_query,
                         registry,
-                        &mut tracked_entities,
-                    );
+                        tracked,
+                    )
+                },
+            );
+            changed_roots.push((GLOBAL_ROOT, smallvec::smallvec![sheet_state]));
+        }
+    }
+
+    changed_roots
+}
+
+/// Selects the entities matched by each rule of the given sheet, using the given `select` function.
+///
+/// Rules which has a `@media` query not matching the given window size are skipped.
+fn prepare_sheet(
+    id: AssetId<StyleSheetAsset>,
+    sheet: &StyleSheetAsset,
+    window_size: Option<(f32, f32)>,
+    registry: &ComponentFilterRegistry,
+    mut select: impl FnMut(&Selector, &mut TrackedEntities) -> SmallVec<[Entity; 8]>,
+) -> SheetState {
+    let mut tracked_entities = TrackedEntities::default();
+    let mut selected_entities = SelectedEntities::default();
+    debug!("Applying style {}", sheet.path());
+
+    for rule in sheet.iter() {
+        if let Some(media) = rule.selector.media() {
+            if !window_size.is_some_and(|(w, h)| media.matches(w, h)) {
+                trace!(
+                    "Skipping rule ({}), since @media {} doesn't match",
+                    rule.selector,
+                    media
+                );
+                continue;
+            }
+        }
+
+        let entities = select(&rule.selector, &mut tracked_entities);
+
+        trace!(
+            "Applying rule ({}) on {} entities",
+            rule.selector.to_string(),
+            entities.len()
+        );
+
+        let selector = rule.selector.clone().with_pseudo_class_weights(|name| {
+            registry
+                .pseudo_classes
+                .get(name)
+                .map(|pseudo_class| pseudo_class.weight)
+        });
+
+        selected_entities.push((selector, entities));
+    }
+
+    // Stable sort, so rules with equal specificity are kept on source order
+    selected_entities.sort_by_key(|(a, _)| a.weight);
+    (id, tracked_entities, selected_entities)
+}
+
+/// Spawns, reuses or despawns [`PseudoElementNode`] entities of each changed root, following the
+/// lifecycle described on [`PseudoElementNode`].
+///
+/// Selectors ending with a pseudo-element selects the host entities, which are replaced here by
+/// their pseudo-element entities, so properties are applied on them instead.
+fn sync_pseudo_elements(
+    world: &mut World,
+    changed_roots: &mut [(Entity, SmallVec<[SheetState; 1]>)],
+) {
+    for (root, sheets) in changed_roots.iter_mut() {
+        let mut alive = SmallVec::<[Entity; 8]>::new();
 
-                    trace!(
-                        "Applying rule ({}) on {} entities",
-                        rule.selector.to_string(),
-                        entities.len()
-                    );
+        for (id, _, selected) in sheets.iter_mut() {
+            for (selector, entities) in selected.iter_mut() {
+                let Some(pseudo_element) = selector.pseudo_element() else {
+                    continue;
+                };
 
-                    selected_entities.push((rule.selector.clone(), entities));
+                let has_content = world
+                    .resource::<Assets<StyleSheetAsset>>()
+                    .get(*id)
+                    .is_some_and(|sheet| sheet.get_properties(selector, "content").is_some());
+
+                // Just like CSS, pseudo-elements without content are not rendered
+                if !has_content {
+                    entities.clear();
+                    continue;
                 }
 
-                selected_entities.sort_by(|(a, _), (b, _)| a.weight.cmp(&b.weight));
-                state.push((id, tracked_entities, selected_entities));
+                for entity in entities.iter_mut() {
+                    *entity = get_or_spawn_pseudo_element(world, *entity, pseudo_element);
+                    alive.push(*entity);
+                }
             }
         }
+
+        let stale = world
+            .query_filtered::<(Entity, &Parent), With<PseudoElementNode>>()
+            .iter(world)
+            .filter(|(entity, parent)| {
+                !alive.contains(entity) && is_descendant_of(world, parent.get(), *root)
+            })
+            .map(|(entity, _)| entity)
+            .collect::<SmallVec<[_; 8]>>();
+
+        for entity in stale {
+            debug!("Despawning pseudo-element {:?}", entity);
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Returns the [`PseudoElementNode`] child entity of the given host, spawning it if needed.
+fn get_or_spawn_pseudo_element(
+    world: &mut World,
+    host: Entity,
+    pseudo_element: PseudoElement,
+) -> Entity {
+    let existing = world.get::<Children>(host).and_then(|children| {
+        children.iter().copied().find(|child| {
+            world
+                .get::<PseudoElementNode>(*child)
+                .is_some_and(|node| node.0 == pseudo_element)
+        })
+    });
+
+    if let Some(entity) = existing {
+        return entity;
+    }
+
+    let entity = world
+        .spawn((TextBundle::default(), PseudoElementNode(pseudo_element)))
+        .id();
+
+    let mut host = world.entity_mut(host);
+    match pseudo_element {
+        PseudoElement::Before => host.insert_children(0, &[entity]),
+        PseudoElement::After => host.push_children(&[entity]),
+    };
+
+    entity
+}
+
+/// Counts how many ancestors the given entity has.
+fn hierarchy_depth(world: &World, entity: Entity) -> usize {
+    let mut depth = 0;
+    let mut current = world.get::<Parent>(entity).map(Parent::get);
+
+    while let Some(entity) = current {
+        depth += 1;
+        current = world.get::<Parent>(entity).map(Parent::get);
+    }
+
+    depth
+}
+
+/// Checks if the given entity is the root or any descendant of it.
+fn is_descendant_of(world: &World, entity: Entity, root: Entity) -> bool {
+    let mut current = Some(entity);
+
+    while let Some(entity) = current {
+        if entity == root {
+            return true;
+        }
+        current = world.get::<Parent>(entity).map(Parent::get);
     }
 
-    state
+    false
 }
 
 /// Select all entities using the given [`Selector`](crate::Selector).
@@ -145,32 +606,57 @@ fn select_entities(
     selector: &Selector,
     world: &World,
     css_query: &CssQueryParam,
-    registry: &mut ComponentFilterRegistry,
+    registry: &ComponentFilterRegistry,
     tracked_entities: &mut TrackedEntities,
 ) -> SmallVec<[Entity; 8]> {
-    let mut parent_tree = selector.get_parent_tree();
-
-    if parent_tree.is_empty() {
-        return SmallVec::new();
-    }
-
     // Build an entity tree with all entities that may be selected.
     // This tree is composed of the entity root and all descendants entities.
-    let mut entity_tree = std::iter::once(root)
+    // Pseudo-element entities are never matched by regular selectors.
+    let entity_tree = std::iter::once(root)
         .chain(
             maybe_children
                 .map(|children| get_children_recursively(children, &css_query.children))
                 .unwrap_or_default(),
         )
+        .filter(|e| !css_query.pseudo_elements.contains(*e))
         .collect::<SmallVec<_>>();
 
-    loop {
-        // TODO: Rework this to use a index to avoid recreating parent_tree every time the systems runs.
-        // This is has little to no impact on performance, since this system doesn't runs often.
-        let node = parent_tree.remove(0);
+    select_entities_in_tree(
+        Some(root),
+        entity_tree,
+        selector,
+        world,
+        css_query,
+        registry,
+        tracked_entities,
+    )
+}
+
+/// Select all entities using the given [`Selector`](crate::Selector), starting from the given entity tree.
+///
+/// The first selector node is matched against all entities on the tree, while the next ones are matched
+/// against the descendants, or only the children when preceded by `>`, of the previously matched entities. The given `root` is the entity which
+/// owns the style sheet, if any, and is the only one matched by [`PseudoClassElement::Root`].
+fn select_entities_in_tree(
+    root: Option<Entity>,
+    mut entity_tree: SmallVec<[Entity; 8]>,
+    selector: &Selector,
+    world: &World,
+    css_query: &CssQueryParam,
+    registry: &ComponentFilterRegistry,
+    tracked_entities: &mut TrackedEntities,
+) -> SmallVec<[Entity; 8]> {
+    let mut parent_tree = selector.parent_tree();
+
+    let Some(mut node) = parent_tree.next() else {
+        return SmallVec::new();
+    };
+    let mut index = 0;
 
+    loop {
         let entities = select_entities_node(
             node,
+            root,
             world,
             css_query,
             registry,
@@ -178,16 +664,140 @@ fn select_entities(
             tracked_entities,
         );
 
-        if parent_tree.is_empty() {
+        let Some(next) = parent_tree.next() else {
             break entities;
+        };
+
+        node = next;
+        index += 1;
+        let children = entities
+            .into_iter()
+            .filter_map(|e| css_query.children.get(e).ok());
+        let descendants: SmallVec<[Entity; 8]> = if selector.is_direct_child(index) {
+            children
+                .flat_map(|children| children.iter().copied())
+                .collect()
         } else {
-            entity_tree = entities
-                .into_iter()
-                .filter_map(|e| css_query.children.get(e).ok())
+            children
                 .flat_map(|children| get_children_recursively(children, &css_query.children))
-                .collect();
+                .collect()
+        };
+        // Like on the top-level tree, pseudo-element entities are never matched by regular selectors.
+        entity_tree = descendants
+            .into_iter()
+            .filter(|e| !css_query.pseudo_elements.contains(*e))
+            .collect();
+    }
+}
+
+/// Checks if a single entity is matched by the given [`Selector`](crate::Selector).
+///
+/// The last selector node is matched against the entity, while the previous ones are matched against
+/// its ancestors, from the nearest one to the farthest one, or only against the parent when followed by `>`.
+pub(crate) fn matches_entity(world: &World, entity: Entity, selector: &Selector) -> bool {
+    // Pseudo-element entities are never matched by regular selectors.
+    if world.get_entity(entity).is_none() || world.get::<PseudoElementNode>(entity).is_some() {
+        return false;
+    }
+
+    let default_registry;
+    let registry = match world.get_resource::<ComponentFilterRegistry>() {
+        Some(registry) => registry,
+        None => {
+            default_registry = ComponentFilterRegistry::default();
+            &default_registry
         }
+    };
+
+    let mut nodes = selector.parent_tree().enumerate().rev();
+
+    let Some((mut index, node)) = nodes.next() else {
+        return false;
+    };
+
+    if !matches_entity_node(node, world, registry, entity) {
+        return false;
     }
+
+    let mut current = entity;
+    nodes.all(|(node_index, node)| {
+        // Whether the previously matched node must be a direct child of this one
+        let direct_child = selector.is_direct_child(index);
+        index = node_index;
+
+        loop {
+            let Some(parent) = world.get::<Parent>(current).map(Parent::get) else {
+                break false;
+            };
+
+            current = parent;
+
+            if matches_entity_node(node, world, registry, current) {
+                break true;
+            } else if direct_child {
+                break false;
+            }
+        }
+    })
+}
+
+/// Checks if a single entity matches all elements of the given selector node.
+fn matches_entity_node(
+    node: &[SelectorElement],
+    world: &World,
+    registry: &ComponentFilterRegistry,
+    entity: Entity,
+) -> bool {
+    let component = node.iter().find_map(|element| match element {
+        SelectorElement::Component(component) => Some(component.as_str()),
+        _ => None,
+    });
+
+    let entities = || SmallVec::from_elem(entity, 1);
+
+    node.iter().all(|element| {
+        let (filtered, _) = match element {
+            SelectorElement::PseudoClass(PseudoClassElement::NthOfType(index)) => {
+                match component.map(|component| registry.get(component)) {
+                    Some(Some(filter)) => get_entities_with_pseudo_class_nth_of_type(
+                        world,
+                        *index,
+                        Some(filter.as_ref()),
+                        entities(),
+                    ),
+                    Some(None) => Default::default(),
+                    None => {
+                        get_entities_with_pseudo_class_nth_of_type(world, *index, None, entities())
+                    }
+                }
+            }
+            SelectorElement::Name(name) => {
+                return world
+                    .get::<Name>(entity)
+                    .is_some_and(|rhs| MatchSelectorElement::matches(rhs, name))
+            }
+            SelectorElement::Class(class) => {
+                return world
+                    .get::<Class>(entity)
+                    .is_some_and(|rhs| MatchSelectorElement::matches(rhs, class))
+            }
+            SelectorElement::Component(component) => {
+                get_entities_with_component(component.as_str(), world, registry, entities())
+            }
+            SelectorElement::PseudoClass(pseudo_class) => {
+                get_entities_with_pseudo_class(world, registry, pseudo_class, entities())
+            }
+            SelectorElement::Any => return world.get::<Node>(entity).is_some(),
+            SelectorElement::Attribute { name, op, value } => {
+                get_entities_with_attribute(name.as_str(), *op, value, world, registry, entities())
+            }
+            SelectorElement::PseudoElement(_) => return true,
+            // All child elements are filtered by [`get_parent_tree`](Selector::get_parent_tree)
+            SelectorElement::Child | SelectorElement::DirectChild => unreachable!(),
+        };
+
+        !filtered.is_empty()
+    })
 }
 
 #[derive(Debug, Default, Clone, Deref, DerefMut)]
@@ -199,15 +809,42 @@ struct MatchedEntities(SmallVec<[Entity; 8]>);
 /// Filter entities matching the given selectors.
 /// This function is called once per node on tree returned by [`get_parent_tree`](Selector::get_parent_tree)
 fn select_entities_node(
-    node: SmallVec<[&SelectorElement; 8]>,
+    node: &[SelectorElement],
+    root: Option<Entity>,
     world: &World,
     css_query: &CssQueryParam,
-    registry: &mut ComponentFilterRegistry,
+    registry: &ComponentFilterRegistry,
     entities: SmallVec<[Entity; 8]>,
     tracked_entities: &mut TrackedEntities,
 ) -> SmallVec<[Entity; 8]> {
-    node.into_iter().fold(entities, |entities, element| {
+    // Structural pseudo-classes scoped to a type uses the component of the same node, like `button:nth-of-type(2)`
+    let component = node.iter().find_map(|element| match element {
+        SelectorElement::Component(component) => Some(component.as_str()),
+        _ => None,
+    });
+
+    node.iter().fold(entities, |entities, element| {
         let (filtered, matched) = match element {
+            SelectorElement::PseudoClass(PseudoClassElement::NthOfType(index)) => {
+                match component.map(|component| registry.get(component)) {
+                    Some(Some(filter)) => get_entities_with_pseudo_class_nth_of_type(
+                        world,
+                        *index,
+                        Some(filter.as_ref()),
+                        entities,
+                    ),
+                    // Unknown component, so nothing can be matched
+                    Some(None) => Default::default(),
+                    None => {
+                        get_entities_with_pseudo_class_nth_of_type(world, *index, None, entities)
+                    }
+                }
+            }
+            // The root never changes, so there is nothing to be tracked
+            SelectorElement::PseudoClass(PseudoClassElement::Root) => {
+                let filtered = entities.into_iter().filter(|&e| Some(e) == root).collect();
+                (FilteredEntities(filtered), MatchedEntities::default())
+            }
             SelectorElement::Name(name) => {
                 get_entities_with(name.as_str(), &css_query.names, entities)
             }
@@ -218,11 +855,18 @@ fn select_entities_node(
                 get_entities_with_component(component.as_str(), world, registry, entities)
             }
             SelectorElement::PseudoClass(pseudo_class) => {
-                get_entities_with_pseudo_class(world, *pseudo_class, entities.clone())
+                get_entities_with_pseudo_class(world, registry, pseudo_class, entities.clone())
             }
             SelectorElement::Any => get_entities_with_any_component(&css_query.any, entities),
+            SelectorElement::Attribute { name, op, value } => {
+                get_entities_with_attribute(name.as_str(), *op, value, world, registry, entities)
+            }
+            // Pseudo-elements entities are resolved from matched entities by [`sync_pseudo_elements`]
+            SelectorElement::PseudoElement(_) => {
+                (FilteredEntities(entities), MatchedEntities::default())
+            }
             // All child elements are filtered by [`get_parent_tree`](Selector::get_parent_tree)
-            SelectorElement::Child => unreachable!(),
+            SelectorElement::Child | SelectorElement::DirectChild => unreachable!(),
         };
 
         if !matched.is_empty() {
@@ -239,6 +883,7 @@ fn select_entities_node(
 }
 
 /// Utility function to filter any entities by using a component with implements [`MatchSelectorElement`]
+/// Each entity is looked up on the query, so the cost depends only on the number of given entities.
 /// Returns new filtered list of entities and a list of entities matched by the query.
 fn get_entities_with<T>(
     name: &str,
@@ -248,15 +893,9 @@ fn get_entities_with<T>(
 where
     T: Component + MatchSelectorElement,
 {
-    let entities = query
-        .iter()
-        .filter_map(|(e, rhs)| {
-            if entities.contains(&e) && rhs.matches(name) {
-                Some(e)
-            } else {
-                None
-            }
-        })
+    let entities = entities
+        .into_iter()
+        .filter(|&e| query.get(e).is_ok_and(|(_, rhs)| rhs.matches(name)))
         .collect::<SmallVec<_>>();
 
     (
@@ -269,7 +908,8 @@ where
 /// Returns new filtered list of entities and a list of entities matched by the query.
 fn get_entities_with_pseudo_class(
     world: &World,
-    pseudo_class: PseudoClassElement,
+    registry: &ComponentFilterRegistry,
+    pseudo_class: &PseudoClassElement,
     entities: SmallVec<[Entity; 8]>,
 ) -> (FilteredEntities, MatchedEntities) {
     match pseudo_class {
@@ -279,69 +919,309 @@ fn get_entities_with_pseudo_class(
         PseudoClassElement::Active => {
             get_entities_with_pseudo_class_interaction(world, entities, &Interaction::Pressed)
         }
-        PseudoClassElement::Unsupported => (FilteredEntities(entities), Default::default()),
+        PseudoClassElement::OnlyChild => get_entities_with_pseudo_class_only_child(world, entities),
+        PseudoClassElement::Empty => get_entities_with_pseudo_class_empty(world, entities),
+        PseudoClassElement::NthOfType(index) => {
+            get_entities_with_pseudo_class_nth_of_type(world, *index, None, entities)
+        }
+        PseudoClassElement::Visible => {
+            get_entities_with_pseudo_class_visibility(world, entities, true)
+        }
+        PseudoClassElement::Hidden => {
+            get_entities_with_pseudo_class_visibility(world, entities, false)
+        }
+        PseudoClassElement::Disabled => {
+            get_entities_with_pseudo_class_disabled(world, registry, entities, true)
+        }
+        PseudoClassElement::Enabled => {
+            get_entities_with_pseudo_class_disabled(world, registry, entities, false)
+        }
+        // Without a known root, any entity which owns a style sheet is a root
+        PseudoClassElement::Root => {
+            let filtered = entities
+                .into_iter()
+                .filter(|&e| world.get::<StyleSheet>(e).is_some())
+                .collect();
+            (FilteredEntities(filtered), Default::default())
+        }
+        PseudoClassElement::Custom(name) => {
+            get_entities_with_custom_pseudo_class(world, registry, name, entities)
+        }
     }
 }
 
-/// Utility function to filter any entities matching a [`PseudoClassElement::Hover`] or
-/// [`PseudoClassElement::Active`] variant
-/// This function looks for [`Interaction`] component with [`Interaction::Hovered`] or
-/// [`Interaction::Pressed`] variant.
-/// Returns a list with entities which are hovered or pressed and a list of entities which where matched.
-fn get_entities_with_pseudo_class_interaction(
+/// Utility function to filter any entities matching a [`PseudoClassElement::Custom`] variant.
+/// This function uses the predicate registered with the same name to check each entity.
+/// Since the predicate may depend on anything, nothing is tracked for changes.
+fn get_entities_with_custom_pseudo_class(
+    world: &World,
+    registry: &ComponentFilterRegistry,
+    name: &str,
+    entities: SmallVec<[Entity; 8]>,
+) -> (FilteredEntities, MatchedEntities) {
+    if let Some(pseudo_class) = registry.pseudo_classes.get(name) {
+        let filtered = entities
+            .into_iter()
+            .filter(|&e| (pseudo_class.predicate)(world, e))
+            .collect();
+
+        (FilteredEntities(filtered), Default::default())
+    } else {
+        error!("Unregistered pseudo-class :{}", name);
+        Default::default()
+    }
+}
+
+/// Utility function to filter any entities matching a [`PseudoClassElement::OnlyChild`] variant.
+/// This function looks for [`Parent`] component and checks if the parent [`Children`] has a single entity.
+/// Returns a list with entities which are the only child of its parent and a list of entities which where matched.
+fn get_entities_with_pseudo_class_only_child(
     world: &World,
     entities: SmallVec<[Entity; 8]>,
-    interaction: &Interaction,
 ) -> (FilteredEntities, MatchedEntities) {
     let filtered = entities
         .iter()
         .copied()
         .filter(|&e| {
             world
-                .get_entity(e)
-                .and_then(|e| e.get::<Interaction>())
-                .is_some_and(|i| i == interaction)
+                .get::<Parent>(e)
+                .and_then(|parent| world.get::<Children>(parent.get()))
+                .is_some_and(|children| children.len() == 1)
         })
         .collect::<SmallVec<_>>();
 
     (FilteredEntities(filtered), MatchedEntities(entities))
 }
 
-/// Filters entities which have the components specified on selector, like "a" or "button".
-///
-/// The component must be registered on [`ComponentFilterRegistry`]
-fn get_entities_with_component(
-    name: &str,
+/// Utility function to filter any entities matching a [`PseudoClassElement::NthOfType`] variant.
+/// This function looks for the parent [`Children`] and checks if the entity is the nth sibling which has the
+/// component checked by the given filter, ignoring [`PseudoElementNode`] ones. Without a filter, all siblings are counted.
+/// Indices starts at 1, so `nth-of-type(0)` matches no entity.
+/// Returns a list with entities which are the nth sibling of its type and a list of entities which where matched.
+fn get_entities_with_pseudo_class_nth_of_type(
     world: &World,
-    components: &mut ComponentFilterRegistry,
+    index: usize,
+    filter: Option<&(dyn ComponentFilter + Send + Sync)>,
     entities: SmallVec<[Entity; 8]>,
 ) -> (FilteredEntities, MatchedEntities) {
-    if let Some(query) = components.0.get_mut(name) {
-        let filtered = query
-            .filter(world)
-            .into_iter()
-            .filter(|e| entities.contains(e))
-            .collect::<SmallVec<_>>();
-
-        (
-            FilteredEntities(filtered.clone()),
-            MatchedEntities(filtered),
-        )
-    } else {
-        error!("Unregistered component selector {}", name);
-        Default::default()
-    }
-}
+    let Some(index) = index.checked_sub(1) else {
+        return (FilteredEntities(SmallVec::new()), MatchedEntities(entities));
+    };
 
-/// Filters entities which have a [`Node`] component.
+    let filtered = entities
+        .iter()
+        .copied()
+        .filter(|&e| {
+            world
+                .get::<Parent>(e)
+                .and_then(|parent| world.get::<Children>(parent.get()))
+                .and_then(|children| {
+                    children
+                        .iter()
+                        .copied()
+                        .filter(|&child| world.get::<PseudoElementNode>(child).is_none())
+                        .filter(|&child| {
+                            filter.map_or(true, |filter| filter.contains(world, child))
+                        })
+                        .nth(index)
+                })
+                .is_some_and(|nth| nth == e)
+        })
+        .collect::<SmallVec<_>>();
+
+    (FilteredEntities(filtered), MatchedEntities(entities))
+}
+
+/// Utility function to filter any entities matching a [`PseudoClassElement::Empty`] variant.
+/// This function looks for [`Children`] component and checks if there is no child, ignoring [`PseudoElementNode`] ones.
+/// Returns a list with entities which has no children and a list of entities which where matched.
+fn get_entities_with_pseudo_class_empty(
+    world: &World,
+    entities: SmallVec<[Entity; 8]>,
+) -> (FilteredEntities, MatchedEntities) {
+    let filtered = entities
+        .iter()
+        .copied()
+        .filter(|&e| {
+            world.get::<Children>(e).map_or(true, |children| {
+                children
+                    .iter()
+                    .all(|&child| world.get::<PseudoElementNode>(child).is_some())
+            })
+        })
+        .collect::<SmallVec<_>>();
+
+    (FilteredEntities(filtered), MatchedEntities(entities))
+}
+
+/// Utility function to filter any entities matching a [`PseudoClassElement::Visible`] or
+/// [`PseudoClassElement::Hidden`] variant.
+/// This function checks the effective visibility of each entity, check [`is_visible`] for more.
+/// Returns a list with entities which are visible or hidden and a list of entities which where matched.
+fn get_entities_with_pseudo_class_visibility(
+    world: &World,
+    entities: SmallVec<[Entity; 8]>,
+    visible: bool,
+) -> (FilteredEntities, MatchedEntities) {
+    let filtered = entities
+        .iter()
+        .copied()
+        .filter(|&e| is_visible(world, e) == visible)
+        .collect::<SmallVec<_>>();
+
+    (FilteredEntities(filtered), MatchedEntities(entities))
+}
+
+/// Checks if the given entity is effectively visible, by following [`Visibility::Inherited`] up to the first
+/// ancestor with an explicit [`Visibility`]. Entities without any explicit [`Visibility`] are visible.
+///
+/// [`InheritedVisibility`](bevy::render::view::InheritedVisibility) isn't used, since it's only computed
+/// after styles are applied and only when rendering is enabled.
+fn is_visible(world: &World, entity: Entity) -> bool {
+    let mut current = Some(entity);
+
+    while let Some(entity) = current {
+        match world.get::<Visibility>(entity) {
+            Some(Visibility::Visible) => return true,
+            Some(Visibility::Hidden) => return false,
+            _ => current = world.get::<Parent>(entity).map(Parent::get),
+        }
+    }
+
+    true
+}
+
+/// Utility function to filter any entities matching a [`PseudoClassElement::Disabled`] or
+/// [`PseudoClassElement::Enabled`] variant.
+/// This function checks if the entity has the component registered as disabled marker. When no component is
+/// registered, all entities are enabled.
+/// Returns a list with entities which are disabled or enabled and a list of entities which where matched.
+fn get_entities_with_pseudo_class_disabled(
+    world: &World,
+    registry: &ComponentFilterRegistry,
+    entities: SmallVec<[Entity; 8]>,
+    disabled: bool,
+) -> (FilteredEntities, MatchedEntities) {
+    let filtered = entities
+        .iter()
+        .copied()
+        .filter(|&e| {
+            registry
+                .disabled
+                .as_ref()
+                .is_some_and(|filter| filter.contains(world, e))
+                == disabled
+        })
+        .collect::<SmallVec<_>>();
+
+    (FilteredEntities(filtered), MatchedEntities(entities))
+}
+
+/// Utility function to filter any entities matching a [`PseudoClassElement::Hover`] or
+/// [`PseudoClassElement::Active`] variant
+/// This function looks for [`Interaction`] component with [`Interaction::Hovered`] or
+/// [`Interaction::Pressed`] variant.
+/// Returns a list with entities which are hovered or pressed and a list of entities which where matched.
+fn get_entities_with_pseudo_class_interaction(
+    world: &World,
+    entities: SmallVec<[Entity; 8]>,
+    interaction: &Interaction,
+) -> (FilteredEntities, MatchedEntities) {
+    let filtered = entities
+        .iter()
+        .copied()
+        .filter(|&e| {
+            world
+                .get_entity(e)
+                .and_then(|e| e.get::<Interaction>())
+                .is_some_and(|i| i == interaction)
+        })
+        .collect::<SmallVec<_>>();
+
+    (FilteredEntities(filtered), MatchedEntities(entities))
+}
+
+/// Filters entities which have the components specified on selector, like "a" or "button".
+///
+/// The component must be registered on [`ComponentFilterRegistry`]. When its [`ComponentCache`] is enabled,
+/// entities with the component are collected once and reused by the next selectors.
+fn get_entities_with_component(
+    name: &str,
+    world: &World,
+    components: &ComponentFilterRegistry,
+    entities: SmallVec<[Entity; 8]>,
+) -> (FilteredEntities, MatchedEntities) {
+    if let Some((name, filter)) = components.get_key_value(name) {
+        let mut cache = components
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let filtered = match cache.as_mut() {
+            Some(cache) => {
+                let cached = cache.entities(name, filter.as_ref(), world);
+                entities
+                    .into_iter()
+                    .filter(|e| cached.contains(e))
+                    .collect::<SmallVec<_>>()
+            }
+            None => entities
+                .into_iter()
+                .filter(|e| filter.contains(world, *e))
+                .collect::<SmallVec<_>>(),
+        };
+
+        (
+            FilteredEntities(filtered.clone()),
+            MatchedEntities(filtered),
+        )
+    } else {
+        error!("Unregistered component selector {}", name);
+        Default::default()
+    }
+}
+
+/// Filters entities which have the attribute specified on selector, like `[interaction=pressed]`.
+///
+/// The attribute must be registered on [`ComponentFilterRegistry`]
+/// Returns entities which matches the attribute and a list of entities which has the attribute component.
+fn get_entities_with_attribute(
+    name: &str,
+    op: AttributeOperator,
+    value: &str,
+    world: &World,
+    registry: &ComponentFilterRegistry,
+    entities: SmallVec<[Entity; 8]>,
+) -> (FilteredEntities, MatchedEntities) {
+    if let Some(attribute) = registry.attributes.get(name) {
+        let matched = entities
+            .into_iter()
+            .filter(|e| attribute.filter.contains(world, *e))
+            .collect::<SmallVec<_>>();
+
+        let filtered = matched
+            .iter()
+            .copied()
+            .filter(|&e| {
+                op == AttributeOperator::Exists || (attribute.predicate)(world, e, op, value)
+            })
+            .collect::<SmallVec<_>>();
+
+        (FilteredEntities(filtered), MatchedEntities(matched))
+    } else {
+        error!("Unregistered attribute selector {}", name);
+        Default::default()
+    }
+}
+
+/// Filters entities which have a [`Node`] component.
 /// This is to mimic the "*" selector on CSS.
 fn get_entities_with_any_component(
     query: &Query<Entity, With<Node>>,
     entities: SmallVec<[Entity; 8]>,
 ) -> (FilteredEntities, MatchedEntities) {
-    let filtered = query
-        .iter()
-        .filter(|e| entities.contains(e))
+    let filtered = entities
+        .into_iter()
+        .filter(|&e| query.contains(e))
         .collect::<SmallVec<_>>();
 
     (
@@ -353,7 +1233,7 @@ fn get_entities_with_any_component(
 /// Traverse the children hierarchy three and returns all entities.
 fn get_children_recursively(
     children: &Children,
-    q_childs: &Query<&Children, With<Node>>,
+    q_childs: &Query<&Children>,
 ) -> SmallVec<[Entity; 8]> {
     children
         .iter()
@@ -371,12 +1251,20 @@ fn get_children_recursively(
 pub(crate) fn hot_reload_style_sheets(
     mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
     mut q_sheets: Query<&mut StyleSheet>,
+    default_sheet: Option<Res<DefaultStyleSheet>>,
+    mut global_sheet: Option<ResMut<GlobalStyleSheet>>,
 ) {
     for evt in assets_events.read() {
         if let AssetEvent::Modified { id } = evt {
+            if let Some(global_sheet) = global_sheet.as_mut().filter(|h| h.id() == *id) {
+                debug!("Refreshing global sheet due to asset reload");
+                global_sheet.refresh();
+            }
+
+            let is_default = default_sheet.as_ref().is_some_and(|h| h.id() == *id);
             q_sheets
                 .iter_mut()
-                .filter(|sheet| sheet.handles().iter().any(|h| h.id() == *id))
+                .filter(|sheet| is_default || sheet.handles().iter().any(|h| h.id() == *id))
                 .for_each(|mut sheet| {
                     debug!("Refreshing sheet {:?} due to asset reload", sheet);
                     sheet.refresh();
@@ -385,131 +1273,2539 @@ pub(crate) fn hot_reload_style_sheets(
     }
 }
 
-/// Clear selected entities, but keep tracked ones.
-pub(crate) fn clear_state(mut sheet_rule: ResMut<StyleSheetState>) {
-    if sheet_rule.has_any_selected_entities() {
-        debug!("Finished applying style sheet.");
-        sheet_rule.clear_selected_entities();
+/// Reapply style sheets whenever one of its assets is added, so the final result
+/// doesn't depends on which order assets finished loading.
+pub(crate) fn refresh_loaded_style_sheets(
+    mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    mut q_sheets: Query<&mut StyleSheet>,
+    default_sheet: Option<Res<DefaultStyleSheet>>,
+    mut global_sheet: Option<ResMut<GlobalStyleSheet>>,
+) {
+    for evt in assets_events.read() {
+        if let AssetEvent::Added { id } = evt {
+            if let Some(global_sheet) = global_sheet.as_mut().filter(|h| h.id() == *id) {
+                debug!("Refreshing global sheet due to asset loaded");
+                global_sheet.refresh();
+            }
+
+            let is_default = default_sheet.as_ref().is_some_and(|h| h.id() == *id);
+            q_sheets
+                .iter_mut()
+                .filter(|sheet| is_default || sheet.handles().iter().any(|h| h.id() == *id))
+                .for_each(|mut sheet| {
+                    debug!("Refreshing sheet {:?} due to asset loaded", sheet);
+                    sheet.refresh();
+                });
+        }
     }
 }
 
-/// Watch for changes on entities which is children of a Entith with [`StyleSheet`].
-/// This system uses a cached list of entities which was matched by some [`SelectorElement`]
-/// when applying some [`StyleSheetAsset`].
+/// Font families registered by a single style sheet, along with the registered font.
+type SheetFontFaces = Vec<(String, Handle<Font>)>;
+
+/// Adds the font families declared by `@font-face` rules to [`FontRegistry`] whenever a style sheet is added or modified.
 ///
-/// Whenever a single child has a single component changed, the entire style sheet is applied again.
-pub(crate) fn watch_tracked_entities(world: &mut World) {
-    if world.is_resource_changed::<StyleSheetState>() {
-        trace!("StyleSheetState resource changed! Skipping watch tracked entities");
-        return;
-    }
+/// Families declared later overrides the ones already registered, including the ones registered by hand.
+/// When a style sheet is modified or removed, the families it registered are removed, unless overridden since then.
+pub(crate) fn register_font_faces(
+    mut registered: Local<HashMap<AssetId<StyleSheetAsset>, SheetFontFaces>>,
+    mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    asset_server: Res<AssetServer>,
+    mut registry: ResMut<FontRegistry>,
+) {
+    for evt in assets_events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id }) =
+            evt
+        else {
+            continue;
+        };
 
-    let Some(state) = world.get_resource::<StyleSheetState>() else {
-        return;
-    };
+        for (family, font) in registered.remove(id).into_iter().flatten() {
+            if registry.get(&family) == Some(&font) {
+                debug!("Unregistering font family \"{}\"", family);
+                registry.remove(&family);
+            }
+        }
 
-    let changed_assets = check_for_changed_assets(state, world);
+        let Some(sheet) = assets.get(*id) else {
+            continue;
+        };
 
-    // This is done separated to isolate where we need &mut World.
-    if !changed_assets.is_empty() {
-        let mut query_state: SystemState<Query<&mut StyleSheet>> = SystemState::new(world);
-        for asset_id in changed_assets {
-            let mut query = query_state.get_mut(world);
-            for mut stylesheet in query.iter_mut() {
-                if stylesheet.handles().iter().any(|h| h.id() == asset_id) {
-                    debug!("Refreshing sheet {:?} due to changed entities", stylesheet);
-                    stylesheet.refresh();
-                }
-            }
+        let fonts = sheet
+            .font_faces()
+            .iter()
+            .map(|font_face| {
+                debug!(
+                    "Registering font family \"{}\" from {}",
+                    font_face.family, font_face.src
+                );
+
+                let font = font_face
+                    .font
+                    .clone()
+                    .unwrap_or_else(|| asset_server.load(&font_face.src));
+                registry.insert(font_face.family.clone(), font.clone());
+                (font_face.family.clone(), font)
+            })
+            .collect::<Vec<_>>();
+
+        if !fonts.is_empty() {
+            registered.insert(*id, fonts);
         }
     }
 }
 
-/// Check if any entity has a component which is styled by any asset, was changed.
-/// If it does, return the [`AssetId<T>`] so it can be refreshed.
-fn check_for_changed_assets(
-    state: &StyleSheetState,
-    world: &World,
-) -> Vec<AssetId<StyleSheetAsset>> {
-    let mut changed_assets = vec![];
-    for (asset_id, tracked_entities, _) in state.iter() {
-        for (element, entities) in tracked_entities.iter() {
-            if entities.is_empty() {
-                continue;
-            }
-
-            let changed = match element {
-                SelectorElement::Name(_) => any_component::<Name>(world, entities),
-                SelectorElement::Component(c) => any_component_changed_by_name(world, entities, c),
-                SelectorElement::Class(_) => any_component::<Class>(world, entities),
-                SelectorElement::PseudoClass(pseudo_class) => {
-                    any_component_changed_by_pseudo_class(world, entities, *pseudo_class)
-                }
-                SelectorElement::Any => any_component::<Node>(world, entities),
-                _ => unreachable!(),
-            };
+/// Replaces placeholder fonts written by font properties by the font resolved through [`FontRegistry`].
+///
+/// This runs after all properties are applied, so only the placeholder of the last applied rule is resolved.
+pub(crate) fn resolve_font_families(
+    registry: Res<FontRegistry>,
+    asset_server: Res<AssetServer>,
+    mut q_texts: Query<&mut Text, Changed<Text>>,
+) {
+    if !registry.has_placeholders() {
+        return;
+    }
 
-            if changed {
-                trace!("Changed! {:?}", element);
-                changed_assets.push(*asset_id);
-                break;
+    for mut text in &mut q_texts {
+        // Text was already marked as changed when the placeholder was written
+        let text = text.bypass_change_detection();
+        for section in &mut text.sections {
+            if let Some(font) = registry.resolve_placeholder(&section.style.font, &asset_server) {
+                section.style.font = font;
             }
         }
     }
+}
+
+/// Reapply all style sheets whenever [`DefaultStyleSheet`] is inserted, changed or removed.
+pub(crate) fn watch_default_style_sheet(
+    mut had_default: Local<bool>,
+    default_sheet: Option<Res<DefaultStyleSheet>>,
+    mut q_sheets: Query<&mut StyleSheet>,
+) {
+    let changed = match &default_sheet {
+        Some(default_sheet) => default_sheet.is_changed(),
+        None => *had_default,
+    };
+    *had_default = default_sheet.is_some();
 
-    changed_assets
+    if changed {
+        debug!("Refreshing all sheets due to default style sheet change");
+        q_sheets.iter_mut().for_each(|mut sheet| sheet.refresh());
+    }
 }
 
-/// Checks if any entity on the given list has it's component changed.
-fn any_component<T: Component>(world: &World, entities: &SmallVec<[Entity; 8]>) -> bool {
-    let this_run = world.read_change_tick();
-    let last_run = world.last_change_tick();
-    for e in entities {
-        if let Some(ticks) = world.get_entity(*e).and_then(|e| e.get_change_ticks::<T>()) {
-            if ticks.is_changed(last_run, this_run) {
-                return true;
+/// Reapply style sheets which has any `@media` rule affected when primary window is resized,
+/// so rules are included or removed as soon as the window crosses a breakpoint.
+///
+/// Style sheets which uses viewport units, like `50vw`, are also reapplied on every resize.
+/// Roots with [`NoAutoRestyle`] are skipped.
+pub(crate) fn watch_window_size(
+    mut last_size: Local<Option<(f32, f32)>>,
+    q_window: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    default_sheet: Option<Res<DefaultStyleSheet>>,
+    mut global_sheet: Option<ResMut<GlobalStyleSheet>>,
+    mut q_sheets: Query<&mut StyleSheet, Without<NoAutoRestyle>>,
+) {
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+
+    let size = (window.width(), window.height());
+    let last = last_size.replace(size);
+
+    if last == Some(size) {
+        return;
+    }
+
+    // When there was no window before, no `@media` rule was matched
+    let crossed = |sheet: &StyleSheetAsset| {
+        sheet
+            .iter()
+            .filter_map(|rule| rule.selector.media())
+            .any(|media| {
+                last.is_some_and(|(w, h)| media.matches(w, h)) != media.matches(size.0, size.1)
+            })
+    };
+
+    if let Some(global_sheet) = global_sheet.as_mut() {
+        if let Some(sheet) = assets.get(global_sheet.handle()) {
+            if crossed(sheet) || sheet.uses_viewport_units() {
+                debug!("Refreshing global sheet due to window resize");
+                global_sheet.refresh();
             }
         }
     }
-    false
+
+    for mut sheet in &mut q_sheets {
+        let mut sheets = default_sheet
+            .as_deref()
+            .map(DefaultStyleSheet::handle)
+            .into_iter()
+            .chain(sheet.handles())
+            .filter_map(|handle| assets.get(handle));
+        if sheets.clone().any(crossed) {
+            debug!("Refreshing sheet {:?} due to @media breakpoint", sheet);
+            sheet.refresh();
+        } else if sheets.any(StyleSheetAsset::uses_viewport_units) {
+            debug!("Refreshing sheet {:?} due to viewport units", sheet);
+            sheet.refresh();
+        }
+    }
 }
 
-/// Checks if any entity on the given list has it's component changed.
-fn any_component_changed_by_name(
-    world: &World,
-    entities: &SmallVec<[Entity; 8]>,
-    component_name: &str,
-) -> bool {
-    let this_run = world.read_change_tick();
-    let last_run = world.last_change_tick();
+/// Sets the primary window cursor icon to the [`CssCursor`] of the hovered entity, if any.
+///
+/// The cursor icon is only reset to [`CursorIcon::Default`] if it was previously changed by this system,
+/// so icons set by other systems aren't overwritten.
+pub(crate) fn update_cursor_icon(
+    mut last_icon: Local<Option<CursorIcon>>,
+    q_cursors: Query<(&Interaction, &CssCursor)>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let hovered = q_cursors
+        .iter()
+        .find(|(interaction, _)| **interaction != Interaction::None)
+        .map(|(_, cursor)| cursor.0);
 
-    let Some(registry) = world.get_resource::<ComponentFilterRegistry>() else {
-        return false;
+    let icon = match (hovered, *last_icon) {
+        (Some(icon), _) => icon,
+        (None, Some(_)) => CursorIcon::Default,
+        (None, None) => return,
     };
-    let Some(boxed_state) = registry.get(component_name) else {
-        return false;
+
+    let Ok(mut window) = q_window.get_single_mut() else {
+        return;
     };
 
-    for e in entities {
-        if let Some(ticks) = boxed_state.get_change_ticks(world, *e) {
-            if ticks.is_changed(last_run, this_run) {
-                return true;
-            }
+    if window.cursor.icon != icon {
+        window.cursor.icon = icon;
+    }
+
+    *last_icon = hovered;
+}
+
+/// Truncates texts with [`TextOverflow::Ellipsis`] which doesn't fit on its [`Node`] width, adding an ellipsis at the end.
+///
+/// Texts are measured using the glyphs of the last text layout, so truncation happens on the frame after
+/// the text stopped fitting on the node.
+pub(crate) fn truncate_text_overflow(
+    mut q_texts: Query<(&Node, &mut Text, &TextLayoutInfo, &TextOverflow), ChangedTextLayout>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Option<Res<UiScale>>,
+) {
+    let scale_factor = q_window
+        .get_single()
+        .map_or(1.0, |window| window.scale_factor())
+        * ui_scale.map_or(1.0, |ui_scale| ui_scale.0);
+
+    for (node, mut text, layout, overflow) in &mut q_texts {
+        if *overflow != TextOverflow::Ellipsis || layout.glyphs.is_empty() {
+            continue;
+        }
+
+        // Glyph positions are in physical pixels and centered on each glyph. White spaces have no glyphs,
+        // so those are included on the width of the next glyph.
+        let mut last_end = 0.0;
+        let widths = layout
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let end = (glyph.position.x + glyph.size.x / 2.0) / scale_factor;
+                let width = end - last_end;
+                last_end = end;
+                width
+            })
+            .collect::<SmallVec<[f32; 32]>>();
+
+        // The ellipsis isn't laid out yet, so it's assumed to be as wide as an average glyph
+        let ellipsis_width = last_end / widths.len() as f32;
+
+        let Some(keep) = ellipsis_cut(&widths, node.size().x, ellipsis_width) else {
+            continue;
+        };
+
+        let cut = &layout.glyphs[keep];
+        trace!("Truncating text at glyph {} of {}", keep, widths.len());
+
+        text.sections.truncate(cut.section_index + 1);
+        if let Some(section) = text.sections.get_mut(cut.section_index) {
+            section.value.truncate(cut.byte_index);
+            section.value.truncate(section.value.trim_end().len());
+            section.value.push('…');
         }
     }
-    false
 }
 
-/// Checks if any entity on the given list has it's component changed.
-fn any_component_changed_by_pseudo_class(
-    world: &World,
-    entities: &SmallVec<[Entity; 8]>,
-    pseudo_class: PseudoClassElement,
-) -> bool {
-    match pseudo_class {
-        PseudoClassElement::Hover | PseudoClassElement::Active => {
-            any_component::<Interaction>(world, entities)
+/// Texts which layout, node size or overflow mode changed.
+type ChangedTextLayout = Or<(
+    Changed<TextLayoutInfo>,
+    Changed<Node>,
+    Changed<TextOverflow>,
+)>;
+
+/// Truncates texts which wraps into more lines than its [`MaxLines`], adding an ellipsis at the end of the last line.
+///
+/// Lines are found using the glyphs of the last text layout, so truncation happens on the frame after the text was laid out.
+pub(crate) fn clamp_text_lines(
+    mut q_texts: Query<(&mut Text, &TextLayoutInfo, &MaxLines), ChangedTextLines>,
+) {
+    for (mut text, layout, max_lines) in &mut q_texts {
+        let glyph_rows = layout
+            .glyphs
+            .iter()
+            .map(|glyph| (glyph.position.y, glyph.size.y))
+            .collect::<SmallVec<[(f32, f32); 32]>>();
+
+        let Some(first_hidden) = first_glyph_after_lines(&glyph_rows, max_lines.0) else {
+            continue;
+        };
+
+        // The last visible glyph is also removed to give room to the ellipsis. If it still doesn't fit,
+        // the ellipsis wraps and the next layout will truncate it again.
+        let cut = &layout.glyphs[first_hidden.saturating_sub(1)];
+        trace!(
+            "Clamping text to {} lines at glyph {}",
+            max_lines.0,
+            first_hidden
+        );
+
+        text.sections.truncate(cut.section_index + 1);
+        if let Some(section) = text.sections.get_mut(cut.section_index) {
+            section.value.truncate(cut.byte_index);
+            section.value.truncate(section.value.trim_end().len());
+            section.value.push('…');
         }
-        PseudoClassElement::Unsupported => false,
+    }
+}
+
+/// Texts which layout or max lines changed.
+type ChangedTextLines = Or<(Changed<TextLayoutInfo>, Changed<MaxLines>)>;
+
+/// Returns the index of the first glyph which is placed after `max_lines` lines, if any.
+///
+/// Each glyph is given by its vertical center and height. A new line starts when a glyph is vertically
+/// apart from the previous one by more than half of the height of the tallest of both.
+fn first_glyph_after_lines(glyph_rows: &[(f32, f32)], max_lines: usize) -> Option<usize> {
+    let mut lines = 1;
+
+    glyph_rows
+        .windows(2)
+        .position(|pair| {
+            let [(previous_y, previous_height), (y, height)] = pair else {
+                return false;
+            };
+
+            if (y - previous_y).abs() > previous_height.max(*height) / 2.0 {
+                lines += 1;
+            }
+
+            lines > max_lines
+        })
+        .map(|index| index + 1)
+}
+
+/// Resolves the `calc(...)` expressions of [`CssCalc`] to [`Val::Px`] on [`Style`] of each entity.
+///
+/// Percentages are relative to the parent [`Node`] size on the last layout, or to the primary window size on root nodes.
+pub(crate) fn resolve_css_calc(
+    mut q_calcs: Query<(&mut Style, &CssCalc, Option<&Parent>)>,
+    q_nodes: Query<&Node>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Option<Res<UiScale>>,
+) {
+    let viewport = q_window.get_single().map_or(Vec2::ZERO, |window| {
+        Vec2::new(window.width(), window.height())
+    });
+    let ui_scale = ui_scale.map_or(1.0, |ui_scale| ui_scale.0);
+
+    for (mut style, calc, maybe_parent) in &mut q_calcs {
+        let basis = maybe_parent
+            .and_then(|parent| q_nodes.get(parent.get()).ok())
+            .map_or(viewport, Node::size);
+
+        // Sizes can't be negative, like on CSS
+        let width = calc
+            .width
+            .map(|calc| Val::Px(calc.resolve(basis.x, viewport, ui_scale).max(0.0)));
+        let height = calc
+            .height
+            .map(|calc| Val::Px(calc.resolve(basis.y, viewport, ui_scale).max(0.0)));
+
+        // Avoid triggering change detection when nothing changed
+        if width.is_some_and(|width| style.width != width) {
+            style.width = width.unwrap();
+        }
+        if height.is_some_and(|height| style.height != height) {
+            style.height = height.unwrap();
+        }
+    }
+}
+
+/// Sets [`Style::aspect_ratio`] of images with [`ObjectFit::Contain`] or [`ObjectFit::Cover`] to the image aspect ratio.
+///
+/// The image size is only known once it's loaded, so the aspect ratio is updated whenever [`UiImageSize`] changes.
+/// Images changed to [`ObjectFit::Fill`] have the aspect ratio cleared.
+pub(crate) fn fit_images(
+    mut q_images: Query<(&mut Style, &UiImageSize, Ref<ObjectFit>), ChangedImageFit>,
+) {
+    for (mut style, image_size, fit) in &mut q_images {
+        let size = image_size.size();
+
+        let aspect_ratio = match *fit {
+            ObjectFit::Fill if fit.is_changed() => None,
+            ObjectFit::Fill => continue,
+            ObjectFit::Contain | ObjectFit::Cover if size.x > 0.0 && size.y > 0.0 => {
+                Some(size.x / size.y)
+            }
+            // Not loaded yet
+            ObjectFit::Contain | ObjectFit::Cover => continue,
+        };
+
+        if style.aspect_ratio != aspect_ratio {
+            style.aspect_ratio = aspect_ratio;
+        }
+    }
+}
+
+/// Images which size or fit changed.
+type ChangedImageFit = Or<(Changed<UiImageSize>, Changed<ObjectFit>)>;
+
+/// Moves children of nodes with [`ScrollPosition`] by the scroll offset, so the content is scrolled.
+///
+/// Bevy UI layout resets the translation of children whenever it differs from the computed one. Children which
+/// [`Transform`] wasn't changed since the last run still have the offset applied back then, so only the difference
+/// is applied on those.
+pub(crate) fn scroll_nodes(
+    mut applied: Local<EntityHashMap<Vec2>>,
+    q_scrolls: Query<(&ScrollPosition, &Children)>,
+    mut q_transforms: Query<&mut Transform, With<Node>>,
+) {
+    let mut scrolled = EntityHashMap::default();
+
+    for (scroll, children) in &q_scrolls {
+        let offset = Vec2::new(scroll.offset_x, scroll.offset_y);
+
+        for &child in children {
+            let Ok(mut transform) = q_transforms.get_mut(child) else {
+                continue;
+            };
+
+            let previous = applied
+                .get(&child)
+                .filter(|_| !transform.is_changed())
+                .copied()
+                .unwrap_or_default();
+
+            let delta = offset - previous;
+            if delta != Vec2::ZERO {
+                transform.translation -= delta.extend(0.0);
+            }
+
+            if offset != Vec2::ZERO {
+                scrolled.insert(child, offset);
+            }
+        }
+    }
+
+    *applied = scrolled;
+}
+
+/// Returns how many glyphs fits in `max_width` when followed by an ellipsis `ellipsis_width` wide,
+/// or [`None`] if all glyphs already fits without an ellipsis.
+fn ellipsis_cut(glyph_widths: &[f32], max_width: f32, ellipsis_width: f32) -> Option<usize> {
+    if glyph_widths.iter().sum::<f32>() <= max_width {
+        return None;
+    }
+
+    let available = max_width - ellipsis_width;
+    let mut width = 0.0;
+
+    Some(
+        glyph_widths
+            .iter()
+            .take_while(|glyph_width| {
+                width += *glyph_width;
+                width <= available
+            })
+            .count(),
+    )
+}
+
+/// Warns about property names used on style sheets which doesn't match any registered [`Property`](crate::Property).
+///
+/// Each unknown name is reported only once every time an asset is loaded or modified.
+pub(crate) fn warn_unknown_properties(
+    mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    registered: Res<RegisteredProperties>,
+) {
+    for evt in assets_events.read() {
+        if let AssetEvent::Added { id } | AssetEvent::Modified { id } = evt {
+            let Some(sheet) = assets.get(*id) else {
+                continue;
+            };
+
+            for name in registered.unknown_properties(sheet) {
+                warn!(
+                    "Unknown property \"{}\" on style sheet {}",
+                    name,
+                    sheet.path()
+                );
+            }
+        }
+    }
+}
+
+/// Components which are reverted by [`unload_removed_style_sheets`].
+type RevertableQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static mut Style>,
+        Option<&'static mut BackgroundColor>,
+        Option<&'static mut BorderColor>,
+        Has<UiImage>,
+    ),
+>;
+
+/// Removes the state of roots which had its [`StyleSheet`] removed or were despawned.
+///
+/// When the removed [`StyleSheet`] was flagged with [`StyleSheet::revert_on_removal`], all entities matched by it
+/// are reverted and any other sheet which also matched those entities is reapplied.
+pub(crate) fn unload_removed_style_sheets(
+    mut removed: RemovedComponents<StyleSheet>,
+    mut state: ResMut<StyleSheetState>,
+    mut style_query: ResMut<StyleQuery>,
+    mut global_sheet: Option<ResMut<GlobalStyleSheet>>,
+    mut q_sheets: Query<&mut StyleSheet>,
+    mut q_styled: RevertableQuery,
+    mut commands: Commands,
+) {
+    let mut reverted = SmallVec::<[Entity; 8]>::new();
+
+    for root in removed.read() {
+        // The component may have been removed and inserted again
+        if q_sheets.contains(root) || !state.contains_key(&root) {
+            continue;
+        }
+
+        debug!("Unloading removed style sheet of {:?}", root);
+
+        let revert = state.remove_root(root).unwrap_or_default();
+        let entities = style_query.remove_root(root);
+
+        if let Some(mut entity) = commands.get_entity(root) {
+            entity.remove::<StyleSheetApplied>();
+        }
+
+        if !revert {
+            continue;
+        }
+
+        for entity in entities {
+            if let Ok((style, background_color, border_color, has_image)) = q_styled.get_mut(entity)
+            {
+                if let Some(mut style) = style {
+                    *style = Style::DEFAULT;
+                }
+                if let Some(mut background_color) = background_color {
+                    // Images uses background color as tint
+                    background_color.0 = if has_image {
+                        BackgroundColor::DEFAULT.0
+                    } else {
+                        Color::NONE
+                    };
+                }
+                if let Some(mut border_color) = border_color {
+                    border_color.0 = Color::NONE;
+                }
+                commands
+                    .entity(entity)
+                    .remove::<(CssGradient, CssBoxShadow, CssCalc, ObjectFit)>();
+                reverted.push(entity);
+            }
+        }
+    }
+
+    if reverted.is_empty() {
+        return;
+    }
+
+    let roots = style_query
+        .roots_matching(&reverted)
+        .collect::<SmallVec<[_; 8]>>();
+
+    for root in roots {
+        if root == GLOBAL_ROOT {
+            if let Some(global_sheet) = global_sheet.as_deref_mut() {
+                debug!("Refreshing global sheet due to reverted entities");
+                global_sheet.refresh();
+            }
+        } else if let Ok(mut sheet) = q_sheets.get_mut(root) {
+            debug!("Refreshing sheet {:?} due to reverted entities", sheet);
+            sheet.refresh();
+        }
+    }
+}
+
+/// Clear selected entities, but keep tracked ones.
+pub(crate) fn clear_state(
+    mut sheet_rule: ResMut<StyleSheetState>,
+    mut style_query: ResMut<StyleQuery>,
+) {
+    if sheet_rule.has_any_selected_entities() {
+        debug!("Finished applying style sheet.");
+        style_query.update(&sheet_rule);
+        // Clearing must not be seen as a change, otherwise [`watch_tracked_entities`] skips the next frame
+        sheet_rule
+            .bypass_change_detection()
+            .clear_selected_entities();
+    }
+}
+
+/// Watch for changes on entities which is children of a Entith with [`StyleSheet`].
+/// This system uses a cached list of entities which was matched by some [`SelectorElement`]
+/// when applying some [`StyleSheetAsset`].
+///
+/// Whenever a single child has a single component changed, the entire style sheet is applied again.
+pub(crate) fn watch_tracked_entities(world: &mut World) {
+    if world.is_resource_changed::<StyleSheetState>() {
+        trace!("StyleSheetState resource changed! Skipping watch tracked entities");
+        return;
+    }
+
+    let Some(state) = world.get_resource::<StyleSheetState>() else {
+        return;
+    };
+
+    let changed_roots = check_for_changed_roots(state, world);
+
+    // This is done separated to isolate where we need &mut World.
+    if !changed_roots.is_empty() {
+        if changed_roots.contains(&GLOBAL_ROOT) {
+            if let Some(mut global_sheet) = world.get_resource_mut::<GlobalStyleSheet>() {
+                debug!("Refreshing global sheet due to changed entities");
+                global_sheet.refresh();
+            }
+        }
+
+        let mut query_state: SystemState<Query<&mut StyleSheet>> = SystemState::new(world);
+        let mut query = query_state.get_mut(world);
+        for root in changed_roots {
+            if let Ok(mut stylesheet) = query.get_mut(root) {
+                debug!("Refreshing sheet {:?} due to changed entities", stylesheet);
+                stylesheet.refresh();
+            }
+        }
+    }
+}
+
+/// Refresh style sheets of roots which has any descendant with a changed, added or removed [`Class`].
+///
+/// Unlike [`watch_tracked_entities`], this also handles entities which wasn't matched before, so a class
+/// which makes an entity match some rule is applied on the same frame.
+pub(crate) fn watch_changed_classes(
+    q_changed: Query<Entity, Changed<Class>>,
+    mut removed: RemovedComponents<Class>,
+    q_parents: Query<&Parent>,
+    mut q_sheets: Query<&mut StyleSheet, Without<NoAutoRestyle>>,
+) {
+    let roots = ancestor_roots(
+        q_changed.iter().chain(removed.read()),
+        &q_parents,
+        &q_sheets,
+    );
+
+    for root in roots {
+        if let Ok(mut sheet) = q_sheets.get_mut(root) {
+            debug!("Refreshing sheet {:?} due to changed classes", sheet);
+            sheet.refresh();
+        }
+    }
+}
+
+/// Nodes which were spawned, reparented or had its [`Class`] changed, ignoring pseudo-elements spawned by `bevy_ecss`.
+type ChangedNodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    (
+        Or<(Added<Node>, Changed<Parent>, Changed<Class>)>,
+        Without<PseudoElementNode>,
+    ),
+>;
+
+/// Refresh [`GlobalStyleSheet`] whenever any node is spawned, reparented or has its [`Class`] changed or removed,
+/// since its rules are selected against all nodes.
+///
+/// When the resource is removed, its state is also removed, so its rules are no longer tracked.
+pub(crate) fn watch_global_style_sheet(
+    mut had_global: Local<bool>,
+    global_sheet: Option<ResMut<GlobalStyleSheet>>,
+    q_changed: ChangedNodeQuery,
+    mut removed_classes: RemovedComponents<Class>,
+    mut state: ResMut<StyleSheetState>,
+) {
+    let had_global = std::mem::replace(&mut *had_global, global_sheet.is_some());
+
+    let Some(mut global_sheet) = global_sheet else {
+        if had_global {
+            state.remove(&GLOBAL_ROOT);
+        }
+        return;
+    };
+
+    if removed_classes.read().count() > 0 || !q_changed.is_empty() {
+        debug!("Refreshing global sheet due to changed nodes");
+        global_sheet.refresh();
+    }
+}
+
+/// Nodes which were spawned with or moved to a new parent, ignoring pseudo-elements spawned by `bevy_ecss`.
+type ChangedParentQuery<'w, 's> =
+    Query<'w, 's, Entity, (Changed<Parent>, With<Node>, Without<PseudoElementNode>)>;
+
+/// Refresh style sheets of roots which had a [`Node`] spawned or moved into its hierarchy,
+/// or had a tracked entity removed from it or losing all its children, so new children are styled
+/// without a manual refresh.
+///
+/// Only roots which are ancestors of those entities are refreshed, so changes outside styled trees are ignored.
+pub(crate) fn watch_hierarchy_changes(
+    q_changed: ChangedParentQuery,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut removed_children: RemovedComponents<Children>,
+    state: Res<StyleSheetState>,
+    q_parents: Query<&Parent>,
+    mut q_sheets: Query<&mut StyleSheet, Without<NoAutoRestyle>>,
+) {
+    let mut roots = ancestor_roots(q_changed.iter(), &q_parents, &q_sheets);
+
+    // Removed entities are detached from hierarchy, so roots are found by the tracked entities.
+    // Entities which had all its children removed are also detected here, since those lose `Children`.
+    for entity in removed_parents.read().chain(removed_children.read()) {
+        for (root, sheets) in state.iter() {
+            let tracked = sheets
+                .iter()
+                .any(|(_, tracked, _)| tracked.values().any(|entities| entities.contains(&entity)));
+
+            if tracked && !roots.contains(root) {
+                roots.push(*root);
+            }
+        }
+    }
+
+    for root in roots {
+        if let Ok(mut sheet) = q_sheets.get_mut(root) {
+            debug!("Refreshing sheet {:?} due to hierarchy changes", sheet);
+            sheet.refresh();
+        }
+    }
+}
+
+/// Returns all [`StyleSheet`] roots which are the given entities or any of its ancestors, without duplicates.
+fn ancestor_roots(
+    entities: impl Iterator<Item = Entity>,
+    q_parents: &Query<&Parent>,
+    q_sheets: &Query<&mut StyleSheet, Without<NoAutoRestyle>>,
+) -> SmallVec<[Entity; 8]> {
+    let mut roots = SmallVec::new();
+
+    for entity in entities {
+        for ancestor in std::iter::once(entity).chain(q_parents.iter_ancestors(entity)) {
+            if q_sheets.contains(ancestor) && !roots.contains(&ancestor) {
+                roots.push(ancestor);
+            }
+        }
+    }
+
+    roots
+}
+
+/// Check if any entity has a component which is styled by any asset, was changed.
+/// If it does, return the root [`Entity`] which holds the [`StyleSheet`] so it can be refreshed.
+fn check_for_changed_roots(state: &StyleSheetState, world: &World) -> Vec<Entity> {
+    let mut changed_roots = vec![];
+    'roots: for (root, sheets) in state.iter() {
+        if world.get::<NoAutoRestyle>(*root).is_some() {
+            continue;
+        }
+
+        for (_, tracked_entities, _) in sheets {
+            for (element, entities) in tracked_entities.iter() {
+                if entities.is_empty() {
+                    continue;
+                }
+
+                let changed = match element {
+                    SelectorElement::Name(_) => any_component::<Name>(world, entities),
+                    SelectorElement::Component(c) => {
+                        any_component_changed_by_name(world, entities, c)
+                    }
+                    SelectorElement::Class(_) => any_component::<Class>(world, entities),
+                    SelectorElement::PseudoClass(pseudo_class) => {
+                        any_component_changed_by_pseudo_class(world, entities, pseudo_class)
+                    }
+                    SelectorElement::Any => any_component::<Node>(world, entities),
+                    SelectorElement::Attribute { name, .. } => {
+                        any_component_changed_by_attribute(world, entities, name)
+                    }
+                    _ => unreachable!(),
+                };
+
+                if changed {
+                    trace!("Changed! {:?}", element);
+                    changed_roots.push(*root);
+                    continue 'roots;
+                }
+            }
+        }
+    }
+
+    changed_roots
+}
+
+/// Checks if any entity on the given list has it's component changed.
+fn any_component<T: Component>(world: &World, entities: &SmallVec<[Entity; 8]>) -> bool {
+    let this_run = world.read_change_tick();
+    let last_run = world.last_change_tick();
+    for e in entities {
+        if let Some(ticks) = world.get_entity(*e).and_then(|e| e.get_change_ticks::<T>()) {
+            if ticks.is_changed(last_run, this_run) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Checks if any entity on the given list has it's component changed.
+fn any_component_changed_by_name(
+    world: &World,
+    entities: &SmallVec<[Entity; 8]>,
+    component_name: &str,
+) -> bool {
+    let this_run = world.read_change_tick();
+    let last_run = world.last_change_tick();
+
+    let Some(registry) = world.get_resource::<ComponentFilterRegistry>() else {
+        return false;
+    };
+    let Some(boxed_state) = registry.get(component_name) else {
+        return false;
+    };
+
+    for e in entities {
+        if let Some(ticks) = boxed_state.get_change_ticks(world, *e) {
+            if ticks.is_changed(last_run, this_run) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Checks if any entity on the given list has it's attribute component changed.
+fn any_component_changed_by_attribute(
+    world: &World,
+    entities: &SmallVec<[Entity; 8]>,
+    attribute_name: &str,
+) -> bool {
+    let this_run = world.read_change_tick();
+    let last_run = world.last_change_tick();
+
+    let Some(registry) = world.get_resource::<ComponentFilterRegistry>() else {
+        return false;
+    };
+    let Some(attribute) = registry.attributes.get(attribute_name) else {
+        return false;
+    };
+
+    entities.iter().any(|e| {
+        attribute
+            .filter
+            .get_change_ticks(world, *e)
+            .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+    })
+}
+
+/// Checks if any entity on the given list has it's component changed.
+fn any_component_changed_by_pseudo_class(
+    world: &World,
+    entities: &SmallVec<[Entity; 8]>,
+    pseudo_class: &PseudoClassElement,
+) -> bool {
+    match pseudo_class {
+        PseudoClassElement::Hover | PseudoClassElement::Active => {
+            any_component::<Interaction>(world, entities)
+        }
+        PseudoClassElement::OnlyChild => {
+            any_component::<Parent>(world, entities) || any_parent_children_changed(world, entities)
+        }
+        PseudoClassElement::NthOfType(_) => {
+            any_component::<Parent>(world, entities) || any_parent_children_changed(world, entities)
+        }
+        // Entities which had its last child removed lose the `Children` component, which is handled
+        // by [`watch_hierarchy_changes`]
+        PseudoClassElement::Empty => any_component::<Children>(world, entities),
+        // Visibility is inherited, so changes on any ancestor may affect the matched entities
+        PseudoClassElement::Visible | PseudoClassElement::Hidden => {
+            any_ancestor_visibility_changed(world, entities)
+        }
+        PseudoClassElement::Disabled | PseudoClassElement::Enabled => {
+            any_disabled_component_changed(world, entities)
+        }
+        PseudoClassElement::Root | PseudoClassElement::Custom(_) => false,
+    }
+}
+
+/// Checks if any entity on the given list had the disabled marker component added, changed or removed.
+fn any_disabled_component_changed(world: &World, entities: &SmallVec<[Entity; 8]>) -> bool {
+    let this_run = world.read_change_tick();
+    let last_run = world.last_change_tick();
+
+    let Some(filter) = world
+        .get_resource::<ComponentFilterRegistry>()
+        .and_then(|registry| registry.disabled.as_ref())
+    else {
+        return false;
+    };
+
+    entities.iter().any(|&e| {
+        filter.was_removed(world, e)
+            || filter
+                .get_change_ticks(world, e)
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+    })
+}
+
+/// Checks if any entity on the given list, or any of its ancestors, has it's [`Visibility`] component changed.
+fn any_ancestor_visibility_changed(world: &World, entities: &SmallVec<[Entity; 8]>) -> bool {
+    let ancestors = entities
+        .iter()
+        .flat_map(|&e| std::iter::successors(Some(e), |&e| world.get::<Parent>(e).map(Parent::get)))
+        .collect();
+
+    any_component::<Visibility>(world, &ancestors)
+}
+
+/// Checks if any entity on the given list has it's parent [`Children`] component changed.
+fn any_parent_children_changed(world: &World, entities: &SmallVec<[Entity; 8]>) -> bool {
+    let parents = entities
+        .iter()
+        .filter_map(|&e| world.get::<Parent>(e).map(Parent::get))
+        .collect::<SmallVec<[Entity; 8]>>();
+
+    any_component::<Children>(world, &parents)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::EcssPlugin;
+
+    #[test]
+    fn select_only_child() {
+        let mut world = World::new();
+
+        let lone_child = world.spawn_empty().id();
+        world.spawn_empty().add_child(lone_child);
+
+        let first_child = world.spawn_empty().id();
+        let second_child = world.spawn_empty().id();
+        world
+            .spawn_empty()
+            .push_children(&[first_child, second_child]);
+
+        let entities = [lone_child, first_child, second_child]
+            .into_iter()
+            .collect();
+        let (filtered, matched) = get_entities_with_pseudo_class(
+            &world,
+            &Default::default(),
+            &PseudoClassElement::OnlyChild,
+            entities,
+        );
+
+        assert_eq!(filtered.as_slice(), &[lone_child]);
+        assert_eq!(matched.len(), 3, "Should track all entities");
+    }
+
+    #[test]
+    fn select_empty() {
+        let (mut app, (empty, item, filled)) =
+            crate::apply_test_sheet(".list:empty { width: 10px; }", |world, root| {
+                let empty = world
+                    .spawn((NodeBundle::default(), Class::new("list")))
+                    .set_parent(root)
+                    .id();
+                let item = world.spawn(NodeBundle::default()).id();
+                let filled = world
+                    .spawn((NodeBundle::default(), Class::new("list")))
+                    .set_parent(root)
+                    .add_child(item)
+                    .id();
+                (empty, item, filled)
+            });
+        assert_eq!(app.world.get::<Style>(empty).unwrap().width, Val::Px(10.0));
+        assert_eq!(app.world.get::<Style>(filled).unwrap().width, Val::Auto);
+
+        let pseudo_element = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                PseudoElementNode(PseudoElement::Before),
+            ))
+            .id();
+        let only_pseudo_element = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("list")))
+            .add_child(pseudo_element)
+            .id();
+
+        let entities = [empty, filled, only_pseudo_element].into_iter().collect();
+        let (filtered, matched) = get_entities_with_pseudo_class(
+            &app.world,
+            &Default::default(),
+            &PseudoClassElement::Empty,
+            entities,
+        );
+        assert_eq!(filtered.as_slice(), &[empty, only_pseudo_element]);
+        assert_eq!(matched.len(), 3, "Should track all entities");
+
+        app.world.entity_mut(filled).remove_children(&[item]);
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<Style>(filled).unwrap().width,
+            Val::Px(10.0),
+            "Should be matched once its last child is removed"
+        );
+    }
+
+    #[test]
+    fn select_visibility() {
+        let (mut app, (inherited, visible, container)) =
+            crate::apply_test_sheet(".item:hidden { width: 10px; }", |world, root| {
+                let inherited = world
+                    .spawn((NodeBundle::default(), Class::new("item")))
+                    .id();
+                let visible = world
+                    .spawn((
+                        NodeBundle {
+                            visibility: Visibility::Visible,
+                            ..default()
+                        },
+                        Class::new("item"),
+                    ))
+                    .id();
+                let container = world
+                    .spawn(NodeBundle::default())
+                    .set_parent(root)
+                    .push_children(&[inherited, visible])
+                    .id();
+                (inherited, visible, container)
+            });
+
+        let entities = [inherited, visible].into_iter().collect();
+        let (filtered, matched) = get_entities_with_pseudo_class(
+            &app.world,
+            &Default::default(),
+            &PseudoClassElement::Visible,
+            entities,
+        );
+        assert_eq!(filtered.as_slice(), &[inherited, visible]);
+        assert_eq!(matched.len(), 2, "Should track all entities");
+
+        app.update();
+        assert_eq!(app.world.get::<Style>(inherited).unwrap().width, Val::Auto);
+
+        *app.world.get_mut::<Visibility>(container).unwrap() = Visibility::Hidden;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<Style>(inherited).unwrap().width,
+            Val::Px(10.0),
+            "Should inherit parent visibility"
+        );
+        assert_eq!(
+            app.world.get::<Style>(visible).unwrap().width,
+            Val::Auto,
+            "Explicit visibility shouldn't be inherited"
+        );
+    }
+
+    #[test]
+    fn select_disabled() {
+        use crate::RegisterComponentSelector;
+
+        #[derive(Component)]
+        struct Disabled;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .register_disabled_component::<Disabled>();
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse(
+                "",
+                ".button:disabled { background-color: red; } .button:enabled { background-color: white; }",
+            ));
+
+        let button = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("button")))
+            .id();
+        app.world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .add_child(button);
+
+        let background = |app: &App| app.world.get::<BackgroundColor>(button).unwrap().0;
+
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(background(&app), Color::WHITE);
+
+        app.world.entity_mut(button).insert(Disabled);
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(background(&app), Color::RED, "Should match once disabled");
+
+        app.world.entity_mut(button).remove::<Disabled>();
+        app.update();
+        app.update();
+        assert_eq!(
+            background(&app),
+            Color::WHITE,
+            "Should match once enabled again"
+        );
+    }
+
+    #[test]
+    fn select_root() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let sheet = assets.add(StyleSheetAsset::parse(
+            "",
+            ":root { background-color: red; }",
+        ));
+        let other_sheet = assets.add(StyleSheetAsset::parse("", ""));
+
+        let child = app.world.spawn(NodeBundle::default()).id();
+        let nested_root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(other_sheet)))
+            .id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .push_children(&[child, nested_root])
+            .id();
+
+        app.update();
+        app.update();
+
+        let background = |e| app.world.get::<BackgroundColor>(e).unwrap().0;
+        assert_eq!(background(root), Color::RED);
+        assert_eq!(
+            background(child),
+            Color::NONE,
+            "Descendants shouldn't match"
+        );
+        assert_eq!(
+            background(nested_root),
+            Color::NONE,
+            "Only the root being applied should match"
+        );
+    }
+
+    #[test]
+    fn select_custom_pseudo_class() {
+        use crate::RegisterPseudoClass;
+
+        #[derive(Component)]
+        struct Selected;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ))
+        .register_pseudo_class("selected", (0, 3, 0), |world, entity| {
+            world.get::<Selected>(entity).is_some()
+        });
+
+        // Registered weight makes the first rule more specific than the second one
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse(
+            "",
+            ".button:selected { background-color: red; } .button.a.b { background-color: white; }",
+        ));
+
+        let selected = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("button a b"), Selected))
+            .id();
+        let unselected = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("button a b")))
+            .id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .push_children(&[selected, unselected])
+            .id();
+
+        let background = |app: &App, e| app.world.get::<BackgroundColor>(e).unwrap().0;
+
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(background(&app, selected), Color::RED);
+        assert_eq!(background(&app, unselected), Color::WHITE);
+
+        app.world.entity_mut(unselected).insert(Selected);
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+        app.update();
+        assert_eq!(
+            background(&app, unselected),
+            Color::RED,
+            "Should match once selected and refreshed"
+        );
+    }
+
+    #[test]
+    fn mark_applied_style_sheet() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", "node { width: 10px; }"));
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .id();
+
+        assert!(app.world.get::<StyleSheetApplied>(root).is_none());
+
+        app.update();
+        assert!(
+            app.world.get::<StyleSheetApplied>(root).is_some(),
+            "Should be applied after a single update"
+        );
+
+        app.world.entity_mut(root).remove::<StyleSheet>();
+        app.update();
+        assert!(
+            app.world.get::<StyleSheetApplied>(root).is_none(),
+            "Should be removed along with style sheet"
+        );
+    }
+
+    #[test]
+    fn select_nth_of_type() {
+        let (app, [first_text, first_button, second_text, second_button, third_button]) =
+            crate::apply_test_sheet(
+                "button:nth-of-type(2) { width: 10px; } .item:nth-of-type(1) { height: 10px; }",
+                |world, root| {
+                    let children = [
+                        world
+                            .spawn((TextBundle::default(), Class::new("item")))
+                            .id(),
+                        world.spawn(ButtonBundle::default()).id(),
+                        world
+                            .spawn((TextBundle::default(), Class::new("item")))
+                            .id(),
+                        world.spawn(ButtonBundle::default()).id(),
+                        world.spawn(ButtonBundle::default()).id(),
+                    ];
+                    world.entity_mut(root).push_children(&children);
+                    children
+                },
+            );
+
+        let width = |e| app.world.get::<Style>(e).unwrap().width;
+        assert_eq!(width(second_button), Val::Px(10.0));
+        for e in [first_text, first_button, second_text, third_button] {
+            assert_eq!(width(e), Val::Auto, "Only the second button should match");
+        }
+
+        let height = |e| app.world.get::<Style>(e).unwrap().height;
+        assert_eq!(
+            height(first_text),
+            Val::Px(10.0),
+            "Without a component, all siblings are counted"
+        );
+        assert_eq!(height(second_text), Val::Auto);
+    }
+
+    #[test]
+    fn scroll_children_by_scroll_position() {
+        let (mut app, (list, item)) =
+            crate::apply_test_sheet(".list { scroll-y: 20px; }", |world, root| {
+                let item = world.spawn(NodeBundle::default()).id();
+                let list = world
+                    .spawn((NodeBundle::default(), Class::new("list")))
+                    .add_child(item)
+                    .id();
+                world.entity_mut(root).add_child(list);
+                (list, item)
+            });
+
+        let translation = |app: &App| app.world.get::<Transform>(item).unwrap().translation;
+        assert_eq!(
+            app.world.get::<ScrollPosition>(list),
+            Some(&ScrollPosition {
+                offset_x: 0.0,
+                offset_y: 20.0
+            })
+        );
+        assert_eq!(translation(&app).y, -20.0);
+
+        app.update();
+        assert_eq!(
+            translation(&app).y,
+            -20.0,
+            "Offset should be applied only once"
+        );
+
+        app.world.get_mut::<ScrollPosition>(list).unwrap().offset_y = 30.0;
+        app.update();
+        assert_eq!(translation(&app).y, -30.0);
+
+        // Simulates a layout update, which resets the translation
+        app.world.get_mut::<Transform>(item).unwrap().translation.y = 5.0;
+        app.update();
+        assert_eq!(translation(&app).y, -25.0);
+    }
+
+    #[test]
+    fn select_nth_of_type_zero() {
+        let mut world = World::new();
+        let child = world.spawn_empty().id();
+        world.spawn_empty().add_child(child);
+
+        let (filtered, _) =
+            get_entities_with_pseudo_class_nth_of_type(&world, 0, None, smallvec::smallvec![child]);
+        assert!(filtered.is_empty(), "Indices starts at 1");
+
+        let (filtered, _) =
+            get_entities_with_pseudo_class_nth_of_type(&world, 1, None, smallvec::smallvec![child]);
+        assert_eq!(filtered.0.into_vec(), [child]);
+    }
+
+    #[test]
+    fn reselect_only_changed_root() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", "node { width: 10px; }"));
+
+        let a = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet.clone())))
+            .id();
+        let b = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .id();
+
+        // Let the style sheets be reapplied due to asset added event
+        app.update();
+        app.update();
+
+        for root in [a, b] {
+            assert_eq!(app.world.get::<Style>(root).unwrap().width, Val::Px(10.0));
+            app.world.get_mut::<Style>(root).unwrap().width = Val::Auto;
+        }
+
+        // Only the first root is touched, so the second one must not be reapplied.
+        app.world.get_mut::<StyleSheet>(a).unwrap().refresh();
+        app.update();
+
+        assert_eq!(app.world.get::<Style>(a).unwrap().width, Val::Px(10.0));
+        assert_eq!(app.world.get::<Style>(b).unwrap().width, Val::Auto);
+
+        let state = app.world.resource::<StyleSheetState>();
+        assert_eq!(
+            state.keys().copied().collect::<Vec<_>>(),
+            vec![a, b],
+            "Both roots should be kept on state"
+        );
+    }
+
+    #[test]
+    fn later_sheets_override_earlier_ones() {
+        for load_first_sheet_first in [true, false] {
+            let mut app = App::new();
+            app.add_plugins((
+                MinimalPlugins,
+                AssetPlugin::default(),
+                EcssPlugin::default(),
+            ));
+
+            let (first, second) = {
+                let assets = app.world.resource::<Assets<StyleSheetAsset>>();
+                (assets.reserve_handle(), assets.reserve_handle())
+            };
+
+            let root = app
+                .world
+                .spawn((
+                    NodeBundle::default(),
+                    StyleSheet::from_handles(vec![first.clone(), second.clone()]),
+                ))
+                .id();
+
+            let mut sheets = vec![
+                (first, StyleSheetAsset::parse("", "node { width: 10px; }")),
+                (second, StyleSheetAsset::parse("", "node { width: 20px; }")),
+            ];
+
+            if !load_first_sheet_first {
+                sheets.reverse();
+            }
+
+            for (handle, sheet) in sheets {
+                app.world
+                    .resource_mut::<Assets<StyleSheetAsset>>()
+                    .insert(handle, sheet);
+                app.update();
+                app.update();
+            }
+
+            assert_eq!(
+                app.world.get::<Style>(root).unwrap().width,
+                Val::Px(20.0),
+                "Second sheet should always win"
+            );
+        }
+    }
+
+    #[test]
+    fn higher_priority_sheet_wins() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let (base, screen) = {
+            let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+            (
+                assets.add(StyleSheetAsset::parse("", "node { width: 10px; }")),
+                assets.add(StyleSheetAsset::parse("", "node { width: 20px; }")),
+            )
+        };
+
+        // The base theme is pushed last, but it has a lower priority
+        let mut sheet = StyleSheet::new(screen);
+        sheet.push_handle_with_priority(base, -1);
+
+        let root = app.world.spawn((NodeBundle::default(), sheet)).id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(root).unwrap().width,
+            Val::Px(20.0),
+            "Higher priority sheet should win"
+        );
+    }
+
+    #[test]
+    fn order_rules_by_specificity() {
+        let classes = (0..11).map(|i| format!("c{i}")).collect::<Vec<_>>();
+        let (app, root) = crate::apply_test_sheet(
+            &format!(
+                "#target {{ width: 20px; }} .{} {{ width: 10px; }}",
+                classes.join(".")
+            ),
+            |world, root| {
+                world
+                    .entity_mut(root)
+                    .insert((Name::new("target"), Class::new(classes.join(" "))));
+                root
+            },
+        );
+
+        assert_eq!(
+            app.world.get::<Style>(root).unwrap().width,
+            Val::Px(20.0),
+            "One id should win over eleven classes"
+        );
+    }
+
+    #[test]
+    fn break_specificity_ties_by_source_order() {
+        for (content, expected) in [
+            (".a { width: 10px; } .b { width: 20px; }", Val::Px(20.0)),
+            (".b { width: 20px; } .a { width: 10px; }", Val::Px(10.0)),
+        ] {
+            let (app, root) = crate::apply_test_sheet(content, |world, root| {
+                world.entity_mut(root).insert(Class::new("a b"));
+                root
+            });
+
+            assert_eq!(
+                app.world.get::<Style>(root).unwrap().width,
+                expected,
+                "Last rule should win on ({content})"
+            );
+        }
+    }
+
+    #[test]
+    fn reparse_modified_sheet() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::with_hot_reload(),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", "node { width: red; }"));
+
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet.clone())))
+            .id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.get::<Style>(root).unwrap().width, Val::Auto);
+
+        app.world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .insert(sheet, StyleSheetAsset::parse("", "node { width: 10px; }"));
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(root).unwrap().width,
+            Val::Px(10.0),
+            "Modified sheet should be parsed again"
+        );
+    }
+
+    #[test]
+    fn style_descendants_of_non_node_root() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse("", ".item { width: 10px; }"));
+
+        let child = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let nested = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        // Neither the root nor this group entity are `Node`s
+        let group = app.world.spawn(Class::new("item")).add_child(nested).id();
+        let root = app
+            .world
+            .spawn(StyleSheet::new(sheet))
+            .push_children(&[child, group])
+            .id();
+
+        app.update();
+        app.update();
+
+        for entity in [child, nested] {
+            assert_eq!(
+                app.world.get::<Style>(entity).unwrap().width,
+                Val::Px(10.0),
+                "Descendants should be styled even without a `Node` parent"
+            );
+        }
+        assert!(app.world.get::<Style>(group).is_none());
+        assert!(app.world.get::<Style>(root).is_none());
+    }
+
+    #[test]
+    fn descendant_root_sheet_overrides_ancestor() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let outer_sheet = assets.add(StyleSheetAsset::parse(
+            "",
+            ".item { width: 10px; } .item.wide { height: 30px; }",
+        ));
+        let inner_sheet = assets.add(StyleSheetAsset::parse(
+            "",
+            ".item { width: 20px; height: 5px; }",
+        ));
+
+        let item = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item wide")))
+            .id();
+        // Spawned before the outer root, so it has a lower entity index
+        let inner = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(inner_sheet.clone())))
+            .add_child(item)
+            .id();
+        let outer = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(outer_sheet.clone())))
+            .add_child(inner)
+            .id();
+        assert!(inner < outer);
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().width,
+            Val::Px(20.0),
+            "Descendant root sheet should be applied last"
+        );
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().height,
+            Val::Px(30.0),
+            "More specific rule should win over a descendant root sheet"
+        );
+
+        // Swap roots, so the outer root becomes the descendant one, without refreshing its sheets
+        app.world.entity_mut(inner).insert(NoAutoRestyle);
+        app.world.entity_mut(outer).insert(NoAutoRestyle);
+        app.world.entity_mut(inner).remove_parent();
+        app.world.entity_mut(outer).set_parent(inner);
+        app.world.entity_mut(item).set_parent(outer);
+
+        app.update();
+
+        let sheets = app
+            .world
+            .resource::<StyleSheetState>()
+            .sheets()
+            .map(|(id, _, _)| *id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sheets,
+            [inner_sheet.id(), outer_sheet.id()],
+            "Root depths should be updated when moved to another parent"
+        );
+    }
+
+    #[test]
+    fn revert_removed_style_sheet() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let (outer, inner) = {
+            let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+            (
+                assets.add(StyleSheetAsset::parse("", ".item { width: 10px; }")),
+                assets.add(StyleSheetAsset::parse(
+                    "",
+                    ".item { width: 20px; background-color: red; }",
+                )),
+            )
+        };
+
+        let item = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let kept = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let reverted_root = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                StyleSheet::new(inner.clone()).revert_on_removal(),
+            ))
+            .add_child(item)
+            .id();
+        let kept_root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(inner)))
+            .add_child(kept)
+            .id();
+        app.world
+            .spawn((NodeBundle::default(), StyleSheet::new(outer)))
+            .push_children(&[reverted_root, kept_root]);
+
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<BackgroundColor>(item).unwrap().0,
+            Color::RED
+        );
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(20.0));
+
+        app.world.entity_mut(reverted_root).remove::<StyleSheet>();
+        app.world.entity_mut(kept_root).remove::<StyleSheet>();
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<BackgroundColor>(item).unwrap().0,
+            Color::NONE,
+            "Should revert background color"
+        );
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().width,
+            Val::Px(10.0),
+            "Ancestor sheet should be reapplied"
+        );
+        assert_eq!(
+            app.world.get::<BackgroundColor>(kept).unwrap().0,
+            Color::RED,
+            "Should keep styles by default"
+        );
+
+        let state = app.world.resource::<StyleSheetState>();
+        assert!(!state.contains_key(&reverted_root));
+        assert!(!state.contains_key(&kept_root));
+    }
+
+    #[test]
+    fn select_with_system_param() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let first = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let nested = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item selected")))
+            .id();
+        let other = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("other")))
+            .add_child(nested)
+            .id();
+        let root = app
+            .world
+            .spawn(NodeBundle::default())
+            .push_children(&[first, other])
+            .id();
+        let outside = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+
+        let mut state = SystemState::<CssSelect>::new(&mut app.world);
+        let select = state.get(&app.world);
+
+        let mut selected = select.select(root, &Selector::parse(".item").unwrap());
+        selected.sort();
+        let mut expected = vec![first, nested];
+        expected.sort();
+        assert_eq!(selected, expected, "Should not select outside of root");
+
+        assert_eq!(
+            select.select(root, &Selector::parse(".other .selected").unwrap()),
+            vec![nested]
+        );
+        assert_eq!(
+            select.select(outside, &Selector::parse(".item").unwrap()),
+            vec![outside]
+        );
+        assert!(select
+            .select(root, &Selector::parse("button").unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn match_single_entity() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let button = app
+            .world
+            .spawn((ButtonBundle::default(), Class::new("item selected")))
+            .id();
+        let item = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let menu = app
+            .world
+            .spawn((NodeBundle::default(), Name::new("menu")))
+            .push_children(&[button, item])
+            .id();
+        let outside = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+
+        let matches = |selector: &str, entity| {
+            Selector::parse(selector)
+                .unwrap()
+                .matches_entity(&app.world, entity)
+        };
+
+        assert!(matches(".item", item));
+        assert!(matches("button.selected", button));
+        assert!(matches("#menu", menu));
+        assert!(matches("*", outside));
+        assert!(!matches("button", item));
+        assert!(!matches(".selected", item));
+        assert!(!matches("#other", menu));
+
+        assert!(matches("#menu .item", item));
+        assert!(matches("#menu button", button));
+        assert!(!matches("#menu .item", outside));
+        assert!(!matches(".item #menu", menu));
+
+        let mut state = SystemState::<CssSelect>::new(&mut app.world);
+        let select = state.get(&app.world);
+        assert!(select.matches(button, &Selector::parse("#menu .selected").unwrap()));
+        assert!(!select.matches(outside, &Selector::parse("#menu .item").unwrap()));
+    }
+
+    #[test]
+    fn select_direct_child() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let first = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let nested = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let group = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("group")))
+            .add_child(nested)
+            .id();
+        let menu = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("menu")))
+            .push_children(&[first, group])
+            .id();
+
+        let mut state = SystemState::<CssSelect>::new(&mut app.world);
+        let select = state.get(&app.world);
+        let selected = |selector: &str| {
+            let mut selected = select.select(menu, &Selector::parse(selector).unwrap());
+            selected.sort();
+            selected
+        };
+
+        let mut all = vec![first, nested];
+        all.sort();
+        assert_eq!(selected(".menu .item"), all);
+        assert_eq!(selected(".menu > .item"), vec![first]);
+        assert_eq!(selected(".menu > .group > .item"), vec![nested]);
+        assert_eq!(selected(".menu > .group .item"), vec![nested]);
+
+        assert!(select.matches(first, &Selector::parse(".menu > .item").unwrap()));
+        assert!(!select.matches(nested, &Selector::parse(".menu > .item").unwrap()));
+        assert!(select.matches(nested, &Selector::parse(".menu > .group .item").unwrap()));
+        assert!(select.matches(nested, &Selector::parse(".menu .group > .item").unwrap()));
+    }
+
+    #[test]
+    fn select_by_attribute() {
+        let (mut app, (pressed, released)) =
+            crate::apply_test_sheet("[interaction=pressed] { width: 10px; }", |world, root| {
+                let pressed = world
+                    .spawn((NodeBundle::default(), Interaction::Pressed))
+                    .set_parent(root)
+                    .id();
+                let released = world
+                    .spawn((NodeBundle::default(), Interaction::None))
+                    .set_parent(root)
+                    .id();
+                (pressed, released)
+            });
+
+        assert_eq!(
+            app.world.get::<Style>(pressed).unwrap().width,
+            Val::Px(10.0)
+        );
+        assert_eq!(app.world.get::<Style>(released).unwrap().width, Val::Auto);
+
+        // Changing the attribute component should reapply the style sheet
+        *app.world.get_mut::<Interaction>(released).unwrap() = Interaction::Pressed;
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(released).unwrap().width,
+            Val::Px(10.0)
+        );
+    }
+
+    #[test]
+    fn select_by_name_substring() {
+        let (app, [menu_item, close_button, unnamed]) = crate::apply_test_sheet(
+            r#"[name*="item"] { width: 10px; }
+                [name^=menu] { height: 20px; }
+                [name$=button] { left: 30px; }"#,
+            |world, root| {
+                let children = [
+                    world
+                        .spawn((NodeBundle::default(), Name::new("menu-item")))
+                        .id(),
+                    world
+                        .spawn((NodeBundle::default(), Name::new("close-button")))
+                        .id(),
+                    world.spawn(NodeBundle::default()).id(),
+                ];
+                world.entity_mut(root).push_children(&children);
+                children
+            },
+        );
+
+        let style = app.world.get::<Style>(menu_item).unwrap();
+        assert_eq!(style.width, Val::Px(10.0));
+        assert_eq!(style.height, Val::Px(20.0));
+        assert_eq!(style.left, Val::Auto);
+
+        let style = app.world.get::<Style>(close_button).unwrap();
+        assert_eq!(style.width, Val::Auto);
+        assert_eq!(style.height, Val::Auto);
+        assert_eq!(style.left, Val::Px(30.0));
+
+        let style = app.world.get::<Style>(unnamed).unwrap();
+        assert_eq!(style.width, Val::Auto);
+        assert_eq!(style.height, Val::Auto);
+        assert_eq!(style.left, Val::Auto);
+    }
+
+    #[test]
+    fn query_selected_entities() {
+        // Selection must be retained after the frame it was applied
+        let (app, (root, first, second)) =
+            crate::apply_test_sheet(".item { width: 10px; }", |world, root| {
+                let first = world
+                    .spawn((NodeBundle::default(), Class::new("item")))
+                    .set_parent(root)
+                    .id();
+                let second = world
+                    .spawn((NodeBundle::default(), Class::new("item")))
+                    .set_parent(root)
+                    .id();
+                (root, first, second)
+            });
+
+        let query = app.world.resource::<StyleQuery>();
+        let selected = query.selected(root, ".item");
+        assert_eq!(selected.len(), 2);
+        assert!(selected.contains(&first));
+        assert!(selected.contains(&second));
+        assert_eq!(query.matches(".item").count(), 2);
+        assert!(query.selected(root, ".other").is_empty());
+    }
+
+    #[test]
+    fn select_by_media_query() {
+        let (mut app, (window, item)) = crate::apply_test_sheet(
+            ".item { width: 10px; } @media (max-width: 800px) { .item { width: 20px; } }",
+            |world, root| {
+                let window = world
+                    .spawn((
+                        Window {
+                            resolution: (1000.0, 600.0).into(),
+                            ..Default::default()
+                        },
+                        PrimaryWindow,
+                    ))
+                    .id();
+                let item = world
+                    .spawn((NodeBundle::default(), Class::new("item")))
+                    .set_parent(root)
+                    .id();
+                (window, item)
+            },
+        );
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(10.0));
+
+        // Crossing the breakpoint should reapply the style sheet
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(700.0, 600.0);
+        app.update();
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(20.0));
+
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(900.0, 600.0);
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(10.0));
+    }
+
+    #[test]
+    fn refresh_viewport_units_on_resize() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let window = app
+            .world
+            .spawn((
+                Window {
+                    resolution: (1000.0, 600.0).into(),
+                    ..Default::default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let viewport_sheet = assets.add(StyleSheetAsset::parse("", ".item { width: 50vw; }"));
+        let fixed_sheet = assets.add(StyleSheetAsset::parse("", ".item { width: 10px; }"));
+
+        let mut spawn_item = |sheet| {
+            let item = app
+                .world
+                .spawn((NodeBundle::default(), Class::new("item")))
+                .id();
+            app.world
+                .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+                .add_child(item);
+            item
+        };
+        let viewport_item = spawn_item(viewport_sheet.clone());
+        let fixed_item = spawn_item(fixed_sheet);
+        let no_restyle_item = spawn_item(viewport_sheet);
+        let no_restyle_root = app.world.get::<Parent>(no_restyle_item).unwrap().get();
+        app.world.entity_mut(no_restyle_root).insert(NoAutoRestyle);
+
+        app.update();
+        app.update();
+
+        for item in [viewport_item, fixed_item, no_restyle_item] {
+            app.world.get_mut::<Style>(item).unwrap().width = Val::Auto;
+        }
+
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(800.0, 600.0);
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(viewport_item).unwrap().width,
+            Val::Vw(50.0),
+            "Sheet with viewport units should be refreshed"
+        );
+        assert_eq!(
+            app.world.get::<Style>(fixed_item).unwrap().width,
+            Val::Auto,
+            "Sheet without viewport units shouldn't be refreshed"
+        );
+        assert_eq!(
+            app.world.get::<Style>(no_restyle_item).unwrap().width,
+            Val::Auto,
+            "Sheet with NoAutoRestyle shouldn't be refreshed"
+        );
+    }
+
+    #[test]
+    fn apply_default_style_sheet() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let reset = assets.add(StyleSheetAsset::parse(
+            "reset.css",
+            "node { width: 10px; height: 10px; }",
+        ));
+        let sheet = assets.add(StyleSheetAsset::parse("sheet.css", "node { width: 20px; }"));
+        app.world.insert_resource(DefaultStyleSheet::new(reset));
+
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .id();
+
+        app.update();
+        app.update();
+
+        let style = app.world.get::<Style>(root).unwrap();
+        assert_eq!(
+            style.height,
+            Val::Px(10.0),
+            "Default style sheet should apply on roots which didn't list it"
+        );
+        assert_eq!(
+            style.width,
+            Val::Px(20.0),
+            "Root style sheet should override the default style sheet"
+        );
+    }
+
+    #[test]
+    fn apply_global_style_sheet() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let mut assets = app.world.resource_mut::<Assets<StyleSheetAsset>>();
+        let global = assets.add(StyleSheetAsset::parse(
+            "global.css",
+            ".item { width: 10px; height: 10px; }",
+        ));
+        let sheet = assets.add(StyleSheetAsset::parse(
+            "sheet.css",
+            ".item { width: 20px; }",
+        ));
+        app.world.insert_resource(GlobalStyleSheet::new(global));
+
+        let unparented = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        let child = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+        app.world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .add_child(child);
+
+        app.update();
+        app.update();
+
+        let style = |app: &App, e| app.world.get::<Style>(e).unwrap().clone();
+        assert_eq!(style(&app, unparented).width, Val::Px(10.0));
+        assert_eq!(style(&app, child).height, Val::Px(10.0));
+        assert_eq!(
+            style(&app, child).width,
+            Val::Px(20.0),
+            "Root style sheet should override the global style sheet"
+        );
+
+        let spawned = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            style(&app, spawned).width,
+            Val::Px(10.0),
+            "Nodes spawned later should also be styled"
+        );
+    }
+
+    #[test]
+    fn refresh_on_class_change() {
+        let (mut app, item) =
+            crate::apply_test_sheet(".selected { width: 10px; }", |world, root| {
+                let item = world.spawn(NodeBundle::default()).id();
+                world
+                    .spawn(NodeBundle::default())
+                    .set_parent(root)
+                    .add_child(item);
+                item
+            });
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Auto);
+
+        // Item wasn't matched before, so it isn't tracked yet
+        app.world
+            .entity_mut(item)
+            .insert(Class::new("item selected"));
+        app.update();
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(10.0));
+
+        app.world.get_mut::<Style>(item).unwrap().width = Val::Auto;
+        app.world.get_mut::<Class>(item).unwrap().remove("item");
+        app.update();
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().width,
+            Val::Px(10.0),
+            "Changing any class should reapply the sheet"
+        );
+    }
+
+    #[test]
+    fn refresh_on_new_children() {
+        let (mut app, (root, unstyled_root)) =
+            crate::apply_test_sheet(".item { width: 10px; }", |world, root| {
+                (root, world.spawn(NodeBundle::default()).id())
+            });
+
+        let item = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .set_parent(root)
+            .id();
+        let outside = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("item")))
+            .set_parent(unstyled_root)
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().width,
+            Val::Px(10.0),
+            "New child should be styled without a manual refresh"
+        );
+        assert_eq!(app.world.get::<Style>(outside).unwrap().width, Val::Auto);
+
+        // Nothing changed, so the root shouldn't be refreshed again
+        app.world.get_mut::<Style>(item).unwrap().width = Val::Auto;
+        app.update();
+        assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Auto);
+
+        app.world.entity_mut(item).remove_parent();
+        app.update();
+        assert_eq!(
+            app.world.get::<Style>(item).unwrap().width,
+            Val::Auto,
+            "Removed child shouldn't be styled"
+        );
+        assert!(app
+            .world
+            .resource::<StyleSheetState>()
+            .sheets()
+            .all(|(_, tracked, _)| tracked.values().all(|e| !e.contains(&item))));
+    }
+
+    #[test]
+    fn cut_glyphs_for_ellipsis() {
+        let widths = [10.0, 10.0, 10.0, 10.0, 10.0];
+
+        assert_eq!(ellipsis_cut(&widths, 50.0, 10.0), None, "All glyphs fits");
+        assert_eq!(ellipsis_cut(&widths, 100.0, 10.0), None);
+        assert_eq!(ellipsis_cut(&widths, 45.0, 10.0), Some(3));
+        assert_eq!(ellipsis_cut(&widths, 40.0, 10.0), Some(3));
+        assert_eq!(ellipsis_cut(&[10.0, 25.0, 5.0], 30.0, 8.0), Some(1));
+        assert_eq!(
+            ellipsis_cut(&widths, 5.0, 10.0),
+            Some(0),
+            "Only the ellipsis is kept"
+        );
+    }
+
+    #[test]
+    fn find_glyph_after_lines() {
+        // Three lines with two glyphs each, where glyphs on the same line have slightly different centers
+        let rows = [
+            (10.0, 20.0),
+            (12.0, 16.0),
+            (40.0, 20.0),
+            (38.0, 16.0),
+            (70.0, 20.0),
+            (70.0, 20.0),
+        ];
+
+        assert_eq!(first_glyph_after_lines(&rows, 1), Some(2));
+        assert_eq!(first_glyph_after_lines(&rows, 2), Some(4));
+        assert_eq!(first_glyph_after_lines(&rows, 3), None, "All lines fits");
+        assert_eq!(first_glyph_after_lines(&[], 1), None);
+    }
+
+    #[test]
+    fn detect_unknown_properties() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet = StyleSheetAsset::parse(
+            "",
+            ".a { backgruond-color: red; width: 10px; } .b { backgruond-color: blue; }",
+        );
+
+        let registered = app.world.resource::<RegisteredProperties>();
+        assert!(registered.contains("background-color"));
+        assert_eq!(
+            registered.unknown_properties(&sheet),
+            ["backgruond-color"],
+            "Unknown property should be reported exactly once"
+        );
+    }
+
+    #[test]
+    fn spawn_pseudo_elements() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet = app
+            .world
+            .resource_mut::<Assets<StyleSheetAsset>>()
+            .add(StyleSheetAsset::parse(
+                "",
+                r#".icon::before { content: "A"; } .icon::after { content: "Z"; } text { font-size: 5px; }"#,
+            ));
+
+        let label = app
+            .world
+            .spawn(TextBundle::from_section("label", default()))
+            .id();
+        let icon = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("icon")))
+            .add_child(label)
+            .id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet)))
+            .add_child(icon)
+            .id();
+
+        app.update();
+
+        let children = app.world.get::<Children>(icon).unwrap().to_vec();
+        assert_eq!(children.len(), 3, "Should spawn both pseudo-elements");
+
+        let (before, after) = (children[0], children[2]);
+        assert_eq!(
+            app.world.get::<PseudoElementNode>(before),
+            Some(&PseudoElementNode(PseudoElement::Before))
+        );
+        assert_eq!(
+            app.world.get::<Text>(before).unwrap().sections[0].value,
+            "A"
+        );
+        assert_eq!(app.world.get::<Text>(after).unwrap().sections[0].value, "Z");
+        assert_eq!(
+            app.world.get::<Text>(before).unwrap().sections[0]
+                .style
+                .font_size,
+            TextStyle::default().font_size,
+            "Regular selectors should not match pseudo-elements"
+        );
+
+        // Reapplying should reuse existing entities
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+        assert_eq!(app.world.get::<Children>(icon).unwrap().to_vec(), children);
+
+        // Pseudo-elements should be removed when rule no longer matches
+        app.world.get_mut::<Class>(icon).unwrap().remove("icon");
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+
+        assert_eq!(app.world.get::<Children>(icon).unwrap().to_vec(), [label]);
+        assert!(app.world.get_entity(before).is_none());
+        assert!(app.world.get_entity(after).is_none());
+    }
+
+    #[test]
+    fn cache_component_entities() {
+        let mut world = World::new();
+        let mut registry = ComponentFilterRegistry::default();
+        registry.insert_component(
+            "text",
+            Box::new(SystemState::<Query<Entity, With<Text>>>::new(&mut world)),
+        );
+
+        let text = world.spawn(Text::default()).id();
+        let other_text = world.spawn(Text::default()).id();
+        let node = world.spawn(Node::default()).id();
+        let select = |registry: &ComponentFilterRegistry, entities: &[Entity]| {
+            let (filtered, _) =
+                get_entities_with_component("text", &world, registry, entities.into());
+            filtered.0.into_vec()
+        };
+
+        registry.enable_cache();
+        assert_eq!(select(&registry, &[text, node]), [text]);
+        assert_eq!(select(&registry, &[node, other_text]), [other_text]);
+        assert_eq!(
+            registry.take_cache().unwrap().collected,
+            1,
+            "Entities should be collected once for repeated selectors"
+        );
+
+        assert_eq!(
+            select(&registry, &[text, node]),
+            [text],
+            "Should filter without cache"
+        );
+        assert!(registry.take_cache().is_none());
+    }
+
+    #[test]
+    fn skip_pseudo_elements_on_descendant_selectors() {
+        let (mut app, (root, icon, label)) = crate::apply_test_sheet(
+            r#".icon::before { content: "A"; } .icon text { font-size: 5px; } .icon > text { color: red; }"#,
+            |world, root| {
+                let label = world
+                    .spawn(TextBundle::from_section("label", default()))
+                    .id();
+                let icon = world
+                    .spawn((NodeBundle::default(), Class::new("icon")))
+                    .set_parent(root)
+                    .add_child(label)
+                    .id();
+                (root, icon, label)
+            },
+        );
+
+        // Pseudo-elements already exists when the sheet is applied again
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+        app.update();
+
+        let before = app.world.get::<Children>(icon).unwrap()[0];
+        assert!(app.world.get::<PseudoElementNode>(before).is_some());
+
+        let style = &app.world.get::<Text>(label).unwrap().sections[0].style;
+        assert_eq!(style.font_size, 5.0);
+        assert_eq!(style.color, Color::RED);
+
+        let style = &app.world.get::<Text>(before).unwrap().sections[0].style;
+        assert_eq!(
+            style.font_size,
+            TextStyle::default().font_size,
+            "Descendant selectors should not match pseudo-elements"
+        );
+        assert_eq!(
+            style.color,
+            TextStyle::default().color,
+            "Child selectors should not match pseudo-elements"
+        );
+    }
+
+    #[test]
+    fn skip_auto_restyle() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            EcssPlugin::default(),
+        ));
+
+        let sheet =
+            app.world
+                .resource_mut::<Assets<StyleSheetAsset>>()
+                .add(StyleSheetAsset::parse(
+                    "",
+                    ".a { width: 10px; } .b { width: 20px; }",
+                ));
+
+        let child = app
+            .world
+            .spawn((NodeBundle::default(), Class::new("a")))
+            .id();
+        let root = app
+            .world
+            .spawn((NodeBundle::default(), StyleSheet::new(sheet), NoAutoRestyle))
+            .add_child(child)
+            .id();
+
+        // Sheet is applied again once its asset added event is processed,
+        // so wait until there are no pending changes on state
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Style>(child).unwrap().width, Val::Px(10.0));
+
+        // Changing a tracked component should not reapply the sheet
+        app.world.get_mut::<Class>(child).unwrap().set("b");
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Style>(child).unwrap().width, Val::Px(10.0));
+
+        // Only a manual refresh reapplies the sheet
+        app.world.get_mut::<StyleSheet>(root).unwrap().refresh();
+        app.update();
+        assert_eq!(app.world.get::<Style>(child).unwrap().width, Val::Px(20.0));
     }
 }